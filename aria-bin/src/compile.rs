@@ -0,0 +1,76 @@
+// SPDX-License-Identifier: Apache-2.0
+use std::{collections::HashSet, path::PathBuf};
+
+use aria_compiler::module_bundle::{MODULE_BUNDLE_EXTENSION, ModuleBundle};
+use aria_parser::ast::SourceBuffer;
+use haxby_vm::vm::VirtualMachine;
+
+use crate::{
+    Args,
+    error_reporting::{print_report_from_compiler_warning, print_report_from_vm_error},
+    file_eval::compile_buffer,
+};
+
+// compiles the program at path, and every module it transitively imports,
+// into a single bundle that can be run by passing it to aria in place of a source file
+pub(crate) fn compile_bundle(path: &str, output: Option<&str>, args: &Args) -> i32 {
+    let src = match SourceBuffer::file(path) {
+        Ok(src) => src,
+        Err(err) => {
+            println!("error reading source file: {err}");
+            return 1;
+        }
+    };
+
+    let Ok(entry) = compile_buffer(src, args) else {
+        return 1;
+    };
+    entry
+        .warnings
+        .iter()
+        .for_each(print_report_from_compiler_warning);
+
+    let mut bundle = ModuleBundle::default();
+    let mut seen = HashSet::new();
+    let mut pending: Vec<(String, Option<PathBuf>)> = entry
+        .imported_paths()
+        .into_iter()
+        .map(|ipath| (ipath, entry.widget_root_path.clone()))
+        .collect();
+    bundle.entry = entry;
+
+    while let Some((ipath, widget_root_path)) = pending.pop() {
+        if !seen.insert(ipath.clone()) {
+            continue;
+        }
+
+        let module = match VirtualMachine::compile_imported_module(
+            &ipath,
+            widget_root_path.as_ref(),
+            !args.no_module_cache,
+        ) {
+            Ok(module) => module,
+            Err(err) => {
+                print_report_from_vm_error(&err.into());
+                return 1;
+            }
+        };
+
+        for next in module.imported_paths() {
+            pending.push((next, module.widget_root_path.clone()));
+        }
+        bundle.imports.push((ipath, module));
+    }
+
+    let output = match output {
+        Some(output) => PathBuf::from(output),
+        None => PathBuf::from(path).with_extension(MODULE_BUNDLE_EXTENSION),
+    };
+    match std::fs::write(&output, bundle.to_bytes()) {
+        Ok(_) => 0,
+        Err(err) => {
+            println!("error writing bundle {}: {err}", output.display());
+            1
+        }
+    }
+}
@@ -1,11 +1,11 @@
 // SPDX-License-Identifier: Apache-2.0
 use std::{collections::HashMap, vec};
 
-use aria_compiler::do_compile::CompilationError;
+use aria_compiler::do_compile::{CompilationError, CompilationWarning};
 use aria_parser::ast::{ParserError, SourcePointer};
 use ariadne::{Color, Label, Report, ReportKind, Source};
 use haxby_vm::{
-    error::{exception::VmException, vm_error::VmError},
+    error::{backtrace::Backtrace, exception::VmException, vm_error::VmError},
     vm::VirtualMachine,
 };
 
@@ -38,16 +38,45 @@ pub(crate) type PrintableReport<'a> = (
 fn build_report_from_msg_and_location<'a>(
     msg: &str,
     locations: Vec<SourcePointer>,
+) -> PrintableReport<'a> {
+    build_report_of_kind(ReportKind::Error, msg, locations, None)
+}
+
+// tail calls reuse their caller's frame, so the backtrace cannot show those
+// callers; the report says how many are missing instead
+fn build_report_from_msg_and_backtrace<'a>(
+    msg: &str,
+    backtrace: &Backtrace,
+) -> PrintableReport<'a> {
+    let locations = backtrace.entries_iter().cloned().collect();
+    let note = match backtrace.elided_frames() {
+        0 => None,
+        1 => Some("1 frame elided by tail calls".to_owned()),
+        n => Some(format!("{n} frames elided by tail calls")),
+    };
+    build_report_of_kind(ReportKind::Error, msg, locations, note)
+}
+
+fn build_report_of_kind<'a>(
+    kind: ReportKind<'a>,
+    msg: &str,
+    locations: Vec<SourcePointer>,
+    note: Option<String>,
 ) -> PrintableReport<'a> {
     let config = ariadne::Config::default().with_index_type(ariadne::IndexType::Byte);
     let magenta = Color::Magenta;
-    let mut report = Report::build(ReportKind::Error, ("unknown".to_owned(), 0..0))
-        .with_message(msg)
-        .with_config(config);
+    let mut message = msg.to_owned();
+    let mut labels = vec![];
     let mut cache = StringCache::default();
     for (idx, loc) in locations.iter().enumerate() {
         let loc = loc.clone();
-        report = report.with_label(
+        // bundled modules do not carry their sources, so there is nothing to
+        // point into; name the line instead
+        if !loc.buffer.has_content() {
+            message.push_str(&format!("\n  at {loc}"));
+            continue;
+        }
+        labels.push(
             Label::new((
                 loc.buffer.name.clone(),
                 loc.location.start..loc.location.stop,
@@ -63,6 +92,21 @@ fn build_report_from_msg_and_location<'a>(
             );
         }
     }
+    // ariadne prints notes below the source, so without any they go in the message
+    let note = match note {
+        Some(note) if labels.is_empty() => {
+            message.push_str(&format!("\n  {note}"));
+            None
+        }
+        note => note,
+    };
+    let mut report = Report::build(kind, ("unknown".to_owned(), 0..0))
+        .with_message(message)
+        .with_config(config)
+        .with_labels(labels);
+    if let Some(note) = note {
+        report = report.with_note(note);
+    }
     (report.finish(), cache)
 }
 
@@ -76,6 +120,11 @@ pub(crate) fn print_report_from_compiler_error(err: &CompilationError) {
     report.eprint(cache).unwrap();
 }
 
+pub(crate) fn print_report_from_compiler_warning(warning: &CompilationWarning) {
+    let (report, cache) = build_report_from_compiler_warning(warning);
+    report.eprint(cache).unwrap();
+}
+
 pub(crate) fn print_report_from_parser_error(err: &ParserError) {
     let (report, cache) = build_report_from_parser_error(err);
     report.eprint(cache).unwrap();
@@ -90,13 +139,12 @@ pub(crate) fn build_report_from_vm_error<'a>(err: &VmError) -> PrintableReport<'
     let msg = err.reason.to_string();
     if err.backtrace.is_empty() {
         if let Some(loc) = &err.loc {
-            build_report_from_msg_and_location(&msg, vec![loc.clone()])
+            build_report_from_msg_and_location(&msg, vec![loc.as_ref().clone()])
         } else {
             build_report_from_msg_and_location(&msg, vec![])
         }
     } else {
-        let backtraces: Vec<_> = err.backtrace.entries_iter().cloned().collect();
-        build_report_from_msg_and_location(&msg, backtraces)
+        build_report_from_msg_and_backtrace(&msg, &err.backtrace)
     }
 }
 
@@ -106,8 +154,7 @@ pub(crate) fn build_report_from_vm_exception<'a>(
 ) -> PrintableReport<'a> {
     let mut cur_frame = Default::default();
     let msg = exc.value.prettyprint(&mut cur_frame, vm);
-    let backtraces: Vec<_> = exc.backtrace.entries_iter().cloned().collect();
-    build_report_from_msg_and_location(&msg, backtraces)
+    build_report_from_msg_and_backtrace(&msg, &exc.backtrace)
 }
 
 pub(crate) fn build_report_from_compiler_error<'a>(
@@ -118,6 +165,14 @@ pub(crate) fn build_report_from_compiler_error<'a>(
     build_report_from_msg_and_location(&msg, vec![loc.clone()])
 }
 
+pub(crate) fn build_report_from_compiler_warning<'a>(
+    warning: &'a CompilationWarning,
+) -> PrintableReport<'a> {
+    let msg = warning.reason.to_string();
+    let loc = &warning.loc;
+    build_report_of_kind(ReportKind::Warning, &msg, vec![loc.clone()], None)
+}
+
 pub(crate) fn build_report_from_parser_error<'a>(err: &'a ParserError) -> PrintableReport<'a> {
     let msg = &err.msg;
     let loc = &err.loc;
@@ -1,5 +1,7 @@
 // SPDX-License-Identifier: Apache-2.0
-use aria_compiler::{CompilationOptions, compile_from_ast};
+use aria_compiler::{
+    CompilationOptions, compile_from_ast, module::CompiledModule, module_bundle::ModuleBundle,
+};
 use aria_parser::ast::{
     SourceBuffer,
     prettyprint::{PrettyPrintable, printout_accumulator::PrintoutAccumulator},
@@ -13,8 +15,8 @@ use haxby_vm::{
 use crate::{
     Args,
     error_reporting::{
-        print_report_from_compiler_error, print_report_from_parser_error,
-        print_report_from_vm_error, print_report_from_vm_exception,
+        print_report_from_compiler_error, print_report_from_compiler_warning,
+        print_report_from_parser_error, print_report_from_vm_error, print_report_from_vm_exception,
     },
 };
 
@@ -23,21 +25,19 @@ impl From<&Args> for CompilationOptions {
         CompilationOptions {
             optimize: !value.disable_optimizer,
             dump_builder: value.dump_ir,
+            exhaustive_match: false,
         }
     }
 }
 
 // To permit return Err(report_blah(x)) where report_blah(x) -> ()
 #[allow(clippy::unit_arg)]
-fn eval_buffer(
-    sb: SourceBuffer,
-    vm: &mut VirtualMachine,
-    args: &Args,
-) -> Result<RuntimeModule, ()> {
+pub(crate) fn compile_buffer(sb: SourceBuffer, args: &Args) -> Result<CompiledModule, ()> {
     let ast = match source_to_ast(&sb) {
         Ok(ast) => ast,
-        Err(err) => {
-            return Err(print_report_from_parser_error(&err));
+        Err(errs) => {
+            errs.iter().for_each(print_report_from_parser_error);
+            return Err(());
         }
     };
 
@@ -49,14 +49,22 @@ fn eval_buffer(
 
     let comp_opts = CompilationOptions::from(args);
 
-    let c_module = match compile_from_ast(&ast, &comp_opts) {
-        Ok(module) => module,
+    match compile_from_ast(&ast, &comp_opts) {
+        Ok(module) => Ok(module),
         Err(err) => {
             err.iter().for_each(print_report_from_compiler_error);
-            return Err(());
+            Err(())
         }
-    };
+    }
+}
 
+// To permit return Err(report_blah(x)) where report_blah(x) -> ()
+#[allow(clippy::unit_arg)]
+fn eval_module(
+    c_module: CompiledModule,
+    vm: &mut VirtualMachine,
+    args: &Args,
+) -> Result<RuntimeModule, ()> {
     if args.dump_mod {
         let mod_buffer = PrintoutAccumulator::default();
         let output = c_module.prettyprint(mod_buffer).value();
@@ -70,7 +78,15 @@ fn eval_buffer(
         }
     };
 
-    let r_module = match vm.load_into_module("", r_module) {
+    // loading the module adds the names it uses that are not defined to its
+    // warnings, so these are only printed once it has been loaded
+    let load_result = vm.load_into_module("", r_module.clone());
+    r_module
+        .warnings()
+        .iter()
+        .for_each(print_report_from_compiler_warning);
+
+    let r_module = match load_result {
         Ok(rle) => match rle {
             haxby_vm::vm::RunloopExit::Ok(m) => m.module,
             haxby_vm::vm::RunloopExit::Exception(exc) => {
@@ -117,16 +133,40 @@ pub(crate) fn file_eval(path: &str, args: &Args) -> i32 {
         None
     };
 
-    let buffer = SourceBuffer::file(path);
-    let exit = match buffer {
-        Ok(src) => match eval_buffer(src, &mut vm, args) {
-            Ok(_) => 0,
-            Err(_) => 1,
+    let exit = match std::fs::read(path) {
+        Ok(data) if ModuleBundle::is_bundle(&data) => match ModuleBundle::from_bytes(&data) {
+            Ok(bundle) => {
+                let registered = bundle.imports.into_iter().try_for_each(|(ipath, module)| {
+                    vm.register_bundled_module(path, &ipath, module)
+                });
+                match registered {
+                    Ok(_) => match eval_module(bundle.entry, &mut vm, args) {
+                        Ok(_) => 0,
+                        Err(_) => 1,
+                    },
+                    Err(err) => {
+                        print_report_from_vm_error(&err.into());
+                        1
+                    }
+                }
+            }
+            Err(err) => {
+                println!("error reading bundle: {err}");
+                1
+            }
+        },
+        _ => match SourceBuffer::file(path) {
+            Ok(src) => {
+                match compile_buffer(src, args).and_then(|cm| eval_module(cm, &mut vm, args)) {
+                    Ok(_) => 0,
+                    Err(_) => 1,
+                }
+            }
+            Err(err) => {
+                println!("error reading source file: {err}");
+                1
+            }
         },
-        Err(err) => {
-            println!("error reading source file: {err}");
-            1
-        }
     };
 
     if let Some(guard) = guard
@@ -1,4 +1,5 @@
 // SPDX-License-Identifier: Apache-2.0
+mod compile;
 mod error_reporting;
 mod file_eval;
 mod repl_eval;
@@ -6,12 +7,14 @@ mod repl_eval;
 #[cfg(test)]
 mod test;
 
-use clap::Parser;
+use clap::{Parser, Subcommand};
 use haxby_vm::vm::{VirtualMachine, VmOptions};
 
 #[derive(Default, Parser, Debug)]
-#[command(author, name = "aria", version = env!("CARGO_PKG_VERSION"), about, trailing_var_arg = true)]
+#[command(author, name = "aria", version = env!("CARGO_PKG_VERSION"), about, trailing_var_arg = true, args_conflicts_with_subcommands = true)]
 struct Args {
+    #[command(subcommand)]
+    command: Option<Command>,
     /// The name of the program file to run
     path: Option<String>,
     /// The destination for the VM performance trace
@@ -37,6 +40,12 @@ struct Args {
     /// Turn off compile-time optimizations
     #[arg(long("disable-optimizer"))]
     disable_optimizer: bool,
+    /// Always compile imported modules from source, ignoring cached bytecode
+    #[arg(long("no-module-cache"))]
+    no_module_cache: bool,
+    /// How many values may be created before the cycle collector runs (0 disables it)
+    #[arg(long("gc-threshold"))]
+    gc_threshold: Option<usize>,
     #[arg(trailing_var_arg = true)]
     extra_args: Vec<String>,
     #[arg(long("print-lib-path"))]
@@ -46,6 +55,18 @@ struct Args {
     no_repl_preamble: bool,
 }
 
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Compile a program and all of its imports into a standalone bundle
+    Compile {
+        /// The name of the program file to compile
+        path: String,
+        /// The destination for the bundle (defaults to the program file with an .ariab extension)
+        #[arg(short, long)]
+        output: Option<String>,
+    },
+}
+
 impl From<&Args> for VmOptions {
     fn from(value: &Args) -> Self {
         let mut options = VmOptions::default();
@@ -59,6 +80,12 @@ impl From<&Args> for VmOptions {
         }
 
         options.vm_args = value.extra_args.clone();
+        if value.no_module_cache {
+            options.use_module_cache = false;
+        }
+        if let Some(gc_threshold) = value.gc_threshold {
+            options.gc_threshold = gc_threshold;
+        }
 
         options
     }
@@ -104,7 +131,9 @@ fn main_loop() -> i32 {
         return 1;
     }
 
-    if let Some(path) = &args.path {
+    if let Some(Command::Compile { path, output }) = &args.command {
+        compile::compile_bundle(path, output.as_deref(), &args)
+    } else if let Some(path) = &args.path {
         file_eval::file_eval(path, &args)
     } else {
         repl_eval::repl_eval(&args)
@@ -175,8 +175,9 @@ impl<'a> Repl<'a> {
         let sb = SourceBuffer::stdin_with_name(repl_module_preamble, "repl");
         let ast = match source_to_ast(&sb) {
             Ok(ast) => ast,
-            Err(err) => {
-                return Err(print_report_from_parser_error(&err));
+            Err(errs) => {
+                errs.iter().for_each(print_report_from_parser_error);
+                return Err(());
             }
         };
 
@@ -221,7 +222,7 @@ impl<'a> Repl<'a> {
     fn try_parse_source(
         &mut self,
         buffer: &SourceBuffer,
-    ) -> Result<aria_parser::ast::ParsedModule, aria_parser::ast::ParserError> {
+    ) -> aria_parser::ast::ParserResult<aria_parser::ast::ParsedModule> {
         source_to_ast(buffer)
     }
 
@@ -245,8 +246,10 @@ impl<'a> Repl<'a> {
 
         let mut ast = match parsed_source {
             Ok(ast) => ast,
-            Err(err) => {
-                return Err(self.print_error_report(build_report_from_parser_error(&err)));
+            Err(errs) => {
+                errs.iter()
+                    .for_each(|e| self.print_error_report(build_report_from_parser_error(e)));
+                return Err(());
             }
         };
 
@@ -286,7 +289,7 @@ impl<'a> Repl<'a> {
         };
 
         if r_module
-            .lift_all_symbols_from_other(&self.module, &self.vm)
+            .lift_all_symbols_from_other(&self.module, true, &self.vm)
             .is_err()
         {
             return Err(());
@@ -299,7 +302,7 @@ impl<'a> Repl<'a> {
                     let new_module = m.module;
                     let _ = self
                         .module
-                        .lift_all_symbols_from_other(&new_module, &self.vm);
+                        .lift_all_symbols_from_other(&new_module, true, &self.vm);
                     Ok(new_module)
                 }
                 haxby_vm::vm::RunloopExit::Exception(exc) => {
@@ -67,6 +67,15 @@ fn repl_can_call_functions() {
     run_passing_repl_line(&mut repl, "foo(12);", &["13"]);
 }
 
+#[test]
+fn repl_keeps_private_names_across_lines() {
+    let cmdline_options = Args::default();
+    let mut repl = build_test_repl(&cmdline_options);
+
+    run_passing_repl_line(&mut repl, "val _tmp = 1;", &[]);
+    run_passing_repl_line(&mut repl, "_tmp + 1;", &["2"]);
+}
+
 #[test]
 fn repl_can_define_structs() {
     let cmdline_options = Args::default();
@@ -352,3 +361,29 @@ for i in 0.to(5) { x += i; }
         &["i = 10"],
     );
 }
+
+#[test]
+fn repl_reports_frames_elided_by_tail_calls() {
+    let cmdline_options = Args::default();
+    let mut repl = build_test_repl(&cmdline_options);
+
+    run_passing_repl_line(
+        &mut repl,
+        "func fail(n) { if n == 0 { throw 1; } return fail(n - 1); }",
+        &[],
+    );
+    run_check_repl_line(
+        &mut repl,
+        "fail(5);",
+        false,
+        &["5 frames elided by tail calls"],
+        &[],
+    );
+    run_check_repl_line(
+        &mut repl,
+        "fail(1);",
+        false,
+        &["1 frame elided by tail calls"],
+        &[],
+    );
+}
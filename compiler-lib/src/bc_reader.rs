@@ -190,6 +190,11 @@ impl BytecodeReader {
                 .map_or(Err(DecodeError::InsufficientData), |b| {
                     Ok(Opcode::WriteIndex(b))
                 }),
+            haxby_opcodes::OPCODE_READ_SLICE => self
+                .read_u8()
+                .map_or(Err(DecodeError::InsufficientData), |b| {
+                    Ok(Opcode::ReadSlice(b))
+                }),
             haxby_opcodes::OPCODE_READ_ATTRIBUTE => self
                 .read_u16()
                 .map_or(Err(DecodeError::InsufficientData), |b| {
@@ -200,6 +205,16 @@ impl BytecodeReader {
                 .map_or(Err(DecodeError::InsufficientData), |b| {
                     Ok(Opcode::WriteAttribute(b))
                 }),
+            haxby_opcodes::OPCODE_TRY_READ_ATTRIBUTE => self
+                .read_u16()
+                .map_or(Err(DecodeError::InsufficientData), |b| {
+                    Ok(Opcode::TryReadAttribute(b))
+                }),
+            haxby_opcodes::OPCODE_TRY_READ_ATTRIBUTE_SYMBOL => self
+                .read_u32()
+                .map_or(Err(DecodeError::InsufficientData), |b| {
+                    Ok(Opcode::TryReadAttributeSymbol(b))
+                }),
             haxby_opcodes::OPCODE_READ_ATTRIBUTE_SYMBOL => self
                 .read_u32()
                 .map_or(Err(DecodeError::InsufficientData), |b| {
@@ -266,8 +281,30 @@ impl BytecodeReader {
             haxby_opcodes::OPCODE_CALL => self
                 .read_u8()
                 .map_or(Err(DecodeError::InsufficientData), |b| Ok(Opcode::Call(b))),
+            haxby_opcodes::OPCODE_TAIL_CALL => self
+                .read_u8()
+                .map_or(Err(DecodeError::InsufficientData), |b| {
+                    Ok(Opcode::TailCall(b))
+                }),
+            haxby_opcodes::OPCODE_CALL_NAMED => {
+                let arg0 = match self.read_u8() {
+                    Ok(b) => b,
+                    Err(_) => {
+                        return Err(DecodeError::InsufficientData);
+                    }
+                };
+                let arg1 = match self.read_u8() {
+                    Ok(b) => b,
+                    Err(_) => {
+                        return Err(DecodeError::InsufficientData);
+                    }
+                };
+                Ok(Opcode::CallNamed(arg0, arg1))
+            }
             haxby_opcodes::OPCODE_RETURN => Ok(Opcode::Return),
             haxby_opcodes::OPCODE_RETURN_UNIT => Ok(Opcode::ReturnUnit),
+            haxby_opcodes::OPCODE_YIELD => Ok(Opcode::Yield),
+            haxby_opcodes::OPCODE_AWAIT => Ok(Opcode::Await),
             haxby_opcodes::OPCODE_TRY_ENTER => self
                 .read_u16()
                 .map_or(Err(DecodeError::InsufficientData), |b| {
@@ -289,6 +326,36 @@ impl BytecodeReader {
             haxby_opcodes::OPCODE_BUILD_STRUCT => Ok(Opcode::BuildStruct),
             haxby_opcodes::OPCODE_BUILD_ENUM => Ok(Opcode::BuildEnum),
             haxby_opcodes::OPCODE_BUILD_MIXIN => Ok(Opcode::BuildMixin),
+            haxby_opcodes::OPCODE_BUILD_MAP => self
+                .read_u32()
+                .map_or(Err(DecodeError::InsufficientData), |b| {
+                    Ok(Opcode::BuildMap(b))
+                }),
+            haxby_opcodes::OPCODE_BUILD_STRING => self
+                .read_u32()
+                .map_or(Err(DecodeError::InsufficientData), |b| {
+                    Ok(Opcode::BuildString(b))
+                }),
+            haxby_opcodes::OPCODE_LIST_CHECK_LEN => {
+                let b0 = match self.read_u8() {
+                    Ok(b) => b,
+                    Err(_) => {
+                        return Err(DecodeError::InsufficientData);
+                    }
+                };
+                let w1 = match self.read_u32() {
+                    Ok(w) => w,
+                    Err(_) => {
+                        return Err(DecodeError::InsufficientData);
+                    }
+                };
+                Ok(Opcode::ListCheckLen(b0, w1))
+            }
+            haxby_opcodes::OPCODE_LIST_TAIL => self
+                .read_u32()
+                .map_or(Err(DecodeError::InsufficientData), |b| {
+                    Ok(Opcode::ListTail(b))
+                }),
             haxby_opcodes::OPCODE_BIND_CASE => {
                 let b0 = match self.read_u8() {
                     Ok(b) => b,
@@ -320,6 +387,10 @@ impl BytecodeReader {
                 Ok(Opcode::BindCaseSymbol(b0, w1))
             }
             haxby_opcodes::OPCODE_INCLUDE_MIXIN => Ok(Opcode::IncludeMixin),
+            haxby_opcodes::OPCODE_REQUIRE_MEMBER => Ok(Opcode::RequireMember),
+            haxby_opcodes::OPCODE_CHECK_MIXIN_CONTRACTS => Ok(Opcode::CheckMixinContracts),
+            haxby_opcodes::OPCODE_BIND_GETTER => Ok(Opcode::BindGetter),
+            haxby_opcodes::OPCODE_BIND_SETTER => Ok(Opcode::BindSetter),
             haxby_opcodes::OPCODE_NEW_ENUM_VAL => {
                 let b0 = match self.read_u8() {
                     Ok(b) => b,
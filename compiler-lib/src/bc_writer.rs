@@ -73,6 +73,7 @@ impl BytecodeWriter {
             Opcode::WriteIndex(n) => self
                 .write_u8(haxby_opcodes::OPCODE_WRITE_INDEX)
                 .write_u8(*n),
+            Opcode::ReadSlice(n) => self.write_u8(haxby_opcodes::OPCODE_READ_SLICE).write_u8(*n),
             Opcode::ReadAttribute(n) => self
                 .write_u8(haxby_opcodes::OPCODE_READ_ATTRIBUTE)
                 .write_u16(*n),
@@ -85,6 +86,12 @@ impl BytecodeWriter {
             Opcode::WriteAttributeSymbol(n) => self
                 .write_u8(haxby_opcodes::OPCODE_WRITE_ATTRIBUTE_SYMBOL)
                 .write_u32(*n),
+            Opcode::TryReadAttribute(n) => self
+                .write_u8(haxby_opcodes::OPCODE_TRY_READ_ATTRIBUTE)
+                .write_u16(*n),
+            Opcode::TryReadAttributeSymbol(n) => self
+                .write_u8(haxby_opcodes::OPCODE_TRY_READ_ATTRIBUTE_SYMBOL)
+                .write_u32(*n),
             Opcode::ReadUplevel(n) => self
                 .write_u8(haxby_opcodes::OPCODE_READ_UPLEVEL)
                 .write_u8(*n),
@@ -111,8 +118,15 @@ impl BytecodeWriter {
                 .write_u8(*n)
                 .write_u16(*d),
             Opcode::Call(n) => self.write_u8(haxby_opcodes::OPCODE_CALL).write_u8(*n),
+            Opcode::CallNamed(n, k) => self
+                .write_u8(haxby_opcodes::OPCODE_CALL_NAMED)
+                .write_u8(*n)
+                .write_u8(*k),
+            Opcode::TailCall(n) => self.write_u8(haxby_opcodes::OPCODE_TAIL_CALL).write_u8(*n),
             Opcode::Return => self.write_u8(haxby_opcodes::OPCODE_RETURN),
             Opcode::ReturnUnit => self.write_u8(haxby_opcodes::OPCODE_RETURN_UNIT),
+            Opcode::Yield => self.write_u8(haxby_opcodes::OPCODE_YIELD),
+            Opcode::Await => self.write_u8(haxby_opcodes::OPCODE_AWAIT),
             Opcode::TryEnter(n) => self.write_u8(haxby_opcodes::OPCODE_TRY_ENTER).write_u16(*n),
             Opcode::TryExit => self.write_u8(haxby_opcodes::OPCODE_TRY_EXIT),
             Opcode::Throw => self.write_u8(haxby_opcodes::OPCODE_THROW),
@@ -125,6 +139,15 @@ impl BytecodeWriter {
                 .write_u8(*n),
             Opcode::BuildStruct => self.write_u8(haxby_opcodes::OPCODE_BUILD_STRUCT),
             Opcode::BuildMixin => self.write_u8(haxby_opcodes::OPCODE_BUILD_MIXIN),
+            Opcode::BuildMap(n) => self.write_u8(haxby_opcodes::OPCODE_BUILD_MAP).write_u32(*n),
+            Opcode::BuildString(n) => self
+                .write_u8(haxby_opcodes::OPCODE_BUILD_STRING)
+                .write_u32(*n),
+            Opcode::ListCheckLen(a, n) => self
+                .write_u8(haxby_opcodes::OPCODE_LIST_CHECK_LEN)
+                .write_u8(*a)
+                .write_u32(*n),
+            Opcode::ListTail(n) => self.write_u8(haxby_opcodes::OPCODE_LIST_TAIL).write_u32(*n),
             Opcode::BuildEnum => self.write_u8(haxby_opcodes::OPCODE_BUILD_ENUM),
             Opcode::BindCase(a, n) => self
                 .write_u8(haxby_opcodes::OPCODE_BIND_CASE)
@@ -135,6 +158,12 @@ impl BytecodeWriter {
                 .write_u8(*a)
                 .write_u32(*n),
             Opcode::IncludeMixin => self.write_u8(haxby_opcodes::OPCODE_INCLUDE_MIXIN),
+            Opcode::RequireMember => self.write_u8(haxby_opcodes::OPCODE_REQUIRE_MEMBER),
+            Opcode::CheckMixinContracts => {
+                self.write_u8(haxby_opcodes::OPCODE_CHECK_MIXIN_CONTRACTS)
+            }
+            Opcode::BindGetter => self.write_u8(haxby_opcodes::OPCODE_BIND_GETTER),
+            Opcode::BindSetter => self.write_u8(haxby_opcodes::OPCODE_BIND_SETTER),
             Opcode::NewEnumVal(a, n) => self
                 .write_u8(haxby_opcodes::OPCODE_NEW_ENUM_VAL)
                 .write_u8(*a)
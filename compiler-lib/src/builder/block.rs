@@ -7,7 +7,7 @@ use aria_parser::ast::SourcePointer;
 use crate::{
     bc_writer::BytecodeWriter,
     builder::{compiler_opcodes::CompilerOpcode, func::FunctionBuilder},
-    constant_value::ConstantValues,
+    constant_value::{ConstantValue, ConstantValues},
     line_table::LineTable,
 };
 
@@ -63,6 +63,109 @@ impl LocalValuesAccess {
     }
 }
 
+// a constant the optimizer can compute with at compile time
+#[derive(Clone, Copy)]
+enum FoldedConstant {
+    Integer(i64),
+    Float(f64),
+    Boolean(bool),
+}
+
+impl FoldedConstant {
+    fn from_opcode(op: &CompilerOpcode, cv: &ConstantValues) -> Option<Self> {
+        match op {
+            CompilerOpcode::Push0 => Some(Self::Integer(0)),
+            CompilerOpcode::Push1 => Some(Self::Integer(1)),
+            CompilerOpcode::PushTrue => Some(Self::Boolean(true)),
+            CompilerOpcode::PushFalse => Some(Self::Boolean(false)),
+            CompilerOpcode::Push(idx) => match cv.get(*idx as usize) {
+                Some(ConstantValue::Integer(n)) => Some(Self::Integer(n)),
+                Some(ConstantValue::Float(f)) => Some(Self::Float(f.raw_value())),
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+
+    fn to_opcode(self, cv: &mut ConstantValues) -> Option<CompilerOpcode> {
+        match self {
+            Self::Integer(0) => Some(CompilerOpcode::Push0),
+            Self::Integer(1) => Some(CompilerOpcode::Push1),
+            Self::Integer(n) => cv
+                .insert(ConstantValue::Integer(n))
+                .ok()
+                .map(CompilerOpcode::Push),
+            Self::Float(f) => cv
+                .insert(ConstantValue::Float(f.into()))
+                .ok()
+                .map(CompilerOpcode::Push),
+            Self::Boolean(true) => Some(CompilerOpcode::PushTrue),
+            Self::Boolean(false) => Some(CompilerOpcode::PushFalse),
+        }
+    }
+
+    fn as_float(self) -> Option<f64> {
+        match self {
+            Self::Integer(n) => Some(n as f64),
+            Self::Float(f) => Some(f),
+            Self::Boolean(_) => None,
+        }
+    }
+
+    fn fold_unary(self, op: &CompilerOpcode) -> Option<Self> {
+        match (op, self) {
+            (CompilerOpcode::Neg, Self::Integer(n)) => n.checked_neg().map(Self::Integer),
+            (CompilerOpcode::Neg, Self::Float(f)) => Some(Self::Float(-f)),
+            (CompilerOpcode::Not, Self::Boolean(b)) => Some(Self::Boolean(!b)),
+            _ => None,
+        }
+    }
+
+    // self is the left hand side, as it is pushed first
+    fn fold_binary(self, op: &CompilerOpcode, rhs: Self) -> Option<Self> {
+        use std::cmp::Ordering;
+
+        if let (Self::Integer(x), Self::Integer(y)) = (self, rhs) {
+            return match op {
+                CompilerOpcode::Add => x.checked_add(y).map(Self::Integer),
+                CompilerOpcode::Sub => x.checked_sub(y).map(Self::Integer),
+                CompilerOpcode::Mul => x.checked_mul(y).map(Self::Integer),
+                CompilerOpcode::Div => x.checked_div(y).map(Self::Integer),
+                CompilerOpcode::Rem => x.checked_rem(y).map(Self::Integer),
+                CompilerOpcode::Equal => Some(Self::Boolean(x == y)),
+                CompilerOpcode::LessThan => Some(Self::Boolean(x < y)),
+                CompilerOpcode::LessThanEqual => Some(Self::Boolean(x <= y)),
+                CompilerOpcode::GreaterThan => Some(Self::Boolean(x > y)),
+                CompilerOpcode::GreaterThanEqual => Some(Self::Boolean(x >= y)),
+                _ => None,
+            };
+        }
+
+        if let (Self::Boolean(x), Self::Boolean(y)) = (self, rhs) {
+            return match op {
+                CompilerOpcode::Equal => Some(Self::Boolean(x == y)),
+                _ => None,
+            };
+        }
+
+        let (x, y) = (self.as_float()?, rhs.as_float()?);
+        // the VM orders NaN as equal to everything, so do the same here
+        let ord = x.partial_cmp(&y).unwrap_or(Ordering::Equal);
+        match op {
+            CompilerOpcode::Add => Some(Self::Float(x + y)),
+            CompilerOpcode::Sub => Some(Self::Float(x - y)),
+            CompilerOpcode::Mul => Some(Self::Float(x * y)),
+            CompilerOpcode::Div if y != 0.0 => Some(Self::Float(x / y)),
+            CompilerOpcode::Rem if y != 0.0 => Some(Self::Float(x % y)),
+            CompilerOpcode::LessThan => Some(Self::Boolean(ord.is_lt())),
+            CompilerOpcode::LessThanEqual => Some(Self::Boolean(ord.is_le())),
+            CompilerOpcode::GreaterThan => Some(Self::Boolean(ord.is_gt())),
+            CompilerOpcode::GreaterThanEqual => Some(Self::Boolean(ord.is_ge())),
+            _ => None,
+        }
+    }
+}
+
 impl BasicBlock {
     pub(crate) fn new(name: &str, id: usize) -> Self {
         Self {
@@ -110,65 +213,184 @@ impl BasicBlock {
         false
     }
 
+    // turns a call that ends the block into a tail call, which also returns
+    // its result; returns false if the block does not end in a call
+    pub(crate) fn make_trailing_call_tail_call(&self) -> bool {
+        let mut br = self.imp.writer.borrow_mut();
+        match br.last_mut() {
+            Some(bbe) if let CompilerOpcode::Call(n) = bbe.op => {
+                bbe.op = CompilerOpcode::TailCall(n);
+                true
+            }
+            _ => false,
+        }
+    }
+
     #[allow(dead_code)]
     pub(crate) fn remove_op_at_idx(&self, idx: usize) {
         self.imp.writer.borrow_mut().remove(idx);
     }
 
-    fn replace_double_jump(&self) -> bool {
+    // follows a chain of blocks that do nothing but jump elsewhere, and
+    // returns where the chain ends if that is not dest itself
+    fn jump_thread_target(dest: &BasicBlock) -> Option<BasicBlock> {
+        let mut seen = vec![dest.id()];
+        let mut cur = dest.clone();
+        loop {
+            let next = match cur.imp.writer.borrow().first() {
+                Some(BasicBlockEntry {
+                    op: CompilerOpcode::Jump(next),
+                    ..
+                }) => next.clone(),
+                _ => break,
+            };
+            // a cycle of empty jumps is an infinite loop, leave it alone
+            if seen.contains(&next.id()) {
+                break;
+            }
+            seen.push(next.id());
+            cur = next;
+        }
+
+        if cur == *dest { None } else { Some(cur) }
+    }
+
+    pub(crate) fn thread_jumps(&self) -> bool {
         let mut any = false;
 
-        let mut br = self.imp.writer.borrow_mut();
-        for i in 0..br.len() {
-            if let CompilerOpcode::Jump(dest) = &br[i].op {
-                let dest = dest.clone();
-                if dest.imp.id == self.imp.id {
-                    continue;
+        for i in 0..self.len() {
+            // the chain may pass through this very block, so only read it here
+            let op = self.imp.writer.borrow()[i].op.clone();
+            let threaded = match op {
+                CompilerOpcode::Jump(dest) => {
+                    Self::jump_thread_target(&dest).map(CompilerOpcode::Jump)
                 }
-                if dest.is_empty() {
-                    continue;
+                CompilerOpcode::JumpTrue(dest) => {
+                    Self::jump_thread_target(&dest).map(CompilerOpcode::JumpTrue)
                 }
-                let dest_br = dest.imp.writer.borrow();
-                if let CompilerOpcode::Jump(final_dest) = &dest_br[0].op {
-                    br[i].op = CompilerOpcode::Jump(final_dest.clone());
-                    any = true;
+                CompilerOpcode::JumpFalse(dest) => {
+                    Self::jump_thread_target(&dest).map(CompilerOpcode::JumpFalse)
                 }
+                CompilerOpcode::JumpConditionally(t, f) => {
+                    let new_t = Self::jump_thread_target(&t);
+                    let new_f = Self::jump_thread_target(&f);
+                    if new_t.is_some() || new_f.is_some() {
+                        Some(CompilerOpcode::JumpConditionally(
+                            new_t.unwrap_or(t),
+                            new_f.unwrap_or(f),
+                        ))
+                    } else {
+                        None
+                    }
+                }
+                _ => None,
+            };
+
+            if let Some(threaded) = threaded {
+                self.imp.writer.borrow_mut()[i].op = threaded;
+                any = true;
             }
         }
 
         any
     }
 
-    fn optimize_true_false(&self, cv: &ConstantValues) {
+    pub(crate) fn optimize_true_false(&self, cv: &ConstantValues) -> bool {
+        let mut any = false;
+
         let mut br = self.imp.writer.borrow_mut();
         for i in 0..br.len() {
             if let CompilerOpcode::ReadNamed(idx) = &br[i].op
-                && let Some(crate::constant_value::ConstantValue::String(x)) = cv.get(*idx as usize)
+                && let Some(ConstantValue::String(x)) = cv.get(*idx as usize)
             {
                 if x == "true" {
                     br[i].op = CompilerOpcode::PushTrue;
+                    any = true;
                 } else if x == "false" {
                     br[i].op = CompilerOpcode::PushFalse;
+                    any = true;
                 }
             }
         }
+
+        any
     }
 
-    fn optimize_redundant_conditional_jumps(&self) {
+    // replaces arithmetic and comparisons whose operands are all pushed
+    // constants with their result; anything that could fail at runtime (e.g. a
+    // division by zero) or that the VM would widen into a big integer is left
+    // for the VM to evaluate
+    pub(crate) fn fold_constants(&self, cv: &mut ConstantValues) -> bool {
+        let mut any = false;
+
+        let mut br = self.imp.writer.borrow_mut();
+        let mut i = 0;
+        while i < br.len() {
+            let folded = match &br[i].op {
+                CompilerOpcode::Neg | CompilerOpcode::Not if i >= 1 => {
+                    FoldedConstant::from_opcode(&br[i - 1].op, cv)
+                        .and_then(|x| x.fold_unary(&br[i].op))
+                        .map(|x| (x, 1))
+                }
+                CompilerOpcode::Add
+                | CompilerOpcode::Sub
+                | CompilerOpcode::Mul
+                | CompilerOpcode::Div
+                | CompilerOpcode::Rem
+                | CompilerOpcode::Equal
+                | CompilerOpcode::LessThan
+                | CompilerOpcode::LessThanEqual
+                | CompilerOpcode::GreaterThan
+                | CompilerOpcode::GreaterThanEqual
+                    if i >= 2 =>
+                {
+                    match (
+                        FoldedConstant::from_opcode(&br[i - 2].op, cv),
+                        FoldedConstant::from_opcode(&br[i - 1].op, cv),
+                    ) {
+                        (Some(x), Some(y)) => x.fold_binary(&br[i].op, y).map(|x| (x, 2)),
+                        _ => None,
+                    }
+                }
+                _ => None,
+            };
+
+            if let Some((folded, operands)) = folded
+                && let Some(op) = folded.to_opcode(cv)
+            {
+                br[i].op = op;
+                br.drain(i - operands..i);
+                i -= operands;
+                any = true;
+            }
+
+            i += 1;
+        }
+
+        any
+    }
+
+    pub(crate) fn optimize_redundant_conditional_jumps(&self) -> bool {
+        let mut any = false;
+
         let mut br = self.imp.writer.borrow_mut();
         let mut i = 0;
         while i + 1 < br.len() {
             match (&br[i].op, &br[i + 1].op) {
                 (CompilerOpcode::PushTrue, CompilerOpcode::JumpTrue(target))
-                | (CompilerOpcode::PushFalse, CompilerOpcode::JumpFalse(target)) => {
+                | (CompilerOpcode::PushFalse, CompilerOpcode::JumpFalse(target))
+                | (CompilerOpcode::PushTrue, CompilerOpcode::JumpConditionally(target, _))
+                | (CompilerOpcode::PushFalse, CompilerOpcode::JumpConditionally(_, target)) => {
                     br[i].op = CompilerOpcode::Jump(target.clone());
                     br.remove(i + 1);
+                    any = true;
                     continue;
                 }
                 (CompilerOpcode::PushTrue, CompilerOpcode::JumpFalse(_))
                 | (CompilerOpcode::PushFalse, CompilerOpcode::JumpTrue(_)) => {
                     br[i].op = CompilerOpcode::Nop;
                     br.remove(i + 1);
+                    any = true;
                     continue;
                 }
                 _ => {}
@@ -176,24 +398,29 @@ impl BasicBlock {
 
             i += 1;
         }
+
+        any
     }
 
-    fn remove_instructions_after_terminal(&self) {
+    pub(crate) fn remove_instructions_after_terminal(&self) -> bool {
         let mut br = self.imp.writer.borrow_mut();
         for i in 0..br.len() {
             if br[i].op.is_terminal() {
-                while br.len() != i + 1 {
-                    br.remove(i + 1);
-                }
-                return;
+                let any = br.len() != i + 1;
+                br.truncate(i + 1);
+                return any;
             }
         }
+
+        false
     }
 
-    fn remove_redundant_local_reads(&self) {
+    pub(crate) fn remove_redundant_local_reads(&self) -> bool {
+        let mut any = false;
+
         let mut br = self.imp.writer.borrow_mut();
         if br.len() < 2 {
-            return;
+            return false;
         }
 
         for i in 0..br.len() - 1 {
@@ -206,6 +433,7 @@ impl BasicBlock {
                                 break;
                             } else {
                                 br[j].op = CompilerOpcode::Dup;
+                                any = true;
                             }
                         }
                         _ => {
@@ -216,12 +444,16 @@ impl BasicBlock {
                 }
             }
         }
+
+        any
     }
 
-    fn remove_redundant_named_reads(&self) {
+    pub(crate) fn remove_redundant_named_reads(&self) -> bool {
+        let mut any = false;
+
         let mut br = self.imp.writer.borrow_mut();
         if br.len() < 2 {
-            return;
+            return false;
         }
 
         for i in 0..br.len() - 1 {
@@ -234,6 +466,7 @@ impl BasicBlock {
                                 break;
                             } else {
                                 br[j].op = CompilerOpcode::Dup;
+                                any = true;
                             }
                         }
                         _ => {
@@ -244,11 +477,15 @@ impl BasicBlock {
                 }
             }
         }
+
+        any
     }
 
-    fn remove_store_load_sequence(&self) {
+    pub(crate) fn remove_store_load_sequence(&self) -> bool {
+        let mut any = false;
+
         if self.len() < 2 {
-            return;
+            return false;
         }
 
         let mut br = self.imp.writer.borrow_mut();
@@ -260,23 +497,28 @@ impl BasicBlock {
             {
                 br[i].op = CompilerOpcode::Dup;
                 br[i + 1].op = CompilerOpcode::WriteLocal(x);
+                any = true;
             }
         }
+
+        any
     }
 
-    fn remove_nop_instructions(&self) {
+    pub(crate) fn remove_nop_instructions(&self) -> bool {
         let mut br = self.imp.writer.borrow_mut();
+        let len = br.len();
         br.retain(|x| !matches!(x.op, CompilerOpcode::Nop));
+        br.len() != len
     }
 
-    fn remove_push_pop_pairs(&self) {
-        if self.len() < 2 {
-            return;
-        }
+    // removes values that are pushed only to be popped right away; dropping a
+    // pair can bring another pair together, so this runs until nothing changes
+    pub(crate) fn remove_push_pop_pairs(&self) -> bool {
+        let mut any = false;
 
         let mut br = self.imp.writer.borrow_mut();
-
-        for i in 0..br.len() - 1 {
+        let mut i = 0;
+        while i + 1 < br.len() {
             if let (
                 CompilerOpcode::Push0
                 | CompilerOpcode::Push1
@@ -288,23 +530,16 @@ impl BasicBlock {
                 CompilerOpcode::Pop,
             ) = (&br[i].op, &br[i + 1].op)
             {
-                br[i].op = CompilerOpcode::Nop;
-                br[i + 1].op = CompilerOpcode::Nop;
+                br.drain(i..i + 2);
+                any = true;
+                i = i.saturating_sub(1);
+                continue;
             }
+
+            i += 1;
         }
-    }
 
-    pub(crate) fn run_optimize_passes(&self, cv: &ConstantValues) {
-        self.optimize_true_false(cv);
-        self.optimize_redundant_conditional_jumps();
-        self.remove_redundant_local_reads();
-        self.remove_redundant_named_reads();
-        self.remove_store_load_sequence();
-        self.remove_instructions_after_terminal();
-        self.remove_nop_instructions();
-        self.remove_push_pop_pairs();
-        self.remove_nop_instructions();
-        while self.replace_double_jump() {}
+        any
     }
 
     pub(crate) fn drop_unused_locals(&self, values: &HashSet<u8>) {
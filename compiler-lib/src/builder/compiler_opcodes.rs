@@ -2,6 +2,7 @@
 use enum_as_inner::EnumAsInner;
 use haxby_opcodes::{
     BuiltinTypeId, BuiltinValueId, Opcode as VmOpcode, enum_case_attribs::CASE_HAS_PAYLOAD,
+    list_check_len_attribs::LEN_AT_LEAST,
 };
 
 use crate::builder::{block::BasicBlock, func::FunctionBuilder};
@@ -38,7 +39,9 @@ pub enum CompilerOpcode {
     TypedefNamed(u16),
     ReadIndex(u8),
     WriteIndex(u8),
+    ReadSlice(u8),
     ReadAttribute(u16),
+    TryReadAttribute(u16),
     WriteAttribute(u16),
     ReadUplevel(u8),
     LogicalAnd,
@@ -56,8 +59,12 @@ pub enum CompilerOpcode {
     JumpConditionally(BasicBlock, BasicBlock),
     JumpIfArgSupplied(u8, BasicBlock),
     Call(u8),
+    CallNamed(u8, u8),
+    TailCall(u8),
     Return,
     ReturnUnit,
+    Yield,
+    Await,
     TryEnter(BasicBlock),
     TryExit,
     Throw,
@@ -67,8 +74,16 @@ pub enum CompilerOpcode {
     BuildStruct,
     BuildEnum,
     BuildMixin,
+    BuildMap(u32),
+    BuildString(u32),
+    ListCheckLen(bool, u32),
+    ListTail(u32),
     BindCase(u8, u16),
     IncludeMixin,
+    RequireMember,
+    CheckMixinContracts,
+    BindGetter,
+    BindSetter,
     NewEnumVal(bool, u16),
     EnumCheckIsCase(u16),
     EnumTryExtractPayload,
@@ -114,7 +129,9 @@ impl CompilerOpcode {
             Self::TypedefNamed(_) => false,
             Self::ReadIndex(_) => false,
             Self::WriteIndex(_) => false,
+            Self::ReadSlice(_) => false,
             Self::ReadAttribute(_) => false,
+            Self::TryReadAttribute(_) => false,
             Self::WriteAttribute(_) => false,
             Self::ReadUplevel(_) => false,
             Self::LogicalAnd => false,
@@ -132,8 +149,12 @@ impl CompilerOpcode {
             Self::JumpConditionally(..) => true,
             Self::JumpIfArgSupplied(..) => false,
             Self::Call(_) => false,
+            Self::CallNamed(..) => false,
+            Self::TailCall(_) => true,
             Self::Return => true,
             Self::ReturnUnit => true,
+            Self::Yield => false,
+            Self::Await => false,
             Self::TryEnter(_) => false,
             Self::TryExit => false,
             Self::Throw => true,
@@ -143,8 +164,16 @@ impl CompilerOpcode {
             Self::BuildStruct => false,
             Self::BuildEnum => false,
             Self::BuildMixin => false,
+            Self::BuildMap(_) => false,
+            Self::BuildString(_) => false,
+            Self::ListCheckLen(..) => false,
+            Self::ListTail(_) => false,
             Self::BindCase(..) => false,
             Self::IncludeMixin => false,
+            Self::RequireMember => false,
+            Self::CheckMixinContracts => false,
+            Self::BindGetter => false,
+            Self::BindSetter => false,
             Self::NewEnumVal(..) => false,
             Self::EnumCheckIsCase(_) => false,
             Self::EnumTryExtractPayload => false,
@@ -202,7 +231,9 @@ impl CompilerOpcode {
             Self::TypedefNamed(n) => VmOpcode::TypedefNamed(*n),
             Self::ReadIndex(n) => VmOpcode::ReadIndex(*n),
             Self::WriteIndex(n) => VmOpcode::WriteIndex(*n),
+            Self::ReadSlice(n) => VmOpcode::ReadSlice(*n),
             Self::ReadAttribute(n) => VmOpcode::ReadAttribute(*n),
+            Self::TryReadAttribute(n) => VmOpcode::TryReadAttribute(*n),
             Self::WriteAttribute(n) => VmOpcode::WriteAttribute(*n),
             Self::ReadUplevel(n) => VmOpcode::ReadUplevel(*n),
             Self::LogicalAnd => VmOpcode::LogicalAnd,
@@ -248,8 +279,12 @@ impl CompilerOpcode {
                 VmOpcode::JumpIfArgSupplied(*arg, offset)
             }
             Self::Call(n) => VmOpcode::Call(*n),
+            Self::CallNamed(n, k) => VmOpcode::CallNamed(*n, *k),
+            Self::TailCall(n) => VmOpcode::TailCall(*n),
             Self::Return => VmOpcode::Return,
             Self::ReturnUnit => VmOpcode::ReturnUnit,
+            Self::Yield => VmOpcode::Yield,
+            Self::Await => VmOpcode::Await,
             Self::TryEnter(dst) => {
                 let offset = parent
                     .position_of_block_instructions(dst)
@@ -264,8 +299,18 @@ impl CompilerOpcode {
             Self::BuildStruct => VmOpcode::BuildStruct,
             Self::BuildEnum => VmOpcode::BuildEnum,
             Self::BuildMixin => VmOpcode::BuildMixin,
+            Self::BuildMap(v) => VmOpcode::BuildMap(*v),
+            Self::BuildString(v) => VmOpcode::BuildString(*v),
+            Self::ListCheckLen(v, n) => {
+                VmOpcode::ListCheckLen(if *v { LEN_AT_LEAST } else { 0 }, *n)
+            }
+            Self::ListTail(v) => VmOpcode::ListTail(*v),
             Self::BindCase(x, y) => VmOpcode::BindCase(*x, *y),
             Self::IncludeMixin => VmOpcode::IncludeMixin,
+            Self::RequireMember => VmOpcode::RequireMember,
+            Self::CheckMixinContracts => VmOpcode::CheckMixinContracts,
+            Self::BindGetter => VmOpcode::BindGetter,
+            Self::BindSetter => VmOpcode::BindSetter,
             Self::NewEnumVal(v, n) => {
                 VmOpcode::NewEnumVal(if *v { CASE_HAS_PAYLOAD } else { 0 }, *n)
             }
@@ -316,7 +361,9 @@ impl std::fmt::Display for CompilerOpcode {
             TypedefNamed(n) => write!(f, "TypedefNamed({})", n),
             ReadIndex(n) => write!(f, "ReadIndex({})", n),
             WriteIndex(n) => write!(f, "WriteIndex({})", n),
+            ReadSlice(n) => write!(f, "ReadSlice({})", n),
             ReadAttribute(n) => write!(f, "ReadAttribute({})", n),
+            TryReadAttribute(n) => write!(f, "TryReadAttribute({})", n),
             WriteAttribute(n) => write!(f, "WriteAttribute({})", n),
             ReadUplevel(n) => write!(f, "ReadUplevel({})", n),
             LogicalAnd => write!(f, "LogicalAnd"),
@@ -338,8 +385,12 @@ impl std::fmt::Display for CompilerOpcode {
                 write!(f, "JumpConditionally({}, {})", tr.name(), fa.name())
             }
             Call(n) => write!(f, "Call({})", n),
+            CallNamed(n, k) => write!(f, "CallNamed({}, {})", n, k),
+            TailCall(n) => write!(f, "TailCall({})", n),
             Return => write!(f, "Return"),
             ReturnUnit => write!(f, "ReturnUnit"),
+            Yield => write!(f, "Yield"),
+            Await => write!(f, "Await"),
             TryEnter(dst) => write!(f, "TryEnter({})", dst.name()),
             TryExit => write!(f, "TryExit"),
             Throw => write!(f, "Throw"),
@@ -349,8 +400,16 @@ impl std::fmt::Display for CompilerOpcode {
             BuildStruct => write!(f, "BuildStruct"),
             BuildEnum => write!(f, "BuildEnum"),
             BuildMixin => write!(f, "BuildMixin"),
+            BuildMap(v) => write!(f, "BuildMap({})", v),
+            BuildString(v) => write!(f, "BuildString({})", v),
+            ListCheckLen(at_least, n) => write!(f, "ListCheckLen({}, {})", at_least, n),
+            ListTail(v) => write!(f, "ListTail({})", v),
             BindCase(x, y) => write!(f, "BindCase({}, {})", x, y),
             IncludeMixin => write!(f, "IncludeMixin"),
+            RequireMember => write!(f, "RequireMember"),
+            CheckMixinContracts => write!(f, "CheckMixinContracts"),
+            BindGetter => write!(f, "BindGetter"),
+            BindSetter => write!(f, "BindSetter"),
             NewEnumVal(has_payload, n) => write!(f, "NewEnumVal({}, {})", has_payload, n),
             EnumCheckIsCase(v) => write!(f, "EnumCheckIsCase({})", v),
             EnumTryExtractPayload => write!(f, "EnumTryExtractPayload"),
@@ -119,33 +119,34 @@ impl FunctionBuilder {
         None
     }
 
-    fn has_entrypoints(&self, blk: &BasicBlock) -> bool {
-        for src_blk in &self.blocks {
-            let br = src_blk.imp.writer.borrow();
+    fn find_unreachable_blocks(&self) -> HashSet<usize> {
+        let mut reachable = HashSet::<usize>::default();
+        let mut worklist = self
+            .blocks
+            .iter()
+            .filter(|blk| blk.id() == 0)
+            .cloned()
+            .collect::<Vec<_>>();
+
+        while let Some(blk) = worklist.pop() {
+            if !reachable.insert(blk.id()) {
+                continue;
+            }
+            let br = blk.imp.writer.borrow();
             for src_op in br.as_slice() {
                 for dst in src_op.op.is_jump_instruction() {
-                    if dst.id() == blk.id() {
-                        return true;
+                    if !reachable.contains(&dst.id()) {
+                        worklist.push(dst);
                     }
                 }
             }
         }
-        false
-    }
-
-    fn find_orphaned_blocks(&self) -> HashSet<usize> {
-        let mut orphans = HashSet::<usize>::default();
 
-        for blk in &self.blocks {
-            if blk.id() != 0 {
-                let has_entrypoints = self.has_entrypoints(blk);
-                if !has_entrypoints {
-                    orphans.insert(blk.id());
-                }
-            }
-        }
-
-        orphans
+        self.blocks
+            .iter()
+            .map(|blk| blk.id())
+            .filter(|id| !reachable.contains(id))
+            .collect()
     }
 
     fn remove_block_with_id(&mut self, id: usize) -> bool {
@@ -159,21 +160,104 @@ impl FunctionBuilder {
         false
     }
 
-    fn run_optimize_passes(&mut self, cv: &ConstantValues) {
-        let orphans = self.find_orphaned_blocks();
-        for orphan_id in &orphans {
-            assert!(self.remove_block_with_id(*orphan_id));
+    fn remove_unreachable_blocks(&mut self) -> bool {
+        let unreachable = self.find_unreachable_blocks();
+        for id in &unreachable {
+            assert!(self.remove_block_with_id(*id));
         }
 
+        !unreachable.is_empty()
+    }
+
+    fn drop_unused_locals(&mut self) -> bool {
         let locals_access = self.calculate_locals_access();
         let unused_locals = locals_access.calculate_unused_locals();
+        if unused_locals.is_empty() {
+            return false;
+        }
 
         for blk in &self.blocks {
-            if !unused_locals.is_empty() {
-                blk.drop_unused_locals(&unused_locals);
-            }
-            blk.run_optimize_passes(cv);
+            blk.drop_unused_locals(&unused_locals);
         }
+        true
+    }
+
+    // runs one optimization pass, and shows its effect when dumping the IR
+    fn run_pass(
+        &mut self,
+        name: &str,
+        options: &CompilationOptions,
+        pass: impl FnOnce(&mut Self) -> bool,
+    ) {
+        if pass(self) && options.dump_builder {
+            println!("(opt: {name}) Intermediate Representation Dump:\n{}", self);
+        }
+    }
+
+    fn run_block_pass(
+        &mut self,
+        name: &str,
+        options: &CompilationOptions,
+        mut pass: impl FnMut(&BasicBlock) -> bool,
+    ) {
+        self.run_pass(name, options, |this| {
+            // every block must run the pass, so do not stop at the first change
+            let mut any = false;
+            for blk in &this.blocks {
+                any |= pass(blk);
+            }
+            any
+        });
+    }
+
+    fn run_optimize_passes(&mut self, cv: &mut ConstantValues, options: &CompilationOptions) {
+        self.run_pass(
+            "remove unreachable blocks",
+            options,
+            Self::remove_unreachable_blocks,
+        );
+        self.run_pass("drop unused locals", options, Self::drop_unused_locals);
+        self.run_block_pass("read true/false as constants", options, |blk| {
+            blk.optimize_true_false(cv)
+        });
+        self.run_block_pass("fold constants", options, |blk| blk.fold_constants(cv));
+        self.run_block_pass(
+            "resolve constant conditional jumps",
+            options,
+            BasicBlock::optimize_redundant_conditional_jumps,
+        );
+        self.run_block_pass(
+            "reuse repeated local reads",
+            options,
+            BasicBlock::remove_redundant_local_reads,
+        );
+        self.run_block_pass(
+            "reuse repeated named reads",
+            options,
+            BasicBlock::remove_redundant_named_reads,
+        );
+        self.run_block_pass(
+            "reuse stored locals",
+            options,
+            BasicBlock::remove_store_load_sequence,
+        );
+        self.run_block_pass(
+            "remove instructions after terminal",
+            options,
+            BasicBlock::remove_instructions_after_terminal,
+        );
+        self.run_block_pass("remove nops", options, BasicBlock::remove_nop_instructions);
+        self.run_block_pass(
+            "remove push/pop pairs",
+            options,
+            BasicBlock::remove_push_pop_pairs,
+        );
+        self.run_block_pass("thread jumps", options, BasicBlock::thread_jumps);
+        self.run_pass(
+            "remove unreachable blocks",
+            options,
+            Self::remove_unreachable_blocks,
+        );
     }
 
     fn calculate_locals_access(&self) -> LocalValuesAccess {
@@ -188,14 +272,14 @@ impl FunctionBuilder {
 
     pub fn write(
         &mut self,
-        cv: &ConstantValues,
+        cv: &mut ConstantValues,
         options: &CompilationOptions,
     ) -> Result<Vec<u8>, crate::do_compile::CompilationErrorReason> {
         if options.dump_builder {
             println!("(unopt) Intermediate Representation Dump:\n{}", self);
         }
         if options.optimize {
-            self.run_optimize_passes(cv);
+            self.run_optimize_passes(cv, options);
             if options.dump_builder {
                 println!("(opt) Intermediate Representation Dump:\n{}", self);
             }
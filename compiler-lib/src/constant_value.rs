@@ -13,6 +13,7 @@ pub struct CompiledCodeObject {
     pub body: Vec<u8>,
     pub required_argc: u8, // arguments that are required to call this function
     pub default_argc: u8,  // additional arguments that this function can accept
+    pub arg_names: Vec<String>, // names of the arguments, for calls with keyword arguments
     pub loc: SourcePointer,
     pub line_table: LineTable,
     pub frame_size: u8,
@@ -3,11 +3,11 @@ use std::{collections::HashSet, fmt::Display, path::PathBuf};
 
 use aria_parser::ast::{
     ArgumentDecl, ArgumentList, AssertStatement, CodeBlock, DeclarationId, ElsePiece, EnumCaseDecl,
-    EnumDecl, EnumDeclEntry, Expression, FunctionBody, Identifier, MatchPattern,
-    MatchPatternEnumCase, MatchRule, MatchStatement, MethodAccess, MethodDecl, MixinIncludeDecl,
-    OperatorDecl, ParsedModule, ReturnStatement, SourceBuffer, SourcePointer, Statement,
-    StringLiteral, StructDecl, StructEntry, ValDeclStatement, prettyprint::PrettyPrintable,
-    source_to_ast,
+    EnumDecl, EnumDeclEntry, Expression, FunctionBody, Identifier, MatchPattern, MatchRule,
+    MatchStatement, MethodAccess, MethodDecl, MixinIncludeDecl, OperatorDecl, ParsedModule,
+    Pattern, PatternEnumCase, PropertyDecl, ReturnStatement, SourceBuffer, SourcePointer,
+    Statement, StringLiteral, StructDecl, StructEntry, ValDeclStatement,
+    prettyprint::PrettyPrintable, source_to_ast,
 };
 use haxby_opcodes::BuiltinTypeId;
 use thiserror::Error;
@@ -16,6 +16,7 @@ use crate::{
     CompilationOptions,
     builder::{block::BasicBlock, compiler_opcodes::CompilerOpcode, func::FunctionBuilder},
     constant_value::{ConstantValue, ConstantValuesError},
+    exhaustiveness::module_enums,
     module::CompiledModule,
     scope::{CompilationScope, ScopeError, ScopeErrorReason},
 };
@@ -30,6 +31,10 @@ pub enum CompilationErrorReason {
     FunctionBodyTooLarge,
     #[error("list length is out of bounds")]
     ListTooLarge,
+    #[error("map literal has too many entries")]
+    MapTooLarge,
+    #[error("format string has too many parts")]
+    FormatStringTooLarge,
     #[error("attempt to modify a read-only value")]
     ReadOnlyValue,
     #[error("{0} is not a valid literal")]
@@ -56,10 +61,20 @@ pub enum CompilationErrorReason {
     DuplicateArgumentName(String),
     #[error("struct members do not support type hints")]
     NoTypeHintOnStructMember,
+    #[error("await is only allowed in an async function")]
+    AwaitOutsideAsync,
+    #[error("async functions cannot yield")]
+    YieldInAsyncFunction,
+    #[error("'{0}' is private to module '{1}' and cannot be imported")]
+    PrivateImport(String, String),
     #[error("nested closures are not supported")]
     NestedClosureDisallowed,
     #[error("attempted to write to {0} values, but {1} were provided")]
     AssignmentArityMismatch(usize, usize),
+    #[error("properties can only be declared in structs and their extensions")]
+    PropertyOutsideStruct,
+    #[error("no enclosing loop is labeled '{0}'")]
+    NoSuchLoopLabel(String),
 }
 
 impl From<&ScopeErrorReason> for CompilationErrorReason {
@@ -108,12 +123,177 @@ impl std::fmt::Debug for CompilationError {
     }
 }
 
+#[derive(Clone, Debug, Error)]
+pub enum CompilationWarningReason {
+    #[error("match over enum '{0}' does not handle {cases}", cases = .1.join(", "))]
+    NonExhaustiveMatch(String, Vec<String>),
+    #[error("variable '{0}' is never read")]
+    UnusedVariable(String),
+    #[error("variable '{0}' is assigned but never read")]
+    AssignedButNeverRead(String),
+    #[error("'{0}' is imported but never used")]
+    UnusedImport(String),
+    #[error("'{0}' shadows an earlier variable with the same name")]
+    ShadowedVariable(String),
+    #[error("this code will never run")]
+    UnreachableCode,
+    #[error("'{0}' is not defined{hint}", hint = did_you_mean(.1))]
+    UnresolvedIdentifier(String, Option<String>),
+}
+
+fn did_you_mean(suggestion: &Option<String>) -> String {
+    match suggestion {
+        Some(s) => format!("; did you mean '{s}'?"),
+        None => String::new(),
+    }
+}
+
+#[derive(Clone)]
+pub struct CompilationWarning {
+    pub loc: SourcePointer,
+    pub reason: CompilationWarningReason,
+}
+
+impl std::fmt::Display for CompilationWarning {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "at {}, warning: {}", self.loc, self.reason)
+    }
+}
+
+impl std::fmt::Debug for CompilationWarning {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "at {}, warning: {}", self.loc, self.reason)
+    }
+}
+
 pub type CompilationResult<T = (), E = CompilationError> = Result<T, E>;
 
+// a try statement enclosing the code being compiled; jumping out of it has to
+// pop its handler and run its finally block (if any) in the context of the try
+#[derive(Clone)]
+struct EnclosingTry {
+    finally: Option<CodeBlock>,
+    scope: CompilationScope,
+    break_dest: Option<BasicBlock>,
+    continue_dest: Option<BasicBlock>,
+    loop_try_depth: usize,
+    labeled_loops: Vec<LabeledLoop>,
+}
+
+// a loop that `break label;` and `continue label;` can refer to
+#[derive(Clone)]
+struct LabeledLoop {
+    label: String,
+    break_dest: BasicBlock,
+    continue_dest: BasicBlock,
+    // how many of enclosing_try were already entered when the loop began
+    try_depth: usize,
+}
+
 #[derive(Default)]
 struct ControlFlowTargets {
     break_dest: Option<BasicBlock>,
     continue_dest: Option<BasicBlock>,
+    // innermost last
+    enclosing_try: Vec<EnclosingTry>,
+    // how many of enclosing_try were already entered when the innermost loop began
+    loop_try_depth: usize,
+    // innermost last
+    labeled_loops: Vec<LabeledLoop>,
+}
+
+impl ControlFlowTargets {
+    fn for_loop(
+        &self,
+        break_dest: BasicBlock,
+        continue_dest: BasicBlock,
+        label: Option<&Identifier>,
+    ) -> Self {
+        let mut labeled_loops = self.labeled_loops.clone();
+        if let Some(label) = label {
+            labeled_loops.push(LabeledLoop {
+                label: label.value.clone(),
+                break_dest: break_dest.clone(),
+                continue_dest: continue_dest.clone(),
+                try_depth: self.enclosing_try.len(),
+            });
+        }
+        Self {
+            break_dest: Some(break_dest),
+            continue_dest: Some(continue_dest),
+            enclosing_try: self.enclosing_try.clone(),
+            loop_try_depth: self.enclosing_try.len(),
+            labeled_loops,
+        }
+    }
+
+    // the innermost loop with the given label
+    fn labeled_loop(&self, label: &Identifier) -> CompilationResult<&LabeledLoop> {
+        self.labeled_loops
+            .iter()
+            .rev()
+            .find(|ll| ll.label == label.value)
+            .ok_or_else(|| CompilationError {
+                loc: label.loc.clone(),
+                reason: CompilationErrorReason::NoSuchLoopLabel(label.value.clone()),
+            })
+    }
+
+    fn for_try(&self, finally: Option<CodeBlock>, scope: &CompilationScope) -> Self {
+        let mut enclosing_try = self.enclosing_try.clone();
+        enclosing_try.push(EnclosingTry {
+            finally,
+            scope: scope.clone(),
+            break_dest: self.break_dest.clone(),
+            continue_dest: self.continue_dest.clone(),
+            loop_try_depth: self.loop_try_depth,
+            labeled_loops: self.labeled_loops.clone(),
+        });
+        Self {
+            break_dest: self.break_dest.clone(),
+            continue_dest: self.continue_dest.clone(),
+            enclosing_try,
+            loop_try_depth: self.loop_try_depth,
+            labeled_loops: self.labeled_loops.clone(),
+        }
+    }
+}
+
+// leaves every try statement entered after the first `depth` ones, innermost first,
+// before a jump (or return) out of them
+fn emit_try_unwind(
+    params: &mut CompileParams,
+    depth: usize,
+    loc: &SourcePointer,
+) -> CompilationResult {
+    let cflow = params.cflow;
+    for idx in (depth..cflow.enclosing_try.len()).rev() {
+        let enclosing = &cflow.enclosing_try[idx];
+        params
+            .writer
+            .get_current_block()
+            .write_opcode_and_source_info(CompilerOpcode::TryExit, loc.clone());
+        if let Some(finally) = &enclosing.finally {
+            let f_scope = enclosing.scope.child();
+            let f_cflow = ControlFlowTargets {
+                break_dest: enclosing.break_dest.clone(),
+                continue_dest: enclosing.continue_dest.clone(),
+                enclosing_try: cflow.enclosing_try[..idx].to_vec(),
+                loop_try_depth: enclosing.loop_try_depth,
+                labeled_loops: enclosing.labeled_loops.clone(),
+            };
+            let mut f_params = CompileParams {
+                module: params.module,
+                scope: &f_scope,
+                writer: params.writer,
+                cflow: &f_cflow,
+                options: params.options,
+            };
+            finally.do_compile(&mut f_params)?;
+        }
+    }
+
+    Ok(())
 }
 
 struct CompileParams<'a> {
@@ -253,6 +433,8 @@ struct ArgumentCountInfo {
     required_args: u8,
     default_args: u8,
     varargs: bool,
+    // the names of the user-declared arguments, which keyword arguments are matched against
+    names: Vec<String>,
 }
 
 fn emit_args_at_target(
@@ -276,6 +458,7 @@ fn emit_args_at_target(
         required_args: 0,
         default_args: 0,
         varargs: args.vararg,
+        names: args.names.iter().map(|arg| arg.name().clone()).collect(),
     };
 
     let mut arg_idx: u8 = 0;
@@ -344,6 +527,56 @@ fn emit_method_decl_compile(md: &MethodDecl, params: &mut CompileParams) -> Comp
     Ok(())
 }
 
+fn emit_property_decl_compile(pd: &PropertyDecl, params: &mut CompileParams) -> CompilationResult {
+    if pd.getter.is_some() && pd.setter.is_some() {
+        params
+            .writer
+            .get_current_block()
+            .write_opcode_and_source_info(CompilerOpcode::Dup, pd.loc.clone());
+    }
+
+    let accessors = [
+        (&pd.getter, CompilerOpcode::BindGetter),
+        (&pd.setter, CompilerOpcode::BindSetter),
+    ];
+    for (accessor, bind) in accessors {
+        let Some(md) = accessor else {
+            continue;
+        };
+        md.do_compile(params)?;
+        params
+            .writer
+            .get_current_block()
+            .write_opcode_and_source_info(CompilerOpcode::BuildFunction, md.loc.clone());
+        let name = StringLiteral {
+            loc: pd.name.loc.clone(),
+            value: pd.name.value.clone(),
+        };
+        name.do_compile(params)?;
+        params
+            .writer
+            .get_current_block()
+            .write_opcode_and_source_info(bind, md.loc.clone());
+    }
+
+    Ok(())
+}
+
+// properties are looked up on the struct of an object, so mixins and enums
+// cannot declare them
+fn reject_property_decls(entries: &[StructEntry]) -> CompilationResult {
+    match entries.iter().find_map(|se| match se {
+        StructEntry::Property(pd) => Some(pd),
+        _ => None,
+    }) {
+        Some(pd) => Err(CompilationError {
+            loc: pd.loc.clone(),
+            reason: CompilationErrorReason::PropertyOutsideStruct,
+        }),
+        None => Ok(()),
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum OperatorArity {
     Exactly(usize),
@@ -545,6 +778,14 @@ lazy_static::lazy_static! {
             },
         );
 
+        map.insert("[:]",
+            OperatorInfo {
+                arity: OperatorArity::Exactly(3),
+                direct_name: "read_slice",
+                reverse_name: "",
+            },
+        );
+
         map
     };
 }
@@ -598,6 +839,7 @@ fn emit_operator_decl_compile(op: &OperatorDecl, params: &mut CompileParams) ->
     let md = MethodDecl {
         loc: op.loc.clone(),
         access: MethodAccess::Instance,
+        is_async: false,
         name: Identifier {
             loc: op.loc.clone(),
             value: op_fn_name,
@@ -697,9 +939,26 @@ fn emit_type_members_compile(
             aria_parser::ast::StructEntry::MixinInclude(mi) => {
                 emit_type_mixin_include_decl_compile(mi, params)?
             }
+            aria_parser::ast::StructEntry::Property(pd) => emit_property_decl_compile(pd, params)?,
         }
     }
 
+    // once all members are in place, check that the type provides what its
+    // mixins require
+    if let Some(include) = entries
+        .iter()
+        .find(|se| matches!(se, aria_parser::ast::StructEntry::MixinInclude(_)))
+    {
+        params
+            .writer
+            .get_current_block()
+            .write_opcode_and_source_info(CompilerOpcode::Dup, include.loc().clone())
+            .write_opcode_and_source_info(
+                CompilerOpcode::CheckMixinContracts,
+                include.loc().clone(),
+            );
+    }
+
     if drop_at_end {
         // remove the last leftover struct
         #[allow(deprecated)] // no entry to ascribe this write to
@@ -771,6 +1030,8 @@ where
         }
     }
 
+    reject_property_decls(&entries)?;
+
     let enum_helper_methods = generate_case_helpers_extension_for_enum(&cases);
     emit_type_members_compile(&enum_helper_methods, params, false)?;
 
@@ -797,7 +1058,7 @@ fn generate_is_case_helper_for_enum(case: &EnumCaseDecl) -> MethodDecl {
         value: "false".to_owned(),
     }));
 
-    let match_case_pattern = MatchPatternEnumCase {
+    let match_case_pattern = PatternEnumCase {
         loc: case.loc.clone(),
         case: case.name.clone(),
         payload: None,
@@ -805,7 +1066,9 @@ fn generate_is_case_helper_for_enum(case: &EnumCaseDecl) -> MethodDecl {
 
     let match_rule = MatchRule {
         loc: case.loc.clone(),
-        patterns: vec![MatchPattern::MatchPatternEnumCase(match_case_pattern)],
+        patterns: vec![MatchPattern::Pattern(Pattern::PatternEnumCase(
+            match_case_pattern,
+        ))],
         then: CodeBlock::from(&Statement::ReturnStatement(return_true_stmt)),
     };
 
@@ -828,6 +1091,7 @@ fn generate_is_case_helper_for_enum(case: &EnumCaseDecl) -> MethodDecl {
     MethodDecl {
         loc: case.loc.clone(),
         access: MethodAccess::Instance,
+        is_async: false,
         name: Identifier {
             loc: case.loc.clone(),
             value: format!("is_{}", case.name.value),
@@ -853,22 +1117,24 @@ fn generate_unwap_case_helper_for_enum(case: &EnumCaseDecl) -> MethodDecl {
         }),
     };
 
-    let match_case_pattern = MatchPatternEnumCase {
+    let match_case_pattern = PatternEnumCase {
         loc: case.loc.clone(),
         case: case.name.clone(),
-        payload: Some(DeclarationId {
+        payload: Some(Box::new(Pattern::DeclarationId(DeclarationId {
             loc: case.loc.clone(),
             name: Identifier {
                 loc: case.loc.clone(),
                 value: "__case_payload".to_owned(),
             },
             ty: None,
-        }),
+        }))),
     };
 
     let match_rule = MatchRule {
         loc: case.loc.clone(),
-        patterns: vec![MatchPattern::MatchPatternEnumCase(match_case_pattern)],
+        patterns: vec![MatchPattern::Pattern(Pattern::PatternEnumCase(
+            match_case_pattern,
+        ))],
         then: CodeBlock::from(&Statement::ReturnStatement(return_true_stmt)),
     };
 
@@ -891,6 +1157,7 @@ fn generate_unwap_case_helper_for_enum(case: &EnumCaseDecl) -> MethodDecl {
     MethodDecl {
         loc: case.loc.clone(),
         access: MethodAccess::Instance,
+        is_async: false,
         name: Identifier {
             loc: case.loc.clone(),
             value: format!("unwrap_{}", case.name.value),
@@ -934,16 +1201,28 @@ pub(crate) fn compile_from_ast(
     let scope = CompilationScope::module();
     let mut mod_init_bytecode = FunctionBuilder::default();
     let cflow = ControlFlowTargets::default();
+    let options = CompilationOptions {
+        exhaustive_match: options.exhaustive_match
+            || ast
+                .flags
+                .flags
+                .contains(&aria_parser::ast::ModuleFlag::ExhaustiveMatch),
+        ..*options
+    };
+
+    scope.set_module_enums(module_enums(ast));
 
     let mut c_params = CompileParams {
         module: &mut dest,
         scope: &scope,
         writer: &mut mod_init_bytecode,
         cflow: &cflow,
-        options,
+        options: &options,
     };
 
     ast.do_compile(&mut c_params)?;
+    dest.warnings = scope.take_warnings();
+    dest.free_names = scope.take_free_names();
 
     Ok(dest)
 }
@@ -954,11 +1233,14 @@ pub(crate) fn compile_from_source(
 ) -> CompilationResult<CompiledModule, Vec<CompilationError>> {
     let ast = match source_to_ast(src) {
         Ok(ast) => ast,
-        Err(err) => {
-            return Err(vec![CompilationError {
-                loc: err.loc,
-                reason: CompilationErrorReason::ParserError(err.msg),
-            }]);
+        Err(errs) => {
+            return Err(errs
+                .into_iter()
+                .map(|err| CompilationError {
+                    loc: err.loc,
+                    reason: CompilationErrorReason::ParserError(err.msg),
+                })
+                .collect());
         }
     };
 
@@ -1,5 +1,9 @@
 // SPDX-License-Identifier: Apache-2.0
-use crate::do_compile::{CompilationResult, CompileNode, CompileParams};
+use aria_parser::ast::Statement;
+
+use crate::do_compile::{
+    CompilationResult, CompilationWarning, CompilationWarningReason, CompileNode, CompileParams,
+};
 
 impl<'a> CompileNode<'a> for aria_parser::ast::CodeBlock {
     fn do_compile(&self, params: &'a mut CompileParams) -> CompilationResult {
@@ -12,8 +16,26 @@ impl<'a> CompileNode<'a> for aria_parser::ast::CodeBlock {
             options: params.options,
         };
 
+        // statements after one that always leaves the block can never run;
+        // only the first of them is reported
+        let mut left_block = false;
+        let mut reported = false;
         for entry in &self.entries {
+            if left_block && !reported {
+                c_params.scope.warn(CompilationWarning {
+                    loc: entry.loc().clone(),
+                    reason: CompilationWarningReason::UnreachableCode,
+                });
+                reported = true;
+            }
             entry.do_compile(&mut c_params)?;
+            left_block |= matches!(
+                entry,
+                Statement::ReturnStatement(_)
+                    | Statement::ThrowStatement(_)
+                    | Statement::BreakStatement(_)
+                    | Statement::ContinueStatement(_)
+            );
         }
         Ok(())
     }
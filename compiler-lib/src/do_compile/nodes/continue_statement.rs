@@ -3,12 +3,26 @@ use crate::{
     builder::compiler_opcodes::CompilerOpcode,
     do_compile::{
         CompilationError, CompilationErrorReason, CompilationResult, CompileNode, CompileParams,
+        emit_try_unwind,
     },
 };
 
 impl<'a> CompileNode<'a> for aria_parser::ast::ContinueStatement {
     fn do_compile(&self, params: &'a mut CompileParams) -> CompilationResult {
-        if let Some(continue_target) = &params.cflow.continue_dest {
+        let cflow = params.cflow;
+        if let Some(label) = &self.label {
+            let target = cflow.labeled_loop(label)?;
+            emit_try_unwind(params, target.try_depth, &self.loc)?;
+            params
+                .writer
+                .get_current_block()
+                .write_opcode_and_source_info(
+                    CompilerOpcode::Jump(target.continue_dest.clone()),
+                    self.loc.clone(),
+                );
+            Ok(())
+        } else if let Some(continue_target) = &cflow.continue_dest {
+            emit_try_unwind(params, cflow.loop_try_depth, &self.loc)?;
             params
                 .writer
                 .get_current_block()
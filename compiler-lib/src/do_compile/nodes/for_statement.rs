@@ -1,9 +1,10 @@
 // SPDX-License-Identifier: Apache-2.0
 use aria_parser::ast::{
     AssignStatement, BreakStatement, CodeBlock, DeclarationId, ElsePiece, Expression, Identifier,
-    IfCondPiece, IfPiece, IfStatement, MatchRule, MatchStatement, ParenExpression,
+    IfCondPiece, IfPiece, IfStatement, MatchRule, MatchStatement, ParenExpression, Pattern,
     PostfixExpression, PostfixRvalue, PostfixTerm, PostfixTermEnumCase, Primary, Statement,
-    ThrowStatement, UnaryOperation, ValDeclEntry, ValDeclStatement, WhileStatement,
+    ThrowStatement, UnaryOperation, ValDeclEntry, ValDeclStatement, ValDestructureStatement,
+    WhileStatement,
 };
 
 use crate::do_compile::{CompilationResult, CompileNode, CompileParams};
@@ -50,22 +51,45 @@ impl<'a> CompileNode<'a> for aria_parser::ast::ForStatement {
         //     }
         // }
 
+        // a loop over a pattern binds each item to a hidden name, and then
+        // destructures it as the first thing in the body, i.e.
+        //     case Some(x) => {
+        //         any_hit = true;
+        //         val <pattern> = x;
+        //         <body of the loop>
+        //     }
+        let (id, destructure) = match &self.pattern {
+            Pattern::DeclarationId(d) if d.ty.is_none() => (d.name.clone(), None),
+            pattern => {
+                let id = Identifier {
+                    loc: pattern.loc().clone(),
+                    value: format!("__for__item__{}", pattern.loc().location.start),
+                };
+                let destructure = Statement::ValDestructureStatement(ValDestructureStatement {
+                    loc: pattern.loc().clone(),
+                    pattern: pattern.clone(),
+                    val: Expression::from(&id),
+                });
+                (id, Some(destructure))
+            }
+        };
+
         // this is the iterator
         let iter_name_ident = Identifier {
             loc: self.loc.clone(),
-            value: format!("__for__iter__{}", self.id.value),
+            value: format!("__for__iter__{}", id.value),
         };
 
         // this is the next value from the iterator (the Maybe, not the actual object)
         let val_next_ident = Identifier {
             loc: self.loc.clone(),
-            value: format!("__for__next__{}", self.id.value),
+            value: format!("__for__next__{}", id.value),
         };
 
         // this becomes true when the for {} body is executed at least once
         let any_hit_ident = Identifier {
             loc: self.loc.clone(),
-            value: format!("__for__any_hit__{}", self.id.value),
+            value: format!("__for__any_hit__{}", id.value),
         };
 
         let fetch_iter_val = val_decl_statement!(
@@ -150,10 +174,13 @@ impl<'a> CompileNode<'a> for aria_parser::ast::ForStatement {
             self.loc.clone(),
             "Maybe",
             "Some",
-            Some(self.id.clone()),
+            Some(id),
             CodeBlock {
                 loc: self.loc.clone(),
-                entries: vec![assign_to_any_hit, Statement::CodeBlock(self.then.clone())],
+                entries: std::iter::once(assign_to_any_hit)
+                    .chain(destructure)
+                    .chain(std::iter::once(Statement::CodeBlock(self.then.clone())))
+                    .collect(),
             },
         );
 
@@ -169,6 +196,7 @@ impl<'a> CompileNode<'a> for aria_parser::ast::ForStatement {
                     if_not_any_hit,
                     Statement::BreakStatement(BreakStatement {
                         loc: self.loc.clone(),
+                        label: None,
                     }),
                 ],
             },
@@ -225,6 +253,7 @@ impl<'a> CompileNode<'a> for aria_parser::ast::ForStatement {
         // this is while true { do the body }
         let w = Statement::WhileStatement(WhileStatement {
             loc: self.loc.clone(),
+            label: self.label.clone(),
             cond: true_cond,
             then: while_body,
             els: None,
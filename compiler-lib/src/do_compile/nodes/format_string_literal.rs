@@ -0,0 +1,74 @@
+// SPDX-License-Identifier: Apache-2.0
+use aria_parser::ast::FormatStringPart;
+
+use crate::{
+    builder::compiler_opcodes::CompilerOpcode,
+    constant_value::ConstantValue,
+    do_compile::{
+        CompilationError, CompilationErrorReason, CompilationResult, CompileNode, CompileParams,
+    },
+};
+
+impl<'a> CompileNode<'a> for aria_parser::ast::FormatStringLiteral {
+    fn do_compile(&self, params: &'a mut CompileParams) -> CompilationResult {
+        // with nothing to interpolate, this is just a plain string constant
+        if self
+            .parts
+            .iter()
+            .all(|part| matches!(part, FormatStringPart::Text(_)))
+        {
+            let text = self
+                .parts
+                .iter()
+                .filter_map(|part| match part {
+                    FormatStringPart::Text(text) => Some(text.as_str()),
+                    FormatStringPart::Expression(_) => None,
+                })
+                .collect::<String>();
+            let const_idx =
+                self.insert_const_or_fail(params, ConstantValue::String(text), &self.loc)?;
+            params
+                .writer
+                .get_current_block()
+                .write_opcode_and_source_info(CompilerOpcode::Push(const_idx), self.loc.clone());
+            return Ok(());
+        }
+
+        let count = self.parts.len();
+        if count > u32::MAX as usize {
+            return Err(CompilationError {
+                loc: self.loc.clone(),
+                reason: CompilationErrorReason::FormatStringTooLarge,
+            });
+        }
+
+        for part in &self.parts {
+            match part {
+                FormatStringPart::Text(text) => {
+                    let const_idx = self.insert_const_or_fail(
+                        params,
+                        ConstantValue::String(text.clone()),
+                        &self.loc,
+                    )?;
+                    params
+                        .writer
+                        .get_current_block()
+                        .write_opcode_and_source_info(
+                            CompilerOpcode::Push(const_idx),
+                            self.loc.clone(),
+                        );
+                }
+                FormatStringPart::Expression(expr) => expr.do_compile(params)?,
+            }
+        }
+
+        params
+            .writer
+            .get_current_block()
+            .write_opcode_and_source_info(
+                CompilerOpcode::BuildString(count as u32),
+                self.loc.clone(),
+            );
+        Ok(())
+    }
+}
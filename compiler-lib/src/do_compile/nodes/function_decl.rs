@@ -1,5 +1,5 @@
 // SPDX-License-Identifier: Apache-2.0
-use haxby_opcodes::function_attribs::FUNC_ACCEPTS_VARARG;
+use haxby_opcodes::function_attribs::{FUNC_ACCEPTS_VARARG, FUNC_IS_ASYNC, FUNC_IS_GENERATOR};
 
 use crate::{
     builder::{compiler_opcodes::CompilerOpcode, func::FunctionBuilder},
@@ -22,12 +22,15 @@ impl<'a> CompileNode<'a> for aria_parser::ast::FunctionDecl {
             options: params.options,
         };
 
+        if self.is_async {
+            c_params.scope.as_function_root().unwrap().mark_async();
+        }
         let argc = emit_args_at_target(&[], &self.args, &[], &mut c_params)?;
 
         self.body.do_compile(&mut c_params)?;
         self.return_unit_value(&mut c_params, &self.loc)?;
 
-        let co = match writer.write(&params.module.constants, params.options) {
+        let co = match writer.write(&mut params.module.constants, params.options) {
             Ok(c) => c,
             Err(er) => {
                 return Err(CompilationError {
@@ -36,19 +39,25 @@ impl<'a> CompileNode<'a> for aria_parser::ast::FunctionDecl {
                 });
             }
         };
-        let frame_size = params.scope.as_function_root().unwrap().num_locals();
+        let f_root = params.scope.as_function_root().unwrap();
+        let frame_size = f_root.num_locals();
         let line_table = writer.write_line_table().clone();
         let a = if self.args.vararg {
             FUNC_ACCEPTS_VARARG
         } else {
             0_u8
-        };
+        } | if f_root.is_generator() {
+            FUNC_IS_GENERATOR
+        } else {
+            0
+        } | if f_root.is_async() { FUNC_IS_ASYNC } else { 0 };
         let cco = CompiledCodeObject {
             name: self.name.value.clone(),
             attribute: a,
             body: co,
             required_argc: argc.required_args,
             default_argc: argc.default_args,
+            arg_names: argc.names,
             loc: self.loc.clone(),
             line_table,
             frame_size,
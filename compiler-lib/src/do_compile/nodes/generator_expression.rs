@@ -0,0 +1,58 @@
+// SPDX-License-Identifier: Apache-2.0
+use aria_parser::ast::{
+    ArgumentDecl, ArgumentList, CodeBlock, ComprehensionClause, DeclarationId, Expression,
+    Identifier, LambdaBody, LambdaFunction, Statement, YieldStatement,
+};
+
+use crate::{
+    builder::compiler_opcodes::CompilerOpcode,
+    do_compile::{CompilationResult, CompileNode, CompileParams},
+};
+
+use super::list_comprehension::comprehension_to_statement;
+
+impl<'a> CompileNode<'a> for aria_parser::ast::GeneratorExpression {
+    fn do_compile(&self, params: &'a mut CompileParams) -> CompilationResult {
+        // a generator expression is an immediately called generator closure,
+        // which is handed the outermost iterable so that it is evaluated (and
+        // any error in it reported) where the expression is written
+        //     (|__gen__iter| => { for x in __gen__iter { if cond { yield expr; } } })(xs)
+        let iter_ident = Identifier {
+            loc: self.loc.clone(),
+            value: format!("__gen__iter__{}", self.loc.location.start),
+        };
+
+        let mut clauses = self.clauses.clone();
+        let outer_iter = match clauses.first_mut() {
+            Some(ComprehensionClause::ComprehensionFor(f)) => {
+                std::mem::replace(&mut f.expr, Expression::from(&iter_ident))
+            }
+            _ => panic!("generator expression must start with a for clause"),
+        };
+
+        let yield_expr = Statement::YieldStatement(YieldStatement {
+            loc: self.expr.loc().clone(),
+            val: self.expr.as_ref().clone(),
+        });
+        let generator = LambdaFunction {
+            loc: self.loc.clone(),
+            args: ArgumentList {
+                loc: self.loc.clone(),
+                names: vec![ArgumentDecl::from(&DeclarationId::from(&iter_ident))],
+                vararg: false,
+            },
+            body: Box::new(LambdaBody::CodeBlock(CodeBlock {
+                loc: self.loc.clone(),
+                entries: vec![comprehension_to_statement(&clauses, yield_expr)],
+            })),
+        };
+
+        outer_iter.do_compile(params)?;
+        generator.do_compile(params)?;
+        params
+            .writer
+            .get_current_block()
+            .write_opcode_and_source_info(CompilerOpcode::Call(1), self.loc.clone());
+        Ok(())
+    }
+}
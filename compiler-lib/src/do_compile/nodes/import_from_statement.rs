@@ -4,7 +4,9 @@ use haxby_opcodes::BuiltinValueId;
 use crate::{
     builder::compiler_opcodes::CompilerOpcode,
     constant_value::ConstantValue,
-    do_compile::{CompilationResult, CompileNode, CompileParams},
+    do_compile::{
+        CompilationError, CompilationErrorReason, CompilationResult, CompileNode, CompileParams,
+    },
 };
 
 impl<'a> CompileNode<'a> for aria_parser::ast::ImportFromStatement {
@@ -18,6 +20,16 @@ impl<'a> CompileNode<'a> for aria_parser::ast::ImportFromStatement {
         match &self.what {
             aria_parser::ast::ImportTarget::IdentifierList(identifiers) => {
                 for identifier in &identifiers.identifiers {
+                    // names starting with an underscore are private to their module
+                    if identifier.value.starts_with('_') {
+                        return Err(CompilationError {
+                            loc: identifier.loc.clone(),
+                            reason: CompilationErrorReason::PrivateImport(
+                                identifier.value.clone(),
+                                self.from.to_dotted_string(),
+                            ),
+                        });
+                    }
                     let ident_idx = self.insert_const_or_fail(
                         params,
                         ConstantValue::String(identifier.value.clone()),
@@ -40,6 +52,9 @@ impl<'a> CompileNode<'a> for aria_parser::ast::ImportFromStatement {
                         params.writer.get_current_block(),
                         self.loc.clone(),
                     )?;
+                    params
+                        .scope
+                        .note_import(&identifier.value, identifier.loc.clone());
                 }
             }
             aria_parser::ast::ImportTarget::All => {
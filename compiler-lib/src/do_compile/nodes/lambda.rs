@@ -9,6 +9,7 @@ impl<'a> CompileNode<'a> for aria_parser::ast::LambdaFunction {
         let f_name = format!("<anon_f_{}>", self.loc);
         let f_obj = FunctionDecl {
             loc: body.loc().clone(),
+            is_async: false,
             name: Identifier {
                 loc: self.loc.clone(),
                 value: f_name.clone(),
@@ -0,0 +1,104 @@
+// SPDX-License-Identifier: Apache-2.0
+use aria_parser::ast::{
+    CodeBlock, ComprehensionClause, ExpressionStatement, ForStatement, Identifier, IfCondPiece,
+    IfPiece, IfStatement, PostfixExpression, Primary, Statement,
+};
+
+use crate::{
+    builder::compiler_opcodes::CompilerOpcode,
+    do_compile::{CompilationResult, CompileNode, CompileParams},
+};
+
+// turns the clauses of a comprehension into the equivalent nest of for and
+// if statements, with the given statement as the innermost body, i.e.
+//     [x * y for x in xs if x > 0 for y in ys]
+// runs <body> as
+//     for x in xs { if x > 0 { for y in ys { <body> } } }
+pub(super) fn comprehension_to_statement(
+    clauses: &[ComprehensionClause],
+    body: Statement,
+) -> Statement {
+    clauses
+        .iter()
+        .rev()
+        .fold(body, |body, clause| match clause {
+            ComprehensionClause::ComprehensionFor(f) => Statement::ForStatement(ForStatement {
+                loc: f.loc.clone(),
+                label: None,
+                pattern: f.pattern.clone(),
+                expr: f.expr.clone(),
+                then: CodeBlock {
+                    loc: f.loc.clone(),
+                    entries: vec![body],
+                },
+                els: None,
+            }),
+            ComprehensionClause::ComprehensionIf(i) => Statement::IfStatement(IfStatement {
+                loc: i.loc.clone(),
+                iff: IfPiece {
+                    content: IfCondPiece {
+                        loc: i.loc.clone(),
+                        expression: Box::new(i.cond.clone()),
+                        then: CodeBlock {
+                            loc: i.loc.clone(),
+                            entries: vec![body],
+                        },
+                    },
+                },
+                elsif: vec![],
+                els: None,
+            }),
+        })
+}
+
+impl<'a> CompileNode<'a> for aria_parser::ast::ListComprehension {
+    fn do_compile(&self, params: &'a mut CompileParams) -> CompilationResult {
+        // the list being built lives in a hidden local, so that the loop body
+        // can append to it directly; it is read back once the loop is done
+        //     val __comp__list = [];
+        //     for x in xs { if cond { __comp__list.append(expr); } }
+        //     <push __comp__list>
+        let list_ident = Identifier {
+            loc: self.loc.clone(),
+            value: format!("__comp__list__{}", self.loc.location.start),
+        };
+
+        let c_scope = params.scope.child();
+        let mut c_params = CompileParams {
+            module: params.module,
+            scope: &c_scope,
+            writer: params.writer,
+            cflow: params.cflow,
+            options: params.options,
+        };
+
+        c_params
+            .writer
+            .get_current_block()
+            .write_opcode_and_source_info(CompilerOpcode::BuildList(0), self.loc.clone());
+        c_scope.emit_untyped_define(
+            &list_ident.value,
+            &mut c_params.module.constants,
+            c_params.writer.get_current_block(),
+            self.loc.clone(),
+        )?;
+
+        let append = Statement::ExpressionStatement(ExpressionStatement {
+            loc: self.expr.loc().clone(),
+            val: Some(From::from(&PostfixExpression::method_call(
+                &Primary::Identifier(list_ident.clone()),
+                "append",
+                std::slice::from_ref(self.expr.as_ref()),
+            ))),
+        });
+        comprehension_to_statement(&self.clauses, append).do_compile(&mut c_params)?;
+
+        c_scope.emit_read(
+            &list_ident.value,
+            &mut c_params.module.constants,
+            c_params.writer.get_current_block(),
+            self.loc.clone(),
+        )?;
+        Ok(())
+    }
+}
@@ -6,7 +6,7 @@ impl<'a> CompileNode<'a> for aria_parser::ast::MatchPattern {
         match self {
             Self::MatchPatternComp(e) => e.do_compile(params),
             Self::MatchPatternRel(e) => e.do_compile(params),
-            Self::MatchPatternEnumCase(e) => e.do_compile(params),
+            Self::Pattern(e) => e.do_compile(params),
         }
     }
 }
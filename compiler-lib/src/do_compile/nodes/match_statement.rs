@@ -1,6 +1,9 @@
 // SPDX-License-Identifier: Apache-2.0
+use haxby_opcodes::BuiltinTypeId;
+
 use crate::{
     builder::compiler_opcodes::CompilerOpcode,
+    constant_value::ConstantValue,
     do_compile::{CompilationResult, CompileNode, CompileParams},
 };
 
@@ -15,6 +18,7 @@ impl<'a> CompileNode<'a> for aria_parser::ast::MatchStatement {
             options: params.options,
         };
 
+        match_param.scope.check_match_exhaustiveness(self);
         self.expr.do_compile(&mut match_param)?;
 
         // store the control expression here so it can be used
@@ -86,6 +90,26 @@ impl<'a> CompileNode<'a> for aria_parser::ast::MatchStatement {
 
         if let Some(els) = &self.els {
             els.then.do_compile(&mut match_param)?;
+        } else if match_param.options.exhaustive_match {
+            // no rule matched and there is no else, which the module
+            // asked to treat as an error rather than a silent no-op
+            let case_name = self.insert_const_or_fail(
+                &mut match_param,
+                ConstantValue::String("NonExhaustiveMatch".to_owned()),
+                &self.loc,
+            )?;
+            match_param
+                .writer
+                .get_current_block()
+                .write_opcode_and_source_info(
+                    CompilerOpcode::PushBuiltinTy(BuiltinTypeId::RuntimeError),
+                    self.loc.clone(),
+                )
+                .write_opcode_and_source_info(
+                    CompilerOpcode::NewEnumVal(false, case_name),
+                    self.loc.clone(),
+                )
+                .write_opcode_and_source_info(CompilerOpcode::Throw, self.loc.clone());
         }
         match_param
             .writer
@@ -1,7 +1,9 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use aria_parser::ast::{DeclarationId, Identifier};
-use haxby_opcodes::function_attribs::{FUNC_ACCEPTS_VARARG, FUNC_IS_METHOD, METHOD_ATTRIBUTE_TYPE};
+use haxby_opcodes::function_attribs::{
+    FUNC_ACCEPTS_VARARG, FUNC_IS_ASYNC, FUNC_IS_GENERATOR, FUNC_IS_METHOD, METHOD_ATTRIBUTE_TYPE,
+};
 
 use crate::{
     builder::{compiler_opcodes::CompilerOpcode, func::FunctionBuilder},
@@ -15,7 +17,7 @@ use crate::{
 
 impl<'a> CompileNode<'a> for aria_parser::ast::MethodDecl {
     fn do_compile(&self, params: &'a mut CompileParams) -> CompilationResult {
-        let attribute = if self.args.vararg {
+        let mut attribute = if self.args.vararg {
             FUNC_ACCEPTS_VARARG
         } else {
             0
@@ -27,6 +29,10 @@ impl<'a> CompileNode<'a> for aria_parser::ast::MethodDecl {
             };
 
         let f_scope = CompilationScope::function(params.scope);
+        if self.is_async {
+            f_scope.as_function_root().unwrap().mark_async();
+            attribute |= FUNC_IS_ASYNC;
+        }
         let cflow = ControlFlowTargets::default();
         let mut writer = FunctionBuilder::default();
         let mut c_params = CompileParams {
@@ -54,9 +60,13 @@ impl<'a> CompileNode<'a> for aria_parser::ast::MethodDecl {
         self.body.do_compile(&mut c_params)?;
         self.return_unit_value(&mut c_params, &self.loc)?;
 
-        let frame_size = c_params.scope.as_function_root().unwrap().num_locals();
+        let f_root = c_params.scope.as_function_root().unwrap();
+        let frame_size = f_root.num_locals();
+        if f_root.is_generator() {
+            attribute |= FUNC_IS_GENERATOR;
+        }
 
-        let co = match writer.write(&params.module.constants, params.options) {
+        let co = match writer.write(&mut params.module.constants, params.options) {
             Ok(c) => c,
             Err(er) => {
                 return Err(CompilationError {
@@ -72,6 +82,7 @@ impl<'a> CompileNode<'a> for aria_parser::ast::MethodDecl {
             body: co,
             required_argc: argc.required_args,
             default_argc: argc.default_args,
+            arg_names: argc.names,
             loc: self.loc.clone(),
             line_table,
             frame_size,
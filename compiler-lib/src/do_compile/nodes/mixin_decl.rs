@@ -4,11 +4,16 @@ use aria_parser::ast::StringLiteral;
 
 use crate::{
     builder::compiler_opcodes::CompilerOpcode,
-    do_compile::{CompilationResult, CompileNode, CompileParams, emit_type_members_compile},
+    do_compile::{
+        CompilationResult, CompileNode, CompileParams, emit_type_members_compile,
+        reject_property_decls,
+    },
 };
 
 impl<'a> CompileNode<'a> for aria_parser::ast::MixinDecl {
     fn do_compile(&self, params: &'a mut CompileParams) -> CompilationResult {
+        reject_property_decls(&self.body)?;
+
         let self_name = StringLiteral {
             loc: self.loc.clone(),
             value: self.name.value.clone(),
@@ -27,6 +32,25 @@ impl<'a> CompileNode<'a> for aria_parser::ast::MixinDecl {
             self.loc.clone(),
         )?;
 
+        for requirement in &self.requirements {
+            let member_name = StringLiteral {
+                loc: requirement.loc.clone(),
+                value: requirement.name.value.clone(),
+            };
+            params
+                .writer
+                .get_current_block()
+                .write_opcode_and_source_info(CompilerOpcode::Dup, requirement.loc.clone());
+            member_name.do_compile(params)?;
+            params
+                .writer
+                .get_current_block()
+                .write_opcode_and_source_info(
+                    CompilerOpcode::RequireMember,
+                    requirement.loc.clone(),
+                );
+        }
+
         emit_type_members_compile(&self.body, params, true)
     }
 }
@@ -2,6 +2,7 @@
 mod add_operation;
 mod assert_statement;
 mod assign_statement;
+mod await_expression;
 mod break_statement;
 mod code_block;
 mod comp_operation;
@@ -14,19 +15,22 @@ mod expression_statement;
 mod extension_decl;
 mod float_literal;
 mod for_statement;
+mod format_string_literal;
 mod function_body;
 mod function_decl;
+mod generator_expression;
 mod identifier;
 mod if_statement;
 mod import_from_statement;
 mod import_statement;
 mod int_literal;
 mod lambda;
+mod list_comprehension;
 mod list_literal;
 mod logical_operation;
+mod map_literal;
 mod match_pattern;
 mod match_pattern_comp;
-mod match_pattern_enum_case;
 mod match_pattern_rel;
 mod match_statement;
 mod method_decl;
@@ -34,6 +38,12 @@ mod mixin_decl;
 mod mul_operation;
 mod paren_expression;
 mod parsed_module;
+mod pattern;
+mod pattern_enum_case;
+mod pattern_list;
+mod pattern_literal;
+mod pattern_struct;
+mod pattern_wildcard;
 mod postfix_rvalue;
 mod primary;
 mod rel_operation;
@@ -49,5 +59,7 @@ mod try_unwrap_expression;
 mod unary_operation;
 mod val_decl_entry;
 mod val_decl_statement;
+mod val_destructure_statement;
 mod while_statement;
 mod write_opeq_statement;
+mod yield_statement;
@@ -41,6 +41,9 @@ impl<'a> CompileNode<'a, (), Vec<CompilationError>> for ParsedModule {
                 aria_parser::ast::TopLevelEntry::ValDeclStatement(v) => {
                     collate_error_if_any!(v.do_compile(params), errors)
                 }
+                aria_parser::ast::TopLevelEntry::ValDestructureStatement(v) => {
+                    collate_error_if_any!(v.do_compile(params), errors)
+                }
                 aria_parser::ast::TopLevelEntry::WriteOpEqStatement(w) => {
                     collate_error_if_any!(w.do_compile(params), errors)
                 }
@@ -133,7 +136,7 @@ impl<'a> CompileNode<'a, (), Vec<CompilationError>> for ParsedModule {
 
         let co = match params
             .writer
-            .write(&params.module.constants, params.options)
+            .write(&mut params.module.constants, params.options)
         {
             Ok(c) => c,
             Err(e) => {
@@ -153,6 +156,7 @@ impl<'a> CompileNode<'a, (), Vec<CompilationError>> for ParsedModule {
             body: co,
             required_argc: 0,
             default_argc: 0,
+            arg_names: vec![],
             loc: self.loc.clone(),
             line_table,
             frame_size,
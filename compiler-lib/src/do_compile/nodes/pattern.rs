@@ -0,0 +1,157 @@
+// SPDX-License-Identifier: Apache-2.0
+use aria_parser::ast::{DeclarationId, SourcePointer};
+
+use crate::{
+    builder::{block::BasicBlock, compiler_opcodes::CompilerOpcode},
+    do_compile::{
+        CompilationError, CompilationErrorReason, CompilationResult, CompileNode, CompileParams,
+    },
+};
+
+// every pattern is compiled to consume the value on top of the stack and leave
+// true (hit) or false (miss) in its place, possibly defining local variables
+impl<'a> CompileNode<'a> for aria_parser::ast::Pattern {
+    fn do_compile(&self, params: &'a mut CompileParams) -> CompilationResult {
+        match self {
+            Self::PatternWildcard(w) => w.do_compile(params),
+            Self::PatternLiteral(l) => l.do_compile(params),
+            Self::PatternList(l) => l.do_compile(params),
+            Self::PatternEnumCase(c) => c.do_compile(params),
+            Self::PatternStruct(s) => s.do_compile(params),
+            Self::DeclarationId(d) => emit_binding(d, params),
+        }
+    }
+}
+
+// patterns that need to look at the value more than once store it in a local
+// first; the name is unique to the pattern so that nested patterns don't clobber
+// the value of the pattern that contains them
+pub(super) fn stash_matched_value(
+    loc: &SourcePointer,
+    params: &mut CompileParams,
+) -> CompilationResult<String> {
+    let name = format!("__pattern_value_{}", loc.location.start);
+    params.scope.emit_untyped_define(
+        &name,
+        &mut params.module.constants,
+        params.writer.get_current_block(),
+        loc.clone(),
+    )?;
+    Ok(name)
+}
+
+pub(super) fn read_matched_value(
+    name: &str,
+    loc: &SourcePointer,
+    params: &mut CompileParams,
+) -> CompilationResult {
+    params.scope.emit_read(
+        name,
+        &mut params.module.constants,
+        params.writer.get_current_block(),
+        loc.clone(),
+    )?;
+    Ok(())
+}
+
+// a pattern made of several checks: each check leaves a boolean on the stack,
+// and the first one that is false skips the rest and makes the pattern a miss
+pub(super) struct PatternChecks {
+    loc: SourcePointer,
+    check_failed: BasicBlock,
+    aftermath: BasicBlock,
+}
+
+impl PatternChecks {
+    pub(super) fn new(loc: &SourcePointer, params: &mut CompileParams) -> Self {
+        let check_failed = params
+            .writer
+            .append_block_at_end(&format!("pattern_chck_failed{}", loc));
+        let aftermath = params
+            .writer
+            .append_block_at_end(&format!("pattern_chck_aftermath{}", loc));
+        Self {
+            loc: loc.clone(),
+            check_failed,
+            aftermath,
+        }
+    }
+
+    pub(super) fn check(&self, params: &mut CompileParams) {
+        params
+            .writer
+            .get_current_block()
+            .write_opcode_and_source_info(
+                CompilerOpcode::JumpFalse(self.check_failed.clone()),
+                self.loc.clone(),
+            );
+    }
+
+    pub(super) fn finish(self, params: &mut CompileParams) {
+        params
+            .writer
+            .get_current_block()
+            .write_opcode_and_source_info(CompilerOpcode::PushTrue, self.loc.clone())
+            .write_opcode_and_source_info(
+                CompilerOpcode::Jump(self.aftermath.clone()),
+                self.loc.clone(),
+            );
+        params.writer.set_current_block(self.check_failed);
+        params
+            .writer
+            .get_current_block()
+            .write_opcode_and_source_info(CompilerOpcode::PushFalse, self.loc.clone())
+            .write_opcode_and_source_info(
+                CompilerOpcode::Jump(self.aftermath.clone()),
+                self.loc.clone(),
+            );
+        params.writer.set_current_block(self.aftermath);
+    }
+}
+
+// a name always matches and binds the value, unless it has a type that the
+// value is not an instance of
+pub(super) fn emit_binding(decl: &DeclarationId, params: &mut CompileParams) -> CompilationResult {
+    if matches!(decl.name.value.as_str(), "true" | "false") {
+        return Err(CompilationError {
+            loc: decl.loc.clone(),
+            reason: CompilationErrorReason::ReservedIdentifier(decl.name.value.clone()),
+        });
+    }
+
+    match &decl.ty {
+        None => {
+            params.scope.emit_untyped_define(
+                &decl.name.value,
+                &mut params.module.constants,
+                params.writer.get_current_block(),
+                decl.loc.clone(),
+            )?;
+            params
+                .writer
+                .get_current_block()
+                .write_opcode_and_source_info(CompilerOpcode::PushTrue, decl.loc.clone());
+        }
+        Some(ty) => {
+            let value = stash_matched_value(&decl.loc, params)?;
+            let checks = PatternChecks::new(&decl.loc, params);
+            read_matched_value(&value, &decl.loc, params)?;
+            ty.do_compile(params)?;
+            params
+                .writer
+                .get_current_block()
+                .write_opcode_and_source_info(CompilerOpcode::Isa, decl.loc.clone());
+            checks.check(params);
+            read_matched_value(&value, &decl.loc, params)?;
+            params.scope.emit_untyped_define(
+                &decl.name.value,
+                &mut params.module.constants,
+                params.writer.get_current_block(),
+                decl.loc.clone(),
+            )?;
+            checks.finish(params);
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,60 @@
+// SPDX-License-Identifier: Apache-2.0
+use crate::{
+    builder::compiler_opcodes::CompilerOpcode,
+    constant_value::ConstantValue,
+    do_compile::{
+        CompilationResult, CompileNode, CompileParams,
+        nodes::pattern::{PatternChecks, read_matched_value, stash_matched_value},
+    },
+};
+
+impl<'a> CompileNode<'a> for aria_parser::ast::PatternEnumCase {
+    fn do_compile(&self, params: &'a mut CompileParams) -> CompilationResult {
+        let case_name_idx = self.insert_const_or_fail(
+            params,
+            ConstantValue::String(self.case.value.clone()),
+            &self.case.loc,
+        )?;
+
+        let payload = match &self.payload {
+            None => {
+                params
+                    .writer
+                    .get_current_block()
+                    .write_opcode_and_source_info(
+                        CompilerOpcode::EnumCheckIsCase(case_name_idx),
+                        self.case.loc.clone(),
+                    );
+                return Ok(());
+            }
+            Some(payload) => payload,
+        };
+
+        let value = stash_matched_value(&self.loc, params)?;
+        let checks = PatternChecks::new(&self.loc, params);
+        read_matched_value(&value, &self.loc, params)?;
+        params
+            .writer
+            .get_current_block()
+            .write_opcode_and_source_info(
+                CompilerOpcode::EnumCheckIsCase(case_name_idx),
+                self.case.loc.clone(),
+            );
+        checks.check(params);
+        // we know we have a case match - now extract the payload, if any
+        read_matched_value(&value, &self.loc, params)?;
+        params
+            .writer
+            .get_current_block()
+            .write_opcode_and_source_info(
+                CompilerOpcode::EnumTryExtractPayload,
+                payload.loc().clone(),
+            );
+        checks.check(params);
+        // the payload is on the stack, and has to match in turn
+        payload.do_compile(params)?;
+        checks.check(params);
+        checks.finish(params);
+        Ok(())
+    }
+}
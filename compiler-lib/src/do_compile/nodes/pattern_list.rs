@@ -0,0 +1,63 @@
+// SPDX-License-Identifier: Apache-2.0
+use crate::{
+    builder::compiler_opcodes::CompilerOpcode,
+    constant_value::ConstantValue,
+    do_compile::{
+        CompilationResult, CompileNode, CompileParams,
+        nodes::pattern::{PatternChecks, read_matched_value, stash_matched_value},
+    },
+};
+
+impl<'a> CompileNode<'a> for aria_parser::ast::PatternList {
+    fn do_compile(&self, params: &'a mut CompileParams) -> CompilationResult {
+        let value = stash_matched_value(&self.loc, params)?;
+        let checks = PatternChecks::new(&self.loc, params);
+
+        // only a list can match, and only if it has enough items for the pattern;
+        // without a rest pattern, it must also not have any more than that
+        read_matched_value(&value, &self.loc, params)?;
+        params
+            .writer
+            .get_current_block()
+            .write_opcode_and_source_info(
+                CompilerOpcode::ListCheckLen(self.rest.is_some(), self.items.len() as u32),
+                self.loc.clone(),
+            );
+        checks.check(params);
+
+        for (idx, item) in self.items.iter().enumerate() {
+            let idx_const =
+                self.insert_const_or_fail(params, ConstantValue::Integer(idx as i64), item.loc())?;
+            read_matched_value(&value, item.loc(), params)?;
+            params
+                .writer
+                .get_current_block()
+                .write_opcode_and_source_info(CompilerOpcode::Push(idx_const), item.loc().clone())
+                .write_opcode_and_source_info(CompilerOpcode::ReadIndex(1), item.loc().clone());
+            item.do_compile(params)?;
+            checks.check(params);
+        }
+
+        if let Some(rest) = &self.rest
+            && let Some(name) = &rest.name
+        {
+            read_matched_value(&value, &rest.loc, params)?;
+            params
+                .writer
+                .get_current_block()
+                .write_opcode_and_source_info(
+                    CompilerOpcode::ListTail(self.items.len() as u32),
+                    rest.loc.clone(),
+                );
+            params.scope.emit_untyped_define(
+                &name.value,
+                &mut params.module.constants,
+                params.writer.get_current_block(),
+                rest.loc.clone(),
+            )?;
+        }
+
+        checks.finish(params);
+        Ok(())
+    }
+}
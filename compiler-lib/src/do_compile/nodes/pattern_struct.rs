@@ -0,0 +1,55 @@
+// SPDX-License-Identifier: Apache-2.0
+use aria_parser::ast::DeclarationId;
+
+use crate::{
+    builder::compiler_opcodes::CompilerOpcode,
+    constant_value::ConstantValue,
+    do_compile::{
+        CompilationResult, CompileNode, CompileParams,
+        nodes::pattern::{PatternChecks, emit_binding, read_matched_value, stash_matched_value},
+    },
+};
+
+impl<'a> CompileNode<'a> for aria_parser::ast::PatternStruct {
+    fn do_compile(&self, params: &'a mut CompileParams) -> CompilationResult {
+        let value = stash_matched_value(&self.loc, params)?;
+        let checks = PatternChecks::new(&self.loc, params);
+
+        if let Some(ty) = &self.ty {
+            read_matched_value(&value, &self.loc, params)?;
+            ty.do_compile(params)?;
+            params
+                .writer
+                .get_current_block()
+                .write_opcode_and_source_info(CompilerOpcode::Isa, self.loc.clone());
+            checks.check(params);
+        }
+
+        for field in &self.fields {
+            let field_name_idx = self.insert_const_or_fail(
+                params,
+                ConstantValue::String(field.name.value.clone()),
+                &field.loc,
+            )?;
+            // a value without the field is a miss, rather than an error
+            read_matched_value(&value, &field.loc, params)?;
+            params
+                .writer
+                .get_current_block()
+                .write_opcode_and_source_info(
+                    CompilerOpcode::TryReadAttribute(field_name_idx),
+                    field.loc.clone(),
+                );
+            checks.check(params);
+            // a field without a pattern binds a variable of the same name
+            match &field.pattern {
+                Some(pattern) => pattern.do_compile(params)?,
+                None => emit_binding(&DeclarationId::from(&field.name), params)?,
+            }
+            checks.check(params);
+        }
+
+        checks.finish(params);
+        Ok(())
+    }
+}
@@ -0,0 +1,16 @@
+// SPDX-License-Identifier: Apache-2.0
+use crate::{
+    builder::compiler_opcodes::CompilerOpcode,
+    do_compile::{CompilationResult, CompileNode, CompileParams},
+};
+
+impl<'a> CompileNode<'a> for aria_parser::ast::PatternWildcard {
+    fn do_compile(&self, params: &'a mut CompileParams) -> CompilationResult {
+        params
+            .writer
+            .get_current_block()
+            .write_opcode_and_source_info(CompilerOpcode::Pop, self.loc.clone())
+            .write_opcode_and_source_info(CompilerOpcode::PushTrue, self.loc.clone());
+        Ok(())
+    }
+}
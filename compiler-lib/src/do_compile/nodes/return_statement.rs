@@ -1,18 +1,30 @@
 // SPDX-License-Identifier: Apache-2.0
 use crate::{
     builder::compiler_opcodes::CompilerOpcode,
-    do_compile::{CompilationResult, CompileNode, CompileParams},
+    do_compile::{CompilationResult, CompileNode, CompileParams, emit_try_unwind},
 };
 
 impl<'a> CompileNode<'a> for aria_parser::ast::ReturnStatement {
     fn do_compile(&self, params: &'a mut CompileParams) -> CompilationResult {
         if let Some(val) = &self.val {
             val.do_compile(params)?;
+            // `return f(...)` can reuse the caller's frame, unless a try statement
+            // still has to be left (and its finally block run) after the call
+            if params.cflow.enclosing_try.is_empty()
+                && params
+                    .writer
+                    .get_current_block()
+                    .make_trailing_call_tail_call()
+            {
+                return Ok(());
+            }
+            emit_try_unwind(params, 0, &self.loc)?;
             params
                 .writer
                 .get_current_block()
                 .write_opcode_and_source_info(CompilerOpcode::Return, self.loc.clone());
         } else {
+            emit_try_unwind(params, 0, &self.loc)?;
             self.return_unit_value(params, &self.loc)?;
         }
         Ok(())
@@ -5,6 +5,7 @@ impl<'a> CompileNode<'a> for aria_parser::ast::Statement {
     fn do_compile(&self, params: &'a mut CompileParams) -> CompilationResult {
         match self {
             Self::ValDeclStatement(l) => l.do_compile(params),
+            Self::ValDestructureStatement(l) => l.do_compile(params),
             Self::AssignStatement(a) => a.do_compile(params),
             Self::WriteOpEqStatement(w) => w.do_compile(params),
             Self::IfStatement(i) => i.do_compile(params),
@@ -13,6 +14,7 @@ impl<'a> CompileNode<'a> for aria_parser::ast::Statement {
             Self::ForStatement(f) => f.do_compile(params),
             Self::ReturnStatement(r) => r.do_compile(params),
             Self::ThrowStatement(t) => t.do_compile(params),
+            Self::YieldStatement(y) => y.do_compile(params),
             Self::TryBlock(t) => t.do_compile(params),
             Self::AssertStatement(a) => a.do_compile(params),
             Self::CodeBlock(c) => c.do_compile(params),
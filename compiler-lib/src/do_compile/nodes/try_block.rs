@@ -1,21 +1,86 @@
 // SPDX-License-Identifier: Apache-2.0
+use aria_parser::ast::FinallyPiece;
+
 use crate::{
     builder::compiler_opcodes::CompilerOpcode,
     do_compile::{CompilationResult, CompileNode, CompileParams},
 };
 
+fn emit_finally(finally: &FinallyPiece, params: &mut CompileParams) -> CompilationResult {
+    let f_scope = params.scope.child();
+    let mut f_params = CompileParams {
+        module: params.module,
+        scope: &f_scope,
+        writer: params.writer,
+        cflow: params.cflow,
+        options: params.options,
+    };
+    finally.then.do_compile(&mut f_params)
+}
+
 impl<'a> CompileNode<'a> for aria_parser::ast::TryBlock {
     fn do_compile(&self, params: &'a mut CompileParams) -> CompilationResult {
+        // with a finally, the whole statement is wrapped in an extra handler
+        // which runs the finally code and rethrows; the normal exits (and
+        // break, continue, return) run a copy of the finally code instead
+        //     TryEnter(finally_handler)
+        //     TryEnter(catch)
+        //     <body>
+        //     TryExit
+        //     TryExit
+        //     <finally>
+        //     Jump(after)
+        // catch:
+        //     <catch>
+        //     TryExit
+        //     <finally>
+        //     Jump(after)
+        // finally_handler:
+        //     <finally>
+        //     Throw
         let try_block = params.writer.insert_block_after(
             &format!("try_{}", &self.body.loc),
             &params.writer.get_current_block(),
         );
-        let catch_block = params
-            .writer
-            .insert_block_after(&format!("catch_{}", &self.catch.loc), &try_block);
-        let after_block = params
-            .writer
-            .insert_block_after(&format!("try_after_catch_{}", &self.body.loc), &catch_block);
+        let catch_block = self.catch.as_ref().map(|catch| {
+            params
+                .writer
+                .insert_block_after(&format!("catch_{}", &catch.loc), &try_block)
+        });
+        let finally_block = self.finally.as_ref().map(|finally| {
+            params.writer.insert_block_after(
+                &format!("finally_{}", &finally.loc),
+                catch_block.as_ref().unwrap_or(&try_block),
+            )
+        });
+        let after_block = params.writer.insert_block_after(
+            &format!("try_after_catch_{}", &self.body.loc),
+            finally_block
+                .as_ref()
+                .or(catch_block.as_ref())
+                .unwrap_or(&try_block),
+        );
+
+        // break, continue and return run the finally code from within the
+        // body and the catch, and also need to leave the catch handler from
+        // within the body
+        let finally_cflow;
+        let finally_cflow = match &self.finally {
+            Some(finally) => {
+                finally_cflow = params
+                    .cflow
+                    .for_try(Some(finally.then.clone()), params.scope);
+                &finally_cflow
+            }
+            None => params.cflow,
+        };
+        let body_cflow;
+        let body_cflow = if self.catch.is_some() {
+            body_cflow = finally_cflow.for_try(None, params.scope);
+            &body_cflow
+        } else {
+            finally_cflow
+        };
 
         params
             .writer
@@ -25,51 +90,123 @@ impl<'a> CompileNode<'a> for aria_parser::ast::TryBlock {
                 self.loc.clone(),
             );
         params.writer.set_current_block(try_block);
-        params
-            .writer
-            .get_current_block()
-            .write_opcode_and_source_info(
-                CompilerOpcode::TryEnter(catch_block.clone()),
-                self.loc.clone(),
-            );
-        self.body.do_compile(params)?;
-        params
-            .writer
-            .get_current_block()
-            .write_opcode_and_source_info(CompilerOpcode::TryExit, self.loc.clone());
-        params
-            .writer
-            .get_current_block()
-            .write_opcode_and_source_info(
-                CompilerOpcode::Jump(after_block.clone()),
-                self.loc.clone(),
-            );
-        params.writer.set_current_block(catch_block);
+        if let Some(finally_block) = &finally_block {
+            params
+                .writer
+                .get_current_block()
+                .write_opcode_and_source_info(
+                    CompilerOpcode::TryEnter(finally_block.clone()),
+                    self.loc.clone(),
+                );
+        }
+        if let Some(catch_block) = &catch_block {
+            params
+                .writer
+                .get_current_block()
+                .write_opcode_and_source_info(
+                    CompilerOpcode::TryEnter(catch_block.clone()),
+                    self.loc.clone(),
+                );
+        }
 
-        let catch_scope = params.scope.child();
-        let mut catch_params = CompileParams {
+        let mut body_params = CompileParams {
             module: params.module,
-            scope: &catch_scope,
+            scope: params.scope,
             writer: params.writer,
-            cflow: params.cflow,
+            cflow: body_cflow,
             options: params.options,
         };
-        catch_params.scope.emit_untyped_define(
-            &self.id.value,
-            &mut catch_params.module.constants,
-            catch_params.writer.get_current_block(),
-            self.id.loc.clone(),
-        )?;
-
-        self.catch.do_compile(&mut catch_params)?;
-        catch_params
+        self.body.do_compile(&mut body_params)?;
+        if self.catch.is_some() {
+            params
+                .writer
+                .get_current_block()
+                .write_opcode_and_source_info(CompilerOpcode::TryExit, self.loc.clone());
+        }
+        if let Some(finally) = &self.finally {
+            params
+                .writer
+                .get_current_block()
+                .write_opcode_and_source_info(CompilerOpcode::TryExit, self.loc.clone());
+            emit_finally(finally, params)?;
+        }
+        params
             .writer
             .get_current_block()
             .write_opcode_and_source_info(
                 CompilerOpcode::Jump(after_block.clone()),
                 self.loc.clone(),
             );
-        catch_params.writer.set_current_block(after_block);
+
+        if let (Some(catch), Some(catch_block)) = (&self.catch, catch_block) {
+            params.writer.set_current_block(catch_block);
+
+            let catch_scope = params.scope.child();
+            let mut catch_params = CompileParams {
+                module: params.module,
+                scope: &catch_scope,
+                writer: params.writer,
+                cflow: finally_cflow,
+                options: params.options,
+            };
+            catch_params.scope.emit_untyped_define(
+                &catch.id.value,
+                &mut catch_params.module.constants,
+                catch_params.writer.get_current_block(),
+                catch.id.loc.clone(),
+            )?;
+
+            catch.then.do_compile(&mut catch_params)?;
+            if let Some(finally) = &self.finally {
+                params
+                    .writer
+                    .get_current_block()
+                    .write_opcode_and_source_info(CompilerOpcode::TryExit, self.loc.clone());
+                emit_finally(finally, params)?;
+            }
+            params
+                .writer
+                .get_current_block()
+                .write_opcode_and_source_info(
+                    CompilerOpcode::Jump(after_block.clone()),
+                    self.loc.clone(),
+                );
+        }
+
+        if let (Some(finally), Some(finally_block)) = (&self.finally, finally_block) {
+            params.writer.set_current_block(finally_block);
+
+            // the exception is on the stack here; stash it while the
+            // finally code runs, then rethrow it
+            let exception_name = format!("__finally_exception_{}", finally.loc.location.start);
+            let handler_scope = params.scope.child();
+            let mut handler_params = CompileParams {
+                module: params.module,
+                scope: &handler_scope,
+                writer: params.writer,
+                cflow: params.cflow,
+                options: params.options,
+            };
+            handler_params.scope.emit_untyped_define(
+                &exception_name,
+                &mut handler_params.module.constants,
+                handler_params.writer.get_current_block(),
+                finally.loc.clone(),
+            )?;
+            emit_finally(finally, &mut handler_params)?;
+            handler_params.scope.emit_read(
+                &exception_name,
+                &mut handler_params.module.constants,
+                handler_params.writer.get_current_block(),
+                finally.loc.clone(),
+            )?;
+            handler_params
+                .writer
+                .get_current_block()
+                .write_opcode_and_source_info(CompilerOpcode::Throw, finally.loc.clone());
+        }
+
+        params.writer.set_current_block(after_block);
 
         Ok(())
     }
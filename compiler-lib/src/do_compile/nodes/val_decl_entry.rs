@@ -32,7 +32,7 @@ impl<'a> CompileNode<'a> for aria_parser::ast::ValDeclEntry {
                     self.loc.clone(),
                 );
         }
-        params.scope.emit_typed_define(
+        params.scope.emit_variable_define(
             &self.id.name.value,
             &mut params.module.constants,
             params.writer.get_current_block(),
@@ -0,0 +1,44 @@
+// SPDX-License-Identifier: Apache-2.0
+use haxby_opcodes::BuiltinTypeId;
+
+use crate::{
+    builder::compiler_opcodes::CompilerOpcode,
+    constant_value::ConstantValue,
+    do_compile::{CompilationResult, CompileNode, CompileParams},
+};
+
+impl<'a> CompileNode<'a> for aria_parser::ast::ValDestructureStatement {
+    fn do_compile(&self, params: &'a mut CompileParams) -> CompilationResult {
+        self.val.do_compile(params)?;
+        self.pattern.do_compile(params)?;
+
+        // a value that does not have the shape of the pattern is a type error
+        let destructure_ok = params.writer.insert_block_after(
+            &format!("destructure_ok_{}", self.loc),
+            &params.writer.get_current_block(),
+        );
+        let unexpected_type_idx = self.insert_const_or_fail(
+            params,
+            ConstantValue::String("UnexpectedType".to_owned()),
+            &self.loc,
+        )?;
+        params
+            .writer
+            .get_current_block()
+            .write_opcode_and_source_info(
+                CompilerOpcode::JumpTrue(destructure_ok.clone()),
+                self.loc.clone(),
+            )
+            .write_opcode_and_source_info(
+                CompilerOpcode::PushBuiltinTy(BuiltinTypeId::RuntimeError),
+                self.loc.clone(),
+            )
+            .write_opcode_and_source_info(
+                CompilerOpcode::NewEnumVal(false, unexpected_type_idx),
+                self.loc.clone(),
+            )
+            .write_opcode_and_source_info(CompilerOpcode::Throw, self.loc.clone());
+        params.writer.set_current_block(destructure_ok);
+        Ok(())
+    }
+}
@@ -1,7 +1,7 @@
 // SPDX-License-Identifier: Apache-2.0
 use crate::{
     builder::compiler_opcodes::CompilerOpcode,
-    do_compile::{CompilationResult, CompileNode, CompileParams, ControlFlowTargets},
+    do_compile::{CompilationResult, CompileNode, CompileParams},
 };
 
 impl<'a> CompileNode<'a> for aria_parser::ast::WhileStatement {
@@ -22,10 +22,9 @@ impl<'a> CompileNode<'a> for aria_parser::ast::WhileStatement {
             .writer
             .append_block_at_end(&format!("after_{}", self.loc));
 
-        let w_cflow = ControlFlowTargets {
-            break_dest: Some(after.clone()),
-            continue_dest: Some(check.clone()),
-        };
+        let w_cflow = params
+            .cflow
+            .for_loop(after.clone(), check.clone(), self.label.as_ref());
 
         let mut c_params = CompileParams {
             module: params.module,
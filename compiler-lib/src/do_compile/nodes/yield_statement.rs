@@ -0,0 +1,31 @@
+// SPDX-License-Identifier: Apache-2.0
+use crate::{
+    builder::compiler_opcodes::CompilerOpcode,
+    do_compile::{
+        CompilationError, CompilationErrorReason, CompilationResult, CompileNode, CompileParams,
+    },
+};
+
+impl<'a> CompileNode<'a> for aria_parser::ast::YieldStatement {
+    fn do_compile(&self, params: &'a mut CompileParams) -> CompilationResult {
+        if params.scope.is_in_async_function() {
+            return Err(CompilationError {
+                loc: self.loc.clone(),
+                reason: CompilationErrorReason::YieldInAsyncFunction,
+            });
+        }
+        if !params.scope.mark_generator() {
+            return Err(CompilationError {
+                loc: self.loc.clone(),
+                reason: CompilationErrorReason::FlowControlNotAllowed,
+            });
+        }
+
+        self.val.do_compile(params)?;
+        params
+            .writer
+            .get_current_block()
+            .write_opcode_and_source_info(CompilerOpcode::Yield, self.loc.clone());
+        Ok(())
+    }
+}
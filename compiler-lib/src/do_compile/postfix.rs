@@ -2,10 +2,14 @@
 use aria_parser::ast::{Expression, ExpressionList, Identifier, SourcePointer};
 use haxby_opcodes::BuiltinTypeId;
 
-use crate::{builder::compiler_opcodes::CompilerOpcode, constant_value::ConstantValue};
+use crate::{
+    builder::{block::BasicBlock, compiler_opcodes::CompilerOpcode},
+    constant_value::ConstantValue,
+};
 
 use super::{
     CompilationError, CompilationErrorReason, CompilationResult, CompileNode, CompileParams,
+    emit_try_unwind,
 };
 
 #[derive(Debug)]
@@ -40,37 +44,99 @@ impl ObjWrite {
 pub(super) enum PostfixValue {
     Primary(Box<aria_parser::ast::Primary>),
     Attribute(Box<PostfixValue>, Box<Identifier>),
-    Call(Box<PostfixValue>, Box<ExpressionList>, SourcePointer),
+    Call(Box<PostfixValue>, Box<aria_parser::ast::PostfixTermCall>),
     Case(Box<PostfixValue>, Box<Identifier>, Option<Expression>),
     Index(Box<PostfixValue>, Box<aria_parser::ast::ExpressionList>),
+    Slice(Box<PostfixValue>, Box<aria_parser::ast::PostfixTermSlice>),
     ObjWrite(Box<PostfixValue>, Vec<ObjWrite>),
     TryProtocol(
         Box<PostfixValue>,
         Box<aria_parser::ast::PostfixTermTryProtocol>,
     ),
+    SafeNav(Box<PostfixValue>, Box<aria_parser::ast::PostfixTermSafeNav>),
+    // the rest of a chain past its first ?. evaluates to Maybe::Some(value),
+    // unless any ?. in it finds no value and the whole chain becomes Maybe::None
+    SafeChain(Box<PostfixValue>, Box<aria_parser::ast::PostfixTermSafeNav>),
+}
+
+// where a ?. that finds no value jumps to, and how many values pushed ahead
+// of it (e.g. call arguments, which go before their callee) it must discard
+#[derive(Clone)]
+struct SafeNavExit {
+    none_block: BasicBlock,
+    pending: usize,
+}
+
+impl SafeNavExit {
+    fn after(&self, pushed: usize) -> Self {
+        Self {
+            none_block: self.none_block.clone(),
+            pending: self.pending + pushed,
+        }
+    }
 }
 
 impl<'a> PostfixValue {
     pub(super) fn emit_read(&self, params: &'a mut CompileParams) -> CompilationResult {
+        self.emit_read_chain(params, None)
+    }
+
+    fn emit_read_chain(
+        &self,
+        params: &'a mut CompileParams,
+        exit: Option<&SafeNavExit>,
+    ) -> CompilationResult {
         match self {
             PostfixValue::Primary(primary) => primary.do_compile(params),
-            PostfixValue::Call(base, args, loc) => {
-                for expr in args.expressions.iter().rev() {
+            PostfixValue::Call(base, call) => {
+                // keyword arguments go below the positional ones, each value
+                // followed by its name, for the VM to match against the callee
+                for kwarg in call.keyword_args.iter().rev() {
+                    kwarg.value.do_compile(params)?;
+                    let name_idx = params
+                        .module
+                        .constants
+                        .insert(ConstantValue::String(kwarg.name.value.clone()))
+                        .map_err(|_| CompilationError {
+                            loc: kwarg.name.loc.clone(),
+                            reason: CompilationErrorReason::TooManyConstants,
+                        })?;
+                    params
+                        .writer
+                        .get_current_block()
+                        .write_opcode_and_source_info(
+                            CompilerOpcode::Push(name_idx),
+                            kwarg.name.loc.clone(),
+                        );
+                }
+                for expr in call.args.expressions.iter().rev() {
                     expr.do_compile(params)?;
                 }
-                let argc = args.expressions.len();
-                base.emit_read(params)?;
+                let argc = call.args.expressions.len();
+                let kwargc = call.keyword_args.len();
+                if argc + kwargc > u8::MAX.into() {
+                    return Err(CompilationError {
+                        loc: call.loc.clone(),
+                        reason: CompilationErrorReason::TooManyArguments,
+                    });
+                }
+                base.emit_read_chain(params, exit.map(|e| e.after(argc + 2 * kwargc)).as_ref())?;
+                let op = if kwargc == 0 {
+                    CompilerOpcode::Call(argc as u8)
+                } else {
+                    CompilerOpcode::CallNamed(argc as u8, kwargc as u8)
+                };
                 params
                     .writer
                     .get_current_block()
-                    .write_opcode_and_source_info(CompilerOpcode::Call(argc as u8), loc.clone());
+                    .write_opcode_and_source_info(op, call.loc.clone());
                 Ok(())
             }
             PostfixValue::Case(base, case, payload) => {
                 if let Some(p) = payload {
                     p.do_compile(params)?;
                 }
-                base.emit_read(params)?;
+                base.emit_read_chain(params, exit.map(|e| e.after(payload.iter().len())).as_ref())?;
                 let identifier_idx = match params
                     .module
                     .constants
@@ -94,7 +160,7 @@ impl<'a> PostfixValue {
                 Ok(())
             }
             PostfixValue::Index(base, index) => {
-                base.emit_read(params)?;
+                base.emit_read_chain(params, exit)?;
                 index.do_compile(params)?;
                 params
                     .writer
@@ -105,6 +171,30 @@ impl<'a> PostfixValue {
                     );
                 Ok(())
             }
+            PostfixValue::Slice(base, slice) => {
+                use haxby_opcodes::read_slice_attribs::{HAS_START, HAS_STEP, HAS_STOP};
+
+                base.emit_read_chain(params, exit)?;
+                let mut flags = 0;
+                for (bound, flag) in [
+                    (&slice.start, HAS_START),
+                    (&slice.stop, HAS_STOP),
+                    (&slice.step, HAS_STEP),
+                ] {
+                    if let Some(bound) = bound {
+                        bound.do_compile(params)?;
+                        flags |= flag;
+                    }
+                }
+                params
+                    .writer
+                    .get_current_block()
+                    .write_opcode_and_source_info(
+                        CompilerOpcode::ReadSlice(flags),
+                        slice.loc.clone(),
+                    );
+                Ok(())
+            }
             PostfixValue::Attribute(base, identifier) => {
                 let identifier_idx = match params
                     .module
@@ -119,7 +209,7 @@ impl<'a> PostfixValue {
                         });
                     }
                 };
-                base.emit_read(params)?;
+                base.emit_read_chain(params, exit)?;
                 params
                     .writer
                     .get_current_block()
@@ -130,7 +220,7 @@ impl<'a> PostfixValue {
                 Ok(())
             }
             PostfixValue::ObjWrite(base, terms) => {
-                base.emit_read(params)?;
+                base.emit_read_chain(params, exit)?;
                 for term in terms {
                     params
                         .writer
@@ -181,7 +271,7 @@ impl<'a> PostfixValue {
                     }
                 };
 
-                base.emit_read(params)?;
+                base.emit_read_chain(params, exit)?;
 
                 let try_unwrap_protocol_idx = params
                     .module
@@ -202,11 +292,175 @@ impl<'a> PostfixValue {
                         CompilerOpcode::ReadAttribute(try_unwrap_protocol_idx),
                         tp.loc.clone(),
                     )
-                    .write_opcode_and_source_info(CompilerOpcode::Call(1), tp.loc.clone())
+                    .write_opcode_and_source_info(CompilerOpcode::Call(1), tp.loc.clone());
+
+                // returning the error straight out of the VM would skip
+                // any finally blocks, so let the caller do the return
+                if mode == haxby_opcodes::try_unwrap_protocol_mode::PROPAGATE_ERROR
+                    && params
+                        .cflow
+                        .enclosing_try
+                        .iter()
+                        .any(|t| t.finally.is_some())
+                {
+                    let ok_block = params
+                        .writer
+                        .append_block_at_end(&format!("try_protocol_ok_{}", tp.loc));
+                    params
+                        .writer
+                        .get_current_block()
+                        .write_opcode_and_source_info(
+                            CompilerOpcode::TryUnwrapProtocol(
+                                haxby_opcodes::try_unwrap_protocol_mode::FLAG_TO_CALLER,
+                            ),
+                            tp.loc.clone(),
+                        )
+                        .write_opcode_and_source_info(
+                            CompilerOpcode::JumpTrue(ok_block.clone()),
+                            tp.loc.clone(),
+                        );
+                    emit_try_unwind(params, 0, &tp.loc)?;
+                    params
+                        .writer
+                        .get_current_block()
+                        .write_opcode_and_source_info(CompilerOpcode::Return, tp.loc.clone());
+                    params.writer.set_current_block(ok_block);
+                } else {
+                    params
+                        .writer
+                        .get_current_block()
+                        .write_opcode_and_source_info(
+                            CompilerOpcode::TryUnwrapProtocol(mode),
+                            tp.loc.clone(),
+                        );
+                }
+                Ok(())
+            }
+            PostfixValue::SafeNav(base, nav) => {
+                let exit = exit.expect("safe navigation outside of a safe chain");
+                base.emit_read_chain(params, Some(exit))?;
+
+                let try_unwrap_protocol_idx = params
+                    .module
+                    .constants
+                    .insert(ConstantValue::String("try_unwrap_protocol".to_string()))
+                    .map_err(|_| CompilationError {
+                        loc: nav.loc.clone(),
+                        reason: CompilationErrorReason::TooManyConstants,
+                    })?;
+                let some_block = params
+                    .writer
+                    .append_block_at_end(&format!("safe_nav_some_{}", nav.loc));
+                let none_block = params
+                    .writer
+                    .append_block_at_end(&format!("safe_nav_none_{}", nav.loc));
+                params
+                    .writer
+                    .get_current_block()
                     .write_opcode_and_source_info(
-                        CompilerOpcode::TryUnwrapProtocol(mode),
-                        tp.loc.clone(),
+                        CompilerOpcode::PushBuiltinTy(BuiltinTypeId::Result),
+                        nav.loc.clone(),
+                    )
+                    .write_opcode_and_source_info(
+                        CompilerOpcode::ReadAttribute(try_unwrap_protocol_idx),
+                        nav.loc.clone(),
+                    )
+                    .write_opcode_and_source_info(CompilerOpcode::Call(1), nav.loc.clone())
+                    .write_opcode_and_source_info(
+                        CompilerOpcode::TryUnwrapProtocol(
+                            haxby_opcodes::try_unwrap_protocol_mode::FLAG_TO_CALLER,
+                        ),
+                        nav.loc.clone(),
+                    )
+                    .write_opcode_and_source_info(
+                        CompilerOpcode::JumpConditionally(some_block.clone(), none_block.clone()),
+                        nav.loc.clone(),
+                    );
+
+                // drop the None (or Err) along with anything pushed ahead of it
+                params.writer.set_current_block(none_block);
+                for _ in 0..=exit.pending {
+                    params
+                        .writer
+                        .get_current_block()
+                        .write_opcode_and_source_info(CompilerOpcode::Pop, nav.loc.clone());
+                }
+                params
+                    .writer
+                    .get_current_block()
+                    .write_opcode_and_source_info(
+                        CompilerOpcode::Jump(exit.none_block.clone()),
+                        nav.loc.clone(),
                     );
+
+                params.writer.set_current_block(some_block);
+                Ok(())
+            }
+            PostfixValue::SafeChain(chain, nav) => {
+                let some_idx = params
+                    .module
+                    .constants
+                    .insert(ConstantValue::String("Some".to_string()))
+                    .map_err(|_| CompilationError {
+                        loc: nav.loc.clone(),
+                        reason: CompilationErrorReason::TooManyConstants,
+                    })?;
+                let none_idx = params
+                    .module
+                    .constants
+                    .insert(ConstantValue::String("None".to_string()))
+                    .map_err(|_| CompilationError {
+                        loc: nav.loc.clone(),
+                        reason: CompilationErrorReason::TooManyConstants,
+                    })?;
+                let none_block = params
+                    .writer
+                    .append_block_at_end(&format!("safe_chain_none_{}", nav.loc));
+                let end_block = params
+                    .writer
+                    .append_block_at_end(&format!("safe_chain_end_{}", nav.loc));
+
+                chain.emit_read_chain(
+                    params,
+                    Some(&SafeNavExit {
+                        none_block: none_block.clone(),
+                        pending: 0,
+                    }),
+                )?;
+                params
+                    .writer
+                    .get_current_block()
+                    .write_opcode_and_source_info(
+                        CompilerOpcode::PushBuiltinTy(BuiltinTypeId::Maybe),
+                        nav.loc.clone(),
+                    )
+                    .write_opcode_and_source_info(
+                        CompilerOpcode::NewEnumVal(true, some_idx),
+                        nav.loc.clone(),
+                    )
+                    .write_opcode_and_source_info(
+                        CompilerOpcode::Jump(end_block.clone()),
+                        nav.loc.clone(),
+                    );
+
+                params.writer.set_current_block(none_block);
+                params
+                    .writer
+                    .get_current_block()
+                    .write_opcode_and_source_info(
+                        CompilerOpcode::PushBuiltinTy(BuiltinTypeId::Maybe),
+                        nav.loc.clone(),
+                    )
+                    .write_opcode_and_source_info(
+                        CompilerOpcode::NewEnumVal(false, none_idx),
+                        nav.loc.clone(),
+                    )
+                    .write_opcode_and_source_info(
+                        CompilerOpcode::Jump(end_block.clone()),
+                        nav.loc.clone(),
+                    );
+
+                params.writer.set_current_block(end_block);
                 Ok(())
             }
         }
@@ -234,14 +488,18 @@ impl<'a> PostfixValue {
                     reason: CompilationErrorReason::ReadOnlyValue,
                 }),
             },
-            PostfixValue::Call(.., loc) => Err(CompilationError {
-                loc: loc.clone(),
+            PostfixValue::Call(_, call) => Err(CompilationError {
+                loc: call.loc.clone(),
                 reason: CompilationErrorReason::ReadOnlyValue,
             }),
             PostfixValue::Case(_, case, _) => Err(CompilationError {
                 loc: case.loc.clone(),
                 reason: CompilationErrorReason::ReadOnlyValue,
             }),
+            PostfixValue::Slice(_, slice) => Err(CompilationError {
+                loc: slice.loc.clone(),
+                reason: CompilationErrorReason::ReadOnlyValue,
+            }),
             PostfixValue::Index(base, index) => {
                 base.emit_read(params)?;
                 index.do_compile(params)?;
@@ -291,70 +549,89 @@ impl<'a> PostfixValue {
                 loc: tp.loc.clone(),
                 reason: CompilationErrorReason::ReadOnlyValue,
             }),
+            PostfixValue::SafeNav(_, nav) | PostfixValue::SafeChain(_, nav) => {
+                Err(CompilationError {
+                    loc: nav.loc.clone(),
+                    reason: CompilationErrorReason::ReadOnlyValue,
+                })
+            }
         }
     }
 }
 
-impl From<&aria_parser::ast::PostfixExpression> for PostfixValue {
-    fn from(value: &aria_parser::ast::PostfixExpression) -> Self {
-        let mut current = PostfixValue::Primary(Box::new(value.base.clone()));
-        for term in &value.terms {
-            match term {
-                aria_parser::ast::PostfixTerm::PostfixTermAttribute(attr) => {
-                    current = PostfixValue::Attribute(Box::new(current), Box::new(attr.id.clone()))
-                }
-                aria_parser::ast::PostfixTerm::PostfixTermIndex(index) => {
-                    current = PostfixValue::Index(Box::new(current), Box::new(index.index.clone()))
-                }
-                aria_parser::ast::PostfixTerm::PostfixTermCall(call) => {
-                    current = PostfixValue::Call(
-                        Box::new(current),
-                        Box::new(call.args.clone()),
-                        call.loc.clone(),
-                    )
-                }
-                aria_parser::ast::PostfixTerm::PostfixTermEnumCase(case) => {
-                    current = PostfixValue::Case(
-                        Box::new(current),
-                        Box::new(case.id.clone()),
-                        case.payload.clone(),
-                    )
-                }
-                aria_parser::ast::PostfixTerm::PostfixTermObjectWrite(wrt) => {
-                    use aria_parser::ast::PostfixTermWrite::{
-                        PostfixTermFieldWrite, PostfixTermIndexWrite,
-                    };
+impl PostfixValue {
+    fn with_term(self, term: &aria_parser::ast::PostfixTerm) -> Self {
+        match term {
+            aria_parser::ast::PostfixTerm::PostfixTermAttribute(attr) => {
+                PostfixValue::Attribute(Box::new(self), Box::new(attr.id.clone()))
+            }
+            aria_parser::ast::PostfixTerm::PostfixTermIndex(index) => {
+                PostfixValue::Index(Box::new(self), Box::new(index.index.clone()))
+            }
+            aria_parser::ast::PostfixTerm::PostfixTermSlice(slice) => {
+                PostfixValue::Slice(Box::new(self), Box::new(slice.clone()))
+            }
+            aria_parser::ast::PostfixTerm::PostfixTermCall(call) => {
+                PostfixValue::Call(Box::new(self), Box::new(call.clone()))
+            }
+            aria_parser::ast::PostfixTerm::PostfixTermEnumCase(case) => PostfixValue::Case(
+                Box::new(self),
+                Box::new(case.id.clone()),
+                case.payload.clone(),
+            ),
+            aria_parser::ast::PostfixTerm::PostfixTermObjectWrite(wrt) => {
+                use aria_parser::ast::PostfixTermWrite::{
+                    PostfixTermFieldWrite, PostfixTermIndexWrite,
+                };
 
-                    let mut terms = vec![];
-                    for term in &wrt.terms.terms {
-                        match term {
-                            PostfixTermFieldWrite(term) => {
-                                let expr = if let Some(expr) = &term.val {
-                                    expr.clone()
-                                } else {
-                                    Expression::from(&term.id)
-                                };
-                                terms.push(ObjWrite::Field(FieldWrite {
-                                    field: term.id.clone(),
-                                    value: expr,
-                                }));
-                            }
-                            PostfixTermIndexWrite(term) => {
-                                terms.push(ObjWrite::Index(IndexWrite {
-                                    index: term.idx.clone(),
-                                    value: term.val.clone(),
-                                }));
-                            }
+                let mut terms = vec![];
+                for term in &wrt.terms.terms {
+                    match term {
+                        PostfixTermFieldWrite(term) => {
+                            let expr = if let Some(expr) = &term.val {
+                                expr.clone()
+                            } else {
+                                Expression::from(&term.id)
+                            };
+                            terms.push(ObjWrite::Field(FieldWrite {
+                                field: term.id.clone(),
+                                value: expr,
+                            }));
+                        }
+                        PostfixTermIndexWrite(term) => {
+                            terms.push(ObjWrite::Index(IndexWrite {
+                                index: term.idx.clone(),
+                                value: term.val.clone(),
+                            }));
                         }
                     }
-                    current = PostfixValue::ObjWrite(Box::new(current), terms)
-                }
-                aria_parser::ast::PostfixTerm::PostfixTermTryProtocol(tp) => {
-                    current = PostfixValue::TryProtocol(Box::new(current), Box::new(tp.clone()))
                 }
+                PostfixValue::ObjWrite(Box::new(self), terms)
+            }
+            aria_parser::ast::PostfixTerm::PostfixTermSafeNav(nav) => {
+                PostfixValue::SafeNav(Box::new(self), Box::new(nav.clone())).with_term(&nav.term)
+            }
+            aria_parser::ast::PostfixTerm::PostfixTermTryProtocol(tp) => {
+                PostfixValue::TryProtocol(Box::new(self), Box::new(tp.clone()))
             }
         }
+    }
+}
 
-        current
+impl From<&aria_parser::ast::PostfixExpression> for PostfixValue {
+    fn from(value: &aria_parser::ast::PostfixExpression) -> Self {
+        let mut current = PostfixValue::Primary(Box::new(value.base.clone()));
+        let mut first_safe_nav = None;
+        for term in &value.terms {
+            if let aria_parser::ast::PostfixTerm::PostfixTermSafeNav(nav) = term {
+                first_safe_nav.get_or_insert_with(|| nav.clone());
+            }
+            current = current.with_term(term);
+        }
+
+        match first_safe_nav {
+            Some(nav) => PostfixValue::SafeChain(Box::new(current), Box::new(nav)),
+            None => current,
+        }
     }
 }
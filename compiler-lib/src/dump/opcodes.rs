@@ -62,6 +62,17 @@ pub fn opcode_prettyprint(
                 << symbol_best_repr(resolver, idx)
                 << "]"
         }
+        Opcode::TryReadAttribute(idx) => {
+            buffer << "TRY_READ_ATTRIB(@" << idx << ") [" << const_best_repr(resolver, idx) << "]"
+        }
+        Opcode::TryReadAttributeSymbol(idx) => {
+            buffer
+                << "TRY_READ_ATTRIB_SYMBOL(#"
+                << idx
+                << ") ["
+                << symbol_best_repr(resolver, idx)
+                << "]"
+        }
         Opcode::WriteAttributeSymbol(idx) => {
             buffer
                 << "WRITE_ATTRIB_SYMBOL(#"
@@ -162,6 +173,7 @@ pub fn opcode_prettyprint(
         | Opcode::TypedefLocal(_)
         | Opcode::ReadIndex(_)
         | Opcode::WriteIndex(_)
+        | Opcode::ReadSlice(_)
         | Opcode::ReadUplevel(_)
         | Opcode::LogicalAnd
         | Opcode::LogicalOr
@@ -178,8 +190,12 @@ pub fn opcode_prettyprint(
         | Opcode::JumpConditionally(..)
         | Opcode::JumpIfArgSupplied(..)
         | Opcode::Call(_)
+        | Opcode::CallNamed(..)
+        | Opcode::TailCall(_)
         | Opcode::Return
         | Opcode::ReturnUnit
+        | Opcode::Yield
+        | Opcode::Await
         | Opcode::TryEnter(_)
         | Opcode::TryExit
         | Opcode::Throw
@@ -189,7 +205,15 @@ pub fn opcode_prettyprint(
         | Opcode::BuildStruct
         | Opcode::BuildEnum
         | Opcode::BuildMixin
+        | Opcode::BuildMap(_)
+        | Opcode::BuildString(_)
+        | Opcode::ListCheckLen(..)
+        | Opcode::ListTail(_)
         | Opcode::IncludeMixin
+        | Opcode::RequireMember
+        | Opcode::CheckMixinContracts
+        | Opcode::BindGetter
+        | Opcode::BindSetter
         | Opcode::EnumTryExtractPayload
         | Opcode::Isa
         | Opcode::LiftModule
@@ -0,0 +1,253 @@
+// SPDX-License-Identifier: Apache-2.0
+use aria_parser::ast::{
+    CodeBlock, CompSymbol, ElsePiece, EnumDecl, EnumDeclEntry, Expression, MatchPattern,
+    MatchStatement, ParsedModule, Pattern, Statement, StructEntry, TopLevelEntry, TryBlock,
+    prettyprint::{PrettyPrintable, printout_accumulator::PrintoutAccumulator},
+};
+
+use crate::do_compile::{CompilationWarning, CompilationWarningReason};
+
+// enum declarations are only visible to the compiler within a single module,
+// so this can only reason about matches over enums declared in the same file;
+// anything it cannot resolve to exactly one local enum is left alone
+#[derive(Default)]
+struct ModuleWalk<'a> {
+    enums: Vec<&'a EnumDecl>,
+}
+
+impl<'a> ModuleWalk<'a> {
+    fn top_level_entry(&mut self, entry: &'a TopLevelEntry) {
+        match entry {
+            TopLevelEntry::FunctionDecl(f) => self.code_block(&f.body.code),
+            TopLevelEntry::StructDecl(s) => self.struct_entries(&s.body),
+            TopLevelEntry::MixinDecl(m) => self.struct_entries(&m.body),
+            TopLevelEntry::EnumDecl(e) => self.enum_decl(e),
+            TopLevelEntry::ExtensionDecl(e) => self.struct_entries(&e.body),
+            TopLevelEntry::IfStatement(s) => {
+                self.code_block(&s.iff.content.then);
+                for elsif in &s.elsif {
+                    self.code_block(&elsif.content.then);
+                }
+                self.else_piece(&s.els);
+            }
+            TopLevelEntry::MatchStatement(m) => self.match_statement(m),
+            TopLevelEntry::WhileStatement(w) => {
+                self.code_block(&w.then);
+                self.else_piece(&w.els);
+            }
+            TopLevelEntry::ForStatement(f) => {
+                self.code_block(&f.then);
+                self.else_piece(&f.els);
+            }
+            TopLevelEntry::CodeBlock(b) => self.code_block(b),
+            TopLevelEntry::TryBlock(t) => self.try_block(t),
+            _ => {}
+        }
+    }
+
+    fn statement(&mut self, stmt: &'a Statement) {
+        match stmt {
+            Statement::IfStatement(s) => {
+                self.code_block(&s.iff.content.then);
+                for elsif in &s.elsif {
+                    self.code_block(&elsif.content.then);
+                }
+                self.else_piece(&s.els);
+            }
+            Statement::MatchStatement(m) => self.match_statement(m),
+            Statement::WhileStatement(w) => {
+                self.code_block(&w.then);
+                self.else_piece(&w.els);
+            }
+            Statement::ForStatement(f) => {
+                self.code_block(&f.then);
+                self.else_piece(&f.els);
+            }
+            Statement::CodeBlock(b) => self.code_block(b),
+            Statement::TryBlock(t) => self.try_block(t),
+            Statement::StructDecl(s) => self.struct_entries(&s.body),
+            Statement::EnumDecl(e) => self.enum_decl(e),
+            Statement::FunctionDecl(f) => self.code_block(&f.body.code),
+            _ => {}
+        }
+    }
+
+    fn code_block(&mut self, block: &'a CodeBlock) {
+        for stmt in &block.entries {
+            self.statement(stmt);
+        }
+    }
+
+    fn else_piece(&mut self, els: &'a Option<ElsePiece>) {
+        if let Some(els) = els {
+            self.code_block(&els.then);
+        }
+    }
+
+    fn try_block(&mut self, t: &'a TryBlock) {
+        self.code_block(&t.body);
+        if let Some(catch) = &t.catch {
+            self.code_block(&catch.then);
+        }
+        if let Some(finally) = &t.finally {
+            self.code_block(&finally.then);
+        }
+    }
+
+    fn match_statement(&mut self, m: &'a MatchStatement) {
+        for rule in &m.rules {
+            self.code_block(&rule.then);
+        }
+        self.else_piece(&m.els);
+    }
+
+    fn enum_decl(&mut self, e: &'a EnumDecl) {
+        self.enums.push(e);
+        for entry in &e.body {
+            if let EnumDeclEntry::StructEntry(se) = entry {
+                self.struct_entry(se);
+            }
+        }
+    }
+
+    fn struct_entries(&mut self, entries: &'a [StructEntry]) {
+        for entry in entries {
+            self.struct_entry(entry);
+        }
+    }
+
+    fn struct_entry(&mut self, entry: &'a StructEntry) {
+        match entry {
+            StructEntry::Method(m) => self.code_block(&m.body.code),
+            StructEntry::Operator(o) => self.code_block(&o.body.code),
+            StructEntry::Struct(s) => self.struct_entries(&s.body),
+            StructEntry::Enum(e) => self.enum_decl(e),
+            StructEntry::Property(p) => {
+                for accessor in p.getter.iter().chain(p.setter.iter()) {
+                    self.code_block(&accessor.body.code);
+                }
+            }
+            StructEntry::Variable(_) | StructEntry::MixinInclude(_) => {}
+        }
+    }
+}
+
+fn enum_case_names(e: &EnumDecl) -> Vec<&str> {
+    e.body
+        .iter()
+        .filter_map(|entry| match entry {
+            EnumDeclEntry::EnumCaseDecl(c) => Some(c.name.value.as_str()),
+            EnumDeclEntry::StructEntry(_) => None,
+        })
+        .collect()
+}
+
+// the last component of a type expression, e.g. Inner for Outer.Inner,
+// which is how a nested enum is named in its declaration
+fn type_name(expr: &Expression) -> String {
+    let name = expr.prettyprint(PrintoutAccumulator::default()).value();
+    match name.rsplit_once('.') {
+        Some((_, last)) => last.to_owned(),
+        None => name,
+    }
+}
+
+// patterns that match any value without checking it
+fn is_irrefutable(pattern: &Pattern) -> bool {
+    match pattern {
+        Pattern::PatternWildcard(_) => true,
+        Pattern::DeclarationId(id) => id.ty.is_none(),
+        _ => false,
+    }
+}
+
+pub(crate) fn check_match(enums: &[EnumDecl], m: &MatchStatement) -> Option<CompilationWarning> {
+    if m.els.is_some() {
+        return None;
+    }
+
+    let mut isa_types: Vec<String> = vec![];
+    let mut catch_all_types: Vec<String> = vec![];
+    let mut mentioned: Vec<&str> = vec![];
+    let mut covered: Vec<&str> = vec![];
+
+    for rule in &m.rules {
+        let mut rule_case = None;
+        let mut rule_isa = None;
+        let mut unconditional = true;
+
+        for pattern in &rule.patterns {
+            match pattern {
+                MatchPattern::MatchPatternComp(c) if c.op == CompSymbol::Isa => {
+                    let name = type_name(&c.expr);
+                    isa_types.push(name.clone());
+                    rule_isa = Some(name);
+                }
+                MatchPattern::Pattern(Pattern::PatternEnumCase(c)) => {
+                    mentioned.push(c.case.value.as_str());
+                    if rule_case.is_some() || !c.payload.as_ref().is_none_or(|p| is_irrefutable(p))
+                    {
+                        unconditional = false;
+                    }
+                    rule_case = Some(c.case.value.as_str());
+                }
+                MatchPattern::Pattern(p) if is_irrefutable(p) => {}
+                _ => unconditional = false,
+            }
+        }
+
+        if unconditional {
+            match (rule_case, rule_isa) {
+                (Some(case), _) => covered.push(case),
+                (None, Some(ty)) => catch_all_types.push(ty),
+                (None, None) => return None,
+            }
+        }
+    }
+
+    if mentioned.is_empty() && isa_types.is_empty() {
+        return None;
+    }
+
+    let candidates: Vec<&EnumDecl> = enums
+        .iter()
+        .filter(|e| isa_types.iter().all(|ty| *ty == e.name.value))
+        .filter(|e| {
+            let cases = enum_case_names(e);
+            mentioned.iter().all(|m| cases.contains(m))
+        })
+        .collect();
+    let [enumm] = candidates.as_slice() else {
+        return None;
+    };
+
+    if catch_all_types.contains(&enumm.name.value) {
+        return None;
+    }
+
+    let missing: Vec<String> = enum_case_names(enumm)
+        .into_iter()
+        .filter(|c| !covered.contains(c))
+        .map(|c| c.to_owned())
+        .collect();
+    if missing.is_empty() {
+        None
+    } else {
+        Some(CompilationWarning {
+            loc: m.loc.clone(),
+            reason: CompilationWarningReason::NonExhaustiveMatch(enumm.name.value.clone(), missing),
+        })
+    }
+}
+
+// every enum declared in the module, wherever it is nested; the matches are
+// checked against these as they are compiled, since they can appear anywhere
+// code can, including within lambdas
+pub(crate) fn module_enums(ast: &ParsedModule) -> Vec<EnumDecl> {
+    let mut walk = ModuleWalk::default();
+    for entry in &ast.entries {
+        walk.top_level_entry(entry);
+    }
+
+    walk.enums.into_iter().cloned().collect()
+}
@@ -1,6 +1,8 @@
 // SPDX-License-Identifier: Apache-2.0
+use std::collections::HashSet;
+
 use aria_parser::ast::{ParsedModule, SourceBuffer};
-use do_compile::{CompilationError, CompilationResult};
+use do_compile::{CompilationError, CompilationResult, CompilationWarning};
 use module::CompiledModule;
 
 pub mod bc_reader;
@@ -9,13 +11,19 @@ pub mod builder;
 pub mod constant_value;
 pub mod do_compile;
 pub mod dump;
+mod exhaustiveness;
 pub mod line_table;
 pub mod module;
+pub mod module_bundle;
+pub mod module_cache;
+pub mod module_file;
 pub mod scope;
+mod unresolved;
 
 pub struct CompilationOptions {
     pub optimize: bool,
     pub dump_builder: bool,
+    pub exhaustive_match: bool,
 }
 
 impl Default for CompilationOptions {
@@ -23,6 +31,7 @@ impl Default for CompilationOptions {
         Self {
             optimize: true,
             dump_builder: false,
+            exhaustive_match: false,
         }
     }
 }
@@ -40,3 +49,23 @@ pub fn compile_from_ast(
 ) -> CompilationResult<CompiledModule, Vec<CompilationError>> {
     do_compile::compile_from_ast(ast, options)
 }
+
+// warns about every name the module uses that it neither defines nor finds in
+// known_names, which should hold the builtins and all names visible in the
+// loaded module (including those brought in by imports), suggesting the
+// closest match for each one
+pub fn check_unresolved_identifiers(
+    module: &CompiledModule,
+    known_names: &HashSet<String>,
+) -> Vec<CompilationWarning> {
+    unresolved::check_unresolved_identifiers(module, known_names)
+}
+
+// like compile_from_source, but reuses the on-disk module cache next to the
+// source file when it is up to date, and refreshes it otherwise
+pub fn compile_from_source_cached(
+    src: &SourceBuffer,
+    options: &CompilationOptions,
+) -> CompilationResult<CompiledModule, Vec<CompilationError>> {
+    module_cache::compile_from_source_cached(src, options)
+}
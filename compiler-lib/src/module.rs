@@ -1,12 +1,24 @@
 // SPDX-License-Identifier: Apache-2.0
 use std::path::PathBuf;
 
-use crate::constant_value::{CompiledCodeObject, ConstantValue, ConstantValues};
+use haxby_opcodes::Opcode;
+
+use crate::{
+    bc_reader::BytecodeReader,
+    constant_value::{CompiledCodeObject, ConstantValue, ConstantValues},
+    do_compile::CompilationWarning,
+    unresolved::FreeNames,
+};
 
 #[derive(Default)]
 pub struct CompiledModule {
     pub constants: ConstantValues,
     pub widget_root_path: Option<PathBuf>,
+    // non-fatal diagnostics from compiling the module; not kept in module files
+    pub warnings: Vec<CompilationWarning>,
+    // names used by the module that it does not define, to be checked once
+    // the module is loaded; not kept in module files either
+    pub(crate) free_names: FreeNames,
 }
 
 impl CompiledModule {
@@ -26,4 +38,27 @@ impl CompiledModule {
             .expect("__entry constant is not a code object")
             .clone()
     }
+
+    // the import paths of every module this module imports, in the order in
+    // which they appear in the module's code
+    pub fn imported_paths(&self) -> Vec<String> {
+        let mut paths: Vec<String> = vec![];
+        for cv in self.constants.values() {
+            let Some(cco) = cv.as_compiled_code_object() else {
+                continue;
+            };
+            let Ok(mut bcr) = BytecodeReader::try_from(cco.body.as_slice()) else {
+                continue;
+            };
+            while let Ok(op) = bcr.read_opcode() {
+                if let Opcode::Import(n) = op
+                    && let Some(ConstantValue::String(ipath)) = self.load_indexed_const(n)
+                    && !paths.contains(&ipath)
+                {
+                    paths.push(ipath);
+                }
+            }
+        }
+        paths
+    }
 }
@@ -0,0 +1,66 @@
+// SPDX-License-Identifier: Apache-2.0
+use crate::{
+    module::CompiledModule,
+    module_file::{ModuleFileError, ModuleFileResult, ModuleReader, ModuleWriter},
+};
+
+pub const MODULE_BUNDLE_MAGIC: &[u8; 8] = b"ARIABNDL";
+pub const MODULE_BUNDLE_EXTENSION: &str = "ariab";
+
+// bump this whenever the layout of the bundle changes; the modules inside
+// are versioned on their own
+pub const MODULE_BUNDLE_VERSION: u16 = 1;
+
+// a program together with every module it (transitively) imports, so that it can
+// run without access to its sources or to the Aria library directories
+#[derive(Default)]
+pub struct ModuleBundle {
+    pub entry: CompiledModule,
+    // keyed by import path, e.g. aria.core.builtin
+    pub imports: Vec<(String, CompiledModule)>,
+}
+
+impl ModuleBundle {
+    pub fn is_bundle(data: &[u8]) -> bool {
+        data.starts_with(MODULE_BUNDLE_MAGIC)
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        // bundled modules are never checked against their sources, so neither
+        // a hash nor the sources themselves are recorded
+        let mut dest = ModuleWriter::default();
+        dest.data.extend_from_slice(MODULE_BUNDLE_MAGIC);
+        dest.write_u16(MODULE_BUNDLE_VERSION)
+            .write_bytes(&self.entry.to_bytes_without_sources())
+            .write_u32(self.imports.len() as u32);
+        for (ipath, module) in &self.imports {
+            dest.write_str(ipath)
+                .write_bytes(&module.to_bytes_without_sources());
+        }
+        dest.data
+    }
+
+    pub fn from_bytes(data: &[u8]) -> ModuleFileResult<Self> {
+        let mut reader = ModuleReader::new(data);
+        if reader.read_slice(MODULE_BUNDLE_MAGIC.len())? != MODULE_BUNDLE_MAGIC {
+            return Err(ModuleFileError::BadMagic);
+        }
+
+        let version = reader.read_u16()?;
+        if version != MODULE_BUNDLE_VERSION {
+            return Err(ModuleFileError::UnsupportedVersion(version));
+        }
+
+        let entry = CompiledModule::from_bytes(reader.read_bytes()?)?;
+
+        let num_imports = reader.read_u32()?;
+        let mut imports = Vec::new();
+        for _ in 0..num_imports {
+            let ipath = reader.read_str()?;
+            let module = CompiledModule::from_bytes(reader.read_bytes()?)?;
+            imports.push((ipath, module));
+        }
+
+        Ok(Self { entry, imports })
+    }
+}
@@ -0,0 +1,93 @@
+// SPDX-License-Identifier: Apache-2.0
+use std::{
+    io::Write,
+    path::{Path, PathBuf},
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+use aria_parser::ast::SourceBuffer;
+
+use crate::{
+    CompilationOptions,
+    do_compile::{CompilationError, CompilationResult},
+    module::CompiledModule,
+    module_file::{ModuleReader, source_hash},
+};
+
+pub const MODULE_CACHE_EXTENSION: &str = "ariac";
+
+// foo/bar.aria is cached as foo/bar.ariac
+pub fn cache_path_for_source(path: &Path) -> PathBuf {
+    path.with_extension(MODULE_CACHE_EXTENSION)
+}
+
+fn source_path(src: &SourceBuffer) -> Option<PathBuf> {
+    let path = PathBuf::from(&src.name);
+    if path.is_file() { Some(path) } else { None }
+}
+
+pub fn load_cached_module(src: &SourceBuffer) -> Option<CompiledModule> {
+    let cache_path = cache_path_for_source(&source_path(src)?);
+    let data = std::fs::read(cache_path).ok()?;
+
+    let mut reader = ModuleReader::new(&data);
+    let header = reader.read_header().ok()?;
+    if header.source_hash != source_hash(&src.content) {
+        return None;
+    }
+
+    let module = CompiledModule::from_bytes(&data).ok()?;
+    // a module that was moved around on disk may resolve widget imports differently
+    let entry = module.load_entry_code_object();
+    if entry.loc.buffer.name != src.name {
+        return None;
+    }
+
+    Some(module)
+}
+
+pub fn store_cached_module(src: &SourceBuffer, module: &CompiledModule) -> std::io::Result<()> {
+    static TEMP_FILE_COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+    let Some(path) = source_path(src) else {
+        return Ok(());
+    };
+    let cache_path = cache_path_for_source(&path);
+    let data = module.to_bytes(source_hash(&src.content));
+
+    // write to a temporary file and rename it in place, so that concurrent readers
+    // (and writers) never observe a partially written cache entry
+    let temp_path = cache_path.with_extension(format!(
+        "{}.{}.{}.tmp",
+        MODULE_CACHE_EXTENSION,
+        std::process::id(),
+        TEMP_FILE_COUNTER.fetch_add(1, Ordering::Relaxed)
+    ));
+    let result = std::fs::File::create(&temp_path)
+        .and_then(|mut f| f.write_all(&data))
+        .and_then(|_| std::fs::rename(&temp_path, &cache_path));
+    if result.is_err() {
+        let _ = std::fs::remove_file(&temp_path);
+    }
+    result
+}
+
+pub(crate) fn compile_from_source_cached(
+    src: &SourceBuffer,
+    options: &CompilationOptions,
+) -> CompilationResult<CompiledModule, Vec<CompilationError>> {
+    // only default compilations are cached, anything else is a debugging session
+    // which wants to observe the compiler at work
+    let cacheable = options.optimize && !options.dump_builder;
+
+    if cacheable && let Some(module) = load_cached_module(src) {
+        return Ok(module);
+    }
+
+    let module = crate::do_compile::compile_from_source(src, options)?;
+    if cacheable {
+        // failing to write the cache (e.g. a read-only library directory) is not an error
+        let _ = store_cached_module(src, &module);
+    }
+    Ok(module)
+}
@@ -0,0 +1,417 @@
+// SPDX-License-Identifier: Apache-2.0
+use std::{path::PathBuf, rc::Rc};
+
+use aria_parser::ast::{Location, SourceBuffer, SourcePointer};
+
+use crate::{
+    constant_value::{CompiledCodeObject, ConstantValue},
+    line_table::LineTable,
+    module::CompiledModule,
+};
+
+pub const MODULE_FILE_MAGIC: &[u8; 8] = b"ARIAMOD\0";
+
+// bump this whenever the layout of the file changes, or the compiler starts
+// emitting bytecode that an older VM would not understand (e.g. new opcodes)
+pub const MODULE_FILE_VERSION: u16 = 13;
+
+const CONST_TAG_INTEGER: u8 = 0;
+const CONST_TAG_STRING: u8 = 1;
+const CONST_TAG_FLOAT: u8 = 2;
+const CONST_TAG_CODE_OBJECT: u8 = 3;
+
+const BUFFER_TAG_CONTENT: u8 = 0;
+const BUFFER_TAG_LINE_STARTS: u8 = 1;
+
+#[derive(Clone, thiserror::Error, PartialEq, Eq, Debug)]
+pub enum ModuleFileError {
+    #[error("data is not a compiled Aria module")]
+    BadMagic,
+
+    #[error("module file version {0} is not supported")]
+    UnsupportedVersion(u16),
+
+    #[error("module was compiled by Aria {0}")]
+    CompilerMismatch(String),
+
+    #[error("module file is truncated")]
+    Truncated,
+
+    #[error("module file contains invalid UTF-8 data")]
+    InvalidString,
+
+    #[error("{0} is not a known constant tag")]
+    UnknownConstantTag(u8),
+
+    #[error("{0} is not a known source buffer tag")]
+    UnknownBufferTag(u8),
+
+    #[error("module file refers to missing source buffer {0}")]
+    MissingSourceBuffer(u32),
+
+    #[error("module file contains inconsistent constant data")]
+    InconsistentConstants,
+}
+
+pub type ModuleFileResult<T> = Result<T, ModuleFileError>;
+
+// FNV-1a is stable across toolchains and platforms, unlike std's DefaultHasher,
+// which matters because the hash is persisted to disk
+pub fn source_hash(content: &str) -> u64 {
+    let mut hash: u64 = 0xcbf29ce484222325;
+    for b in content.as_bytes() {
+        hash ^= *b as u64;
+        hash = hash.wrapping_mul(0x100000001b3);
+    }
+    hash
+}
+
+pub struct ModuleFileHeader {
+    pub version: u16,
+    pub compiler_version: String,
+    pub source_hash: u64,
+}
+
+#[derive(Default)]
+pub(crate) struct ModuleWriter {
+    pub(crate) data: Vec<u8>,
+    buffers: Vec<SourceBuffer>,
+}
+
+impl ModuleWriter {
+    fn write_u8(&mut self, val: u8) -> &mut Self {
+        self.data.push(val);
+        self
+    }
+
+    pub(crate) fn write_u16(&mut self, val: u16) -> &mut Self {
+        self.data.extend_from_slice(&val.to_le_bytes());
+        self
+    }
+
+    pub(crate) fn write_u32(&mut self, val: u32) -> &mut Self {
+        self.data.extend_from_slice(&val.to_le_bytes());
+        self
+    }
+
+    fn write_u64(&mut self, val: u64) -> &mut Self {
+        self.data.extend_from_slice(&val.to_le_bytes());
+        self
+    }
+
+    pub(crate) fn write_bytes(&mut self, val: &[u8]) -> &mut Self {
+        self.write_u32(val.len() as u32);
+        self.data.extend_from_slice(val);
+        self
+    }
+
+    pub(crate) fn write_str(&mut self, val: &str) -> &mut Self {
+        self.write_bytes(val.as_bytes())
+    }
+
+    fn buffer_index(&mut self, buffer: &SourceBuffer) -> u32 {
+        // all pointers in a module generally share one buffer, so check identity before
+        // falling back to a (potentially expensive) comparison of the contents
+        let existing = self
+            .buffers
+            .iter()
+            .position(|b| Rc::ptr_eq(&b.content, &buffer.content) && b.name == buffer.name)
+            .or_else(|| self.buffers.iter().position(|b| b == buffer));
+        match existing {
+            Some(idx) => idx as u32,
+            None => {
+                self.buffers.push(buffer.clone());
+                (self.buffers.len() - 1) as u32
+            }
+        }
+    }
+
+    fn write_pointer(&mut self, ptr: &SourcePointer) -> &mut Self {
+        let idx = self.buffer_index(&ptr.buffer);
+        self.write_u32(idx)
+            .write_u32(ptr.location.start as u32)
+            .write_u32(ptr.location.stop as u32)
+    }
+
+    fn write_code_object(&mut self, cco: &CompiledCodeObject) -> &mut Self {
+        self.write_str(&cco.name)
+            .write_u8(cco.attribute)
+            .write_u8(cco.required_argc)
+            .write_u8(cco.default_argc)
+            .write_u8(cco.frame_size)
+            .write_pointer(&cco.loc)
+            .write_bytes(&cco.body);
+
+        self.write_u8(cco.arg_names.len() as u8);
+        for name in &cco.arg_names {
+            self.write_str(name);
+        }
+
+        let entries = cco.line_table.entries();
+        self.write_u32(entries.len() as u32);
+        for (idx, ptr) in &entries {
+            self.write_u16(*idx).write_pointer(ptr);
+        }
+        self
+    }
+
+    fn write_constant(&mut self, cv: &ConstantValue) -> &mut Self {
+        match cv {
+            ConstantValue::Integer(n) => self.write_u8(CONST_TAG_INTEGER).write_u64(*n as u64),
+            ConstantValue::String(s) => self.write_u8(CONST_TAG_STRING).write_str(s),
+            ConstantValue::Float(f) => self
+                .write_u8(CONST_TAG_FLOAT)
+                .write_u64(f.raw_value().to_bits()),
+            ConstantValue::CompiledCodeObject(cco) => {
+                self.write_u8(CONST_TAG_CODE_OBJECT).write_code_object(cco)
+            }
+        }
+    }
+}
+
+pub struct ModuleReader<'a> {
+    data: &'a [u8],
+    idx: usize,
+    buffers: Vec<SourceBuffer>,
+}
+
+impl<'a> ModuleReader<'a> {
+    pub fn new(data: &'a [u8]) -> Self {
+        Self {
+            data,
+            idx: 0,
+            buffers: Vec::new(),
+        }
+    }
+
+    pub(crate) fn read_slice(&mut self, len: usize) -> ModuleFileResult<&'a [u8]> {
+        if self.idx + len <= self.data.len() {
+            let ret = &self.data[self.idx..self.idx + len];
+            self.idx += len;
+            Ok(ret)
+        } else {
+            Err(ModuleFileError::Truncated)
+        }
+    }
+
+    fn read_u8(&mut self) -> ModuleFileResult<u8> {
+        Ok(self.read_slice(1)?[0])
+    }
+
+    pub(crate) fn read_u16(&mut self) -> ModuleFileResult<u16> {
+        let s = self.read_slice(2)?;
+        Ok(u16::from_le_bytes([s[0], s[1]]))
+    }
+
+    pub(crate) fn read_u32(&mut self) -> ModuleFileResult<u32> {
+        let s = self.read_slice(4)?;
+        Ok(u32::from_le_bytes([s[0], s[1], s[2], s[3]]))
+    }
+
+    fn read_u64(&mut self) -> ModuleFileResult<u64> {
+        let s = self.read_slice(8)?;
+        let mut bytes = [0u8; 8];
+        bytes.copy_from_slice(s);
+        Ok(u64::from_le_bytes(bytes))
+    }
+
+    pub(crate) fn read_bytes(&mut self) -> ModuleFileResult<&'a [u8]> {
+        let len = self.read_u32()? as usize;
+        self.read_slice(len)
+    }
+
+    pub(crate) fn read_str(&mut self) -> ModuleFileResult<String> {
+        let bytes = self.read_bytes()?;
+        std::str::from_utf8(bytes)
+            .map(|s| s.to_owned())
+            .map_err(|_| ModuleFileError::InvalidString)
+    }
+
+    fn read_pointer(&mut self) -> ModuleFileResult<SourcePointer> {
+        let idx = self.read_u32()?;
+        let start = self.read_u32()? as usize;
+        let stop = self.read_u32()? as usize;
+        let buffer = self
+            .buffers
+            .get(idx as usize)
+            .ok_or(ModuleFileError::MissingSourceBuffer(idx))?;
+        Ok(buffer.pointer(Location { start, stop }))
+    }
+
+    fn read_code_object(&mut self) -> ModuleFileResult<CompiledCodeObject> {
+        let name = self.read_str()?;
+        let attribute = self.read_u8()?;
+        let required_argc = self.read_u8()?;
+        let default_argc = self.read_u8()?;
+        let frame_size = self.read_u8()?;
+        let loc = self.read_pointer()?;
+        let body = self.read_bytes()?.to_vec();
+
+        let num_arg_names = self.read_u8()?;
+        let mut arg_names = Vec::with_capacity(num_arg_names as usize);
+        for _ in 0..num_arg_names {
+            arg_names.push(self.read_str()?);
+        }
+
+        let line_table = LineTable::default();
+        let num_entries = self.read_u32()?;
+        for _ in 0..num_entries {
+            let idx = self.read_u16()?;
+            let ptr = self.read_pointer()?;
+            line_table.insert(idx, ptr);
+        }
+
+        Ok(CompiledCodeObject {
+            name,
+            attribute,
+            body,
+            required_argc,
+            default_argc,
+            arg_names,
+            loc,
+            line_table,
+            frame_size,
+        })
+    }
+
+    fn read_constant(&mut self) -> ModuleFileResult<ConstantValue> {
+        match self.read_u8()? {
+            CONST_TAG_INTEGER => Ok(ConstantValue::Integer(self.read_u64()? as i64)),
+            CONST_TAG_STRING => Ok(ConstantValue::String(self.read_str()?)),
+            CONST_TAG_FLOAT => Ok(ConstantValue::Float(
+                f64::from_bits(self.read_u64()?).into(),
+            )),
+            CONST_TAG_CODE_OBJECT => {
+                Ok(ConstantValue::CompiledCodeObject(self.read_code_object()?))
+            }
+            tag => Err(ModuleFileError::UnknownConstantTag(tag)),
+        }
+    }
+
+    pub fn read_header(&mut self) -> ModuleFileResult<ModuleFileHeader> {
+        if self.read_slice(MODULE_FILE_MAGIC.len())? != MODULE_FILE_MAGIC {
+            return Err(ModuleFileError::BadMagic);
+        }
+
+        let version = self.read_u16()?;
+        if version != MODULE_FILE_VERSION {
+            return Err(ModuleFileError::UnsupportedVersion(version));
+        }
+
+        let compiler_version = self.read_str()?;
+        let source_hash = self.read_u64()?;
+
+        Ok(ModuleFileHeader {
+            version,
+            compiler_version,
+            source_hash,
+        })
+    }
+
+    pub fn read_module(&mut self) -> ModuleFileResult<CompiledModule> {
+        let header = self.read_header()?;
+        if header.compiler_version != env!("CARGO_PKG_VERSION") {
+            return Err(ModuleFileError::CompilerMismatch(header.compiler_version));
+        }
+
+        let num_buffers = self.read_u32()?;
+        for _ in 0..num_buffers {
+            let name = self.read_str()?;
+            let buffer = match self.read_u8()? {
+                BUFFER_TAG_CONTENT => SourceBuffer::stdin_with_name(&self.read_str()?, &name),
+                BUFFER_TAG_LINE_STARTS => {
+                    let num_lines = self.read_u32()?;
+                    let mut line_starts = Vec::new();
+                    for _ in 0..num_lines {
+                        line_starts.push(self.read_u32()? as usize);
+                    }
+                    SourceBuffer::without_content(&name, line_starts)
+                }
+                tag => return Err(ModuleFileError::UnknownBufferTag(tag)),
+            };
+            self.buffers.push(buffer);
+        }
+
+        let widget_root_path = match self.read_u8()? {
+            0 => None,
+            _ => Some(PathBuf::from(self.read_str()?)),
+        };
+
+        let mut module = CompiledModule {
+            widget_root_path,
+            ..Default::default()
+        };
+
+        let num_constants = self.read_u32()?;
+        for i in 0..num_constants {
+            let cv = self.read_constant()?;
+            match module.constants.insert(cv) {
+                Ok(idx) if idx as u32 == i => {}
+                _ => return Err(ModuleFileError::InconsistentConstants),
+            }
+        }
+
+        if module.constants.is_empty() {
+            return Err(ModuleFileError::InconsistentConstants);
+        }
+
+        Ok(module)
+    }
+}
+
+impl CompiledModule {
+    // source_hash identifies the source the module was compiled from, so that
+    // cached copies of the module can be invalidated when it changes
+    pub fn to_bytes(&self, source_hash: u64) -> Vec<u8> {
+        self.write_module(source_hash, true)
+    }
+
+    // for modules that are shipped rather than cached: the sources are left
+    // out, and only the line offsets needed to report line numbers are kept
+    pub fn to_bytes_without_sources(&self) -> Vec<u8> {
+        self.write_module(0, false)
+    }
+
+    fn write_module(&self, source_hash: u64, with_sources: bool) -> Vec<u8> {
+        // constants are written first so that every source buffer they refer to is known,
+        // and then spliced after the buffer table which the reader needs upfront
+        let mut body = ModuleWriter::default();
+        body.write_u32(self.constants.len() as u32);
+        for cv in self.constants.values() {
+            body.write_constant(cv);
+        }
+
+        let mut dest = ModuleWriter::default();
+        dest.data.extend_from_slice(MODULE_FILE_MAGIC);
+        dest.write_u16(MODULE_FILE_VERSION)
+            .write_str(env!("CARGO_PKG_VERSION"))
+            .write_u64(source_hash);
+
+        dest.write_u32(body.buffers.len() as u32);
+        for buffer in &body.buffers {
+            dest.write_str(&buffer.name);
+            if with_sources && buffer.has_content() {
+                dest.write_u8(BUFFER_TAG_CONTENT).write_str(&buffer.content);
+            } else {
+                let line_starts = buffer.line_starts();
+                dest.write_u8(BUFFER_TAG_LINE_STARTS)
+                    .write_u32(line_starts.len() as u32);
+                for start in line_starts {
+                    dest.write_u32(start as u32);
+                }
+            }
+        }
+
+        match &self.widget_root_path {
+            Some(path) => dest.write_u8(1).write_str(&path.to_string_lossy()),
+            None => dest.write_u8(0),
+        };
+
+        dest.data.extend_from_slice(&body.data);
+        dest.data
+    }
+
+    pub fn from_bytes(data: &[u8]) -> ModuleFileResult<Self> {
+        ModuleReader::new(data).read_module()
+    }
+}
@@ -1,12 +1,19 @@
 // SPDX-License-Identifier: Apache-2.0
-use std::{cell::RefCell, collections::HashMap, rc::Rc};
+use std::{
+    cell::{Cell, RefCell},
+    collections::{BTreeSet, HashMap, HashSet},
+    rc::Rc,
+};
 
-use aria_parser::ast::SourcePointer;
+use aria_parser::ast::{EnumDecl, MatchStatement, SourcePointer};
 use haxby_opcodes::BuiltinTypeId;
 
 use crate::{
     builder::{block::BasicBlock, compiler_opcodes::CompilerOpcode},
     constant_value::ConstantValues,
+    do_compile::{CompilationWarning, CompilationWarningReason},
+    exhaustiveness::check_match,
+    unresolved::FreeNames,
 };
 
 trait Numeric<Output = Self> {
@@ -81,12 +88,88 @@ pub struct ScopeError {
 
 pub type ScopeResult<T = ()> = Result<T, ScopeError>;
 
+// how a local declared with val is used; these are collected for the whole
+// module, so that the ones that are never read can be reported at the end
+struct LocalUsage {
+    name: String,
+    loc: SourcePointer,
+    read: Cell<bool>,
+    // how many times it is assigned, counting its initial value; reads are
+    // not tracked per assignment, so this only tells apart a variable that
+    // is never assigned again from one that is
+    writes: Cell<usize>,
+}
+
 #[derive(Default)]
 pub struct ModuleRootScope {
     symbols: RefCell<HashMap<String, u16>>,
+    warnings: RefCell<Vec<CompilationWarning>>,
+    locals: RefCell<Vec<Rc<LocalUsage>>>,
+    imports: RefCell<Vec<(String, SourcePointer)>>,
+    // every name that is looked up in the module, from anywhere within it
+    named_reads: RefCell<Vec<(String, SourcePointer)>>,
+    // and every name that is assigned without being a local
+    named_writes: RefCell<Vec<(String, SourcePointer)>>,
+    // the names of all locals in the module's functions
+    local_names: RefCell<BTreeSet<String>>,
+    // the enums declared anywhere in the module, for checking matches over them
+    enums: RefCell<Vec<EnumDecl>>,
 }
 
 impl ModuleRootScope {
+    fn take_warnings(&self) -> Vec<CompilationWarning> {
+        let mut warnings = self.warnings.take();
+
+        for local in self.locals.take() {
+            if local.read.get() {
+                continue;
+            }
+            let reason = if local.writes.get() <= 1 {
+                CompilationWarningReason::UnusedVariable(local.name.clone())
+            } else {
+                CompilationWarningReason::AssignedButNeverRead(local.name.clone())
+            };
+            warnings.push(CompilationWarning {
+                loc: local.loc.clone(),
+                reason,
+            });
+        }
+
+        let named_reads = self
+            .named_reads
+            .borrow()
+            .iter()
+            .map(|(name, _)| name.clone())
+            .collect::<HashSet<_>>();
+        for (name, loc) in self.imports.take() {
+            if !named_reads.contains(&name) {
+                warnings.push(CompilationWarning {
+                    loc,
+                    reason: CompilationWarningReason::UnusedImport(name),
+                });
+            }
+        }
+
+        warnings.sort_by_key(|w| w.loc.location.start);
+        warnings
+    }
+
+    // names can be read before their definition later in the module, so this
+    // is only accurate once the whole module has been compiled
+    fn take_free_names(&self) -> FreeNames {
+        let symbols = self.symbols.borrow();
+        FreeNames {
+            references: self
+                .named_reads
+                .take()
+                .into_iter()
+                .chain(self.named_writes.take())
+                .filter(|(name, _)| !symbols.contains_key(name))
+                .collect(),
+            local_names: self.local_names.take(),
+        }
+    }
+
     pub fn emit_typed_define(
         &self,
         name: &str,
@@ -119,6 +202,9 @@ impl ModuleRootScope {
         dest: BasicBlock,
         loc: SourcePointer,
     ) -> ScopeResult {
+        self.named_writes
+            .borrow_mut()
+            .push((name.to_owned(), loc.clone()));
         if let Some(existing_idx) = self.symbols.borrow().get(name) {
             dest.write_opcode_and_source_info(CompilerOpcode::WriteNamed(*existing_idx), loc);
             Ok(())
@@ -146,6 +232,9 @@ impl ModuleRootScope {
         dest: BasicBlock,
         loc: SourcePointer,
     ) -> ScopeResult {
+        self.named_reads
+            .borrow_mut()
+            .push((name.to_owned(), loc.clone()));
         if let Some(existing_idx) = self.symbols.borrow().get(name) {
             dest.write_opcode_and_source_info(CompilerOpcode::ReadNamed(*existing_idx), loc);
         } else {
@@ -272,6 +361,10 @@ pub struct FunctionRootScope {
     parent: CompilationScope,
     lexical_parent: Option<(CompilationScope, BasicBlock)>,
     pub(crate) uplevels: RefCell<Vec<UplevelInfo>>,
+    is_generator: Cell<bool>,
+    is_async: Cell<bool>,
+    // the locals of this function that were declared with val
+    usage: RefCell<HashMap<u8, Rc<LocalUsage>>>,
 }
 
 impl FunctionRootScope {
@@ -282,6 +375,9 @@ impl FunctionRootScope {
             parent: parent.get_module_scope().unwrap(),
             lexical_parent: None,
             uplevels: Default::default(),
+            is_generator: Default::default(),
+            is_async: Default::default(),
+            usage: Default::default(),
         }
     }
 
@@ -292,6 +388,9 @@ impl FunctionRootScope {
             parent: lexical_parent.0.get_module_scope().unwrap(),
             lexical_parent: Some(lexical_parent),
             uplevels: Default::default(),
+            is_generator: Default::default(),
+            is_async: Default::default(),
+            usage: Default::default(),
         }
     }
 
@@ -299,6 +398,30 @@ impl FunctionRootScope {
         self.index_provider.borrow().get_max_index()
     }
 
+    pub fn is_generator(&self) -> bool {
+        self.is_generator.get()
+    }
+
+    pub fn is_async(&self) -> bool {
+        self.is_async.get()
+    }
+
+    pub(crate) fn mark_async(&self) {
+        self.is_async.set(true);
+    }
+
+    fn note_read(&self, idx: u8) {
+        if let Some(usage) = self.usage.borrow().get(&idx) {
+            usage.read.set(true);
+        }
+    }
+
+    fn note_write(&self, idx: u8) {
+        if let Some(usage) = self.usage.borrow().get(&idx) {
+            usage.writes.set(usage.writes.get() + 1);
+        }
+    }
+
     pub fn emit_typed_define(
         &self,
         name: &str,
@@ -320,6 +443,7 @@ impl FunctionRootScope {
         loc: SourcePointer,
     ) -> ScopeResult {
         if let Some(existing_idx) = self.symbols.borrow().get(name) {
+            self.note_write(*existing_idx);
             dest.write_opcode_and_source_info(CompilerOpcode::WriteLocal(*existing_idx), loc);
             Ok(())
         } else if let Some(uplevel_info) =
@@ -379,6 +503,7 @@ impl FunctionRootScope {
     ) -> ScopeResult {
         let maybe_idx = self.symbols.borrow().get(name).cloned();
         if let Some(existing_idx) = maybe_idx {
+            self.note_read(existing_idx);
             dest.write_opcode_and_source_info(CompilerOpcode::ReadLocal(existing_idx), loc);
             return Ok(());
         }
@@ -402,6 +527,8 @@ impl FunctionRootScope {
     ) -> ScopeResult<Option<UplevelSymbolResolution>> {
         let maybe_idx = self.symbols.borrow().get(name).cloned();
         if let Some(existing_idx) = maybe_idx {
+            // a closure capturing the local may read it at any time
+            self.note_read(existing_idx);
             Ok(Some(UplevelSymbolResolution {
                 depth: 0,
                 index_at_depth: existing_idx,
@@ -463,6 +590,7 @@ impl FunctionChildScope {
         loc: SourcePointer,
     ) -> ScopeResult {
         if let Some(existing_idx) = self.symbols.borrow().get(name) {
+            self.get_function_root().note_write(*existing_idx);
             dest.write_opcode_and_source_info(CompilerOpcode::WriteLocal(*existing_idx), loc);
             Ok(())
         } else {
@@ -478,6 +606,7 @@ impl FunctionChildScope {
         loc: SourcePointer,
     ) -> ScopeResult {
         if let Some(existing_idx) = self.symbols.borrow().get(name) {
+            self.get_function_root().note_read(*existing_idx);
             dest.write_opcode_and_source_info(CompilerOpcode::ReadLocal(*existing_idx), loc);
             Ok(())
         } else {
@@ -493,6 +622,7 @@ impl FunctionChildScope {
         want_dup_on_stack: bool,
     ) -> ScopeResult<Option<UplevelSymbolResolution>> {
         if let Some(existing_idx) = self.symbols.borrow().get(name) {
+            self.get_function_root().note_read(*existing_idx);
             Ok(Some(UplevelSymbolResolution {
                 depth: 0,
                 index_at_depth: *existing_idx,
@@ -534,6 +664,96 @@ impl CompilationScope {
         }
     }
 
+    // a function containing a yield is a generator; returns false if there
+    // is no enclosing function to mark
+    pub(crate) fn mark_generator(&self) -> bool {
+        match self {
+            Self::ModuleRoot(_) | Self::ModuleChild(_) => false,
+            Self::FunctionRoot(fr) => {
+                fr.is_generator.set(true);
+                true
+            }
+            Self::FunctionChild(fc) => {
+                fc.get_function_root().is_generator.set(true);
+                true
+            }
+        }
+    }
+
+    // await is only valid directly inside an async function, not in a
+    // closure nested in one
+    pub(crate) fn is_in_async_function(&self) -> bool {
+        match self {
+            Self::ModuleRoot(_) | Self::ModuleChild(_) => false,
+            Self::FunctionRoot(fr) => fr.is_async(),
+            Self::FunctionChild(fc) => fc.get_function_root().is_async(),
+        }
+    }
+
+    fn module_root(&self) -> Rc<ModuleRootScope> {
+        match self {
+            Self::ModuleRoot(r) => r.clone(),
+            Self::ModuleChild(c) => c.parent.module_root(),
+            Self::FunctionRoot(fr) => fr.parent.module_root(),
+            Self::FunctionChild(fc) => fc.parent.module_root(),
+        }
+    }
+
+    fn function_root(&self) -> Option<Rc<FunctionRootScope>> {
+        match self {
+            Self::ModuleRoot(_) | Self::ModuleChild(_) => None,
+            Self::FunctionRoot(fr) => Some(fr.clone()),
+            Self::FunctionChild(fc) => Some(fc.get_function_root()),
+        }
+    }
+
+    // the local a name refers to in the current function, if any
+    fn resolve_local(&self, name: &str) -> Option<u8> {
+        match self {
+            Self::ModuleRoot(_) | Self::ModuleChild(_) => None,
+            Self::FunctionRoot(fr) => fr.symbols.borrow().get(name).cloned(),
+            Self::FunctionChild(fc) => match fc.symbols.borrow().get(name) {
+                Some(idx) => Some(*idx),
+                None => fc.parent.resolve_local(name),
+            },
+        }
+    }
+
+    pub(crate) fn warn(&self, warning: CompilationWarning) {
+        self.module_root().warnings.borrow_mut().push(warning);
+    }
+
+    pub(crate) fn set_module_enums(&self, enums: Vec<EnumDecl>) {
+        *self.module_root().enums.borrow_mut() = enums;
+    }
+
+    // warns about a match without an else that leaves cases of an enum
+    // declared in this module unhandled
+    pub(crate) fn check_match_exhaustiveness(&self, m: &MatchStatement) {
+        let root = self.module_root();
+        let warning = check_match(&root.enums.borrow(), m);
+        if let Some(warning) = warning {
+            self.warn(warning);
+        }
+    }
+
+    // the warnings found while compiling the module, in source order; only
+    // meaningful once the whole module has been compiled
+    pub(crate) fn take_warnings(&self) -> Vec<CompilationWarning> {
+        self.module_root().take_warnings()
+    }
+
+    pub(crate) fn take_free_names(&self) -> FreeNames {
+        self.module_root().take_free_names()
+    }
+
+    // names imported at the top level of the module should be used somewhere in it
+    pub(crate) fn note_import(&self, name: &str, loc: SourcePointer) {
+        if let Self::ModuleRoot(r) = self {
+            r.imports.borrow_mut().push((name.to_owned(), loc));
+        }
+    }
+
     pub fn child(&self) -> Self {
         match self {
             CompilationScope::ModuleRoot(_) | CompilationScope::ModuleChild(_) => {
@@ -552,6 +772,13 @@ impl CompilationScope {
         dest: BasicBlock,
         loc: SourcePointer,
     ) -> ScopeResult {
+        if self.function_root().is_some() {
+            self.module_root()
+                .local_names
+                .borrow_mut()
+                .insert(name.to_owned());
+        }
+
         match self {
             Self::ModuleRoot(r) => r.emit_typed_define(name, consts, dest, loc),
             Self::FunctionRoot(r) => r.emit_typed_define(name, consts, dest, loc),
@@ -560,6 +787,47 @@ impl CompilationScope {
         }
     }
 
+    // defines a local declared with val, keeping track of whether it is ever
+    // read and of which outer val declaration it shadows, if any; names that
+    // start with an underscore (including the compiler's hidden ones) are exempt
+    pub fn emit_variable_define(
+        &self,
+        name: &str,
+        consts: &mut ConstantValues,
+        dest: BasicBlock,
+        loc: SourcePointer,
+    ) -> ScopeResult {
+        let Some(function_root) = self.function_root().filter(|_| !name.starts_with('_')) else {
+            return self.emit_typed_define(name, consts, dest, loc);
+        };
+
+        // declaring the same name again in one scope is a common way of
+        // replacing a value, so only warn about hiding one from an outer scope
+        if let Self::FunctionChild(fc) = self
+            && !fc.symbols.borrow().contains_key(name)
+            && let Some(idx) = fc.parent.resolve_local(name)
+            && function_root.usage.borrow().contains_key(&idx)
+        {
+            self.warn(CompilationWarning {
+                loc: loc.clone(),
+                reason: CompilationWarningReason::ShadowedVariable(name.to_owned()),
+            });
+        }
+
+        self.emit_typed_define(name, consts, dest, loc.clone())?;
+
+        let idx = self.resolve_local(name).expect("local was just defined");
+        let usage = Rc::new(LocalUsage {
+            name: name.to_owned(),
+            loc,
+            read: Default::default(),
+            writes: Default::default(),
+        });
+        function_root.usage.borrow_mut().insert(idx, usage.clone());
+        self.module_root().locals.borrow_mut().push(usage);
+        Ok(())
+    }
+
     pub fn emit_untyped_define(
         &self,
         name: &str,
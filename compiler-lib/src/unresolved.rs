@@ -0,0 +1,82 @@
+// SPDX-License-Identifier: Apache-2.0
+use std::collections::{BTreeSet, HashSet};
+
+use aria_parser::ast::SourcePointer;
+
+use crate::{
+    do_compile::{CompilationWarning, CompilationWarningReason},
+    module::CompiledModule,
+};
+
+// the names a module reads or assigns without defining them itself; whether
+// they exist depends on the builtins of the VM and on what `import *` brings
+// in, so they can only be checked once those are known
+#[derive(Default)]
+pub(crate) struct FreeNames {
+    pub(crate) references: Vec<(String, SourcePointer)>,
+    // every local declared in the module, as candidates for misspellings
+    pub(crate) local_names: BTreeSet<String>,
+}
+
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b = b.chars().collect::<Vec<_>>();
+    let mut prev = (0..=b.len()).collect::<Vec<_>>();
+    let mut cur = vec![0; b.len() + 1];
+
+    for (i, ca) in a.chars().enumerate() {
+        cur[0] = i + 1;
+        for (j, cb) in b.iter().enumerate() {
+            let substitution = prev[j] + usize::from(ca != *cb);
+            cur[j + 1] = substitution.min(prev[j + 1] + 1).min(cur[j] + 1);
+        }
+        std::mem::swap(&mut prev, &mut cur);
+    }
+
+    prev[b.len()]
+}
+
+// the closest candidate, as long as it is close enough to be a plausible typo;
+// ties go to the candidate that sorts first, so that suggestions are stable
+fn closest_name<'a>(name: &str, candidates: &BTreeSet<&'a str>) -> Option<&'a str> {
+    let max_distance = (name.chars().count() / 3).max(1);
+    candidates
+        .iter()
+        .filter(|c| !c.starts_with("__"))
+        .map(|c| (edit_distance(name, c), *c))
+        .filter(|(d, _)| *d <= max_distance)
+        .min_by_key(|(d, _)| *d)
+        .map(|(_, c)| c)
+}
+
+pub(crate) fn check_unresolved_identifiers(
+    module: &CompiledModule,
+    known_names: &HashSet<String>,
+) -> Vec<CompilationWarning> {
+    let free_names = &module.free_names;
+    let candidates = known_names
+        .iter()
+        .map(String::as_str)
+        .chain(free_names.local_names.iter().map(String::as_str))
+        .collect::<BTreeSet<_>>();
+
+    // a compound assignment both reads and writes its target, but it should
+    // only be reported once
+    let mut unresolved = free_names
+        .references
+        .iter()
+        .filter(|(name, _)| !known_names.contains(name))
+        .collect::<Vec<_>>();
+    unresolved.sort_by_key(|(_, loc)| loc.location.start);
+    unresolved.dedup_by_key(|(name, loc)| (name.as_str(), loc.location.start));
+
+    unresolved
+        .into_iter()
+        .map(|(name, loc)| CompilationWarning {
+            loc: loc.clone(),
+            reason: CompilationWarningReason::UnresolvedIdentifier(
+                name.clone(),
+                closest_name(name, &candidates).map(str::to_owned),
+            ),
+        })
+        .collect()
+}
@@ -3,6 +3,8 @@ use std::collections::HashMap;
 use std::sync::Arc;
 
 use crate::parser::{self, Parse, SyntaxNode, SyntaxToken};
+use aria_compiler::{CompilationOptions, compile_from_source};
+use aria_parser::ast::SourceBuffer;
 use line_index::{LineCol, LineIndex};
 use rowan::{TextRange, TextSize};
 
@@ -131,6 +133,53 @@ impl DocumentState {
 
         out
     }
+
+    // names starting with an underscore are private to their module, so
+    // `import _name from module;` does not compile
+    pub fn private_import_ranges(&self) -> Vec<(TextRange, String)> {
+        use crate::lexer::SyntaxKind as K;
+
+        self.parse
+            .syntax()
+            .descendants()
+            .filter(|node| node.kind() == K::StmtImport)
+            .flat_map(|stmt| stmt.children().filter(|node| node.kind() == K::IdentList))
+            .flat_map(|list| list.children_with_tokens().filter_map(|e| e.into_token()))
+            .filter(|tok| tok.kind() == K::Identifier && tok.text().starts_with('_'))
+            .map(|tok| {
+                let msg = format!(
+                    "'{}' is private to its module and cannot be imported",
+                    tok.text()
+                );
+                (tok.text_range(), msg)
+            })
+            .collect()
+    }
+
+    // the warnings from compiling the document; there are none to report
+    // while the document does not compile
+    pub fn compiler_warning_ranges(&self) -> Vec<(TextRange, String)> {
+        let options = CompilationOptions {
+            optimize: false,
+            ..Default::default()
+        };
+        let Ok(module) = compile_from_source(&SourceBuffer::stdin(&self.text), &options) else {
+            return vec![];
+        };
+
+        module
+            .warnings
+            .iter()
+            .map(|warning| {
+                let clamp = |offset: usize| TextSize::from(offset as u32).min(self.text_size);
+                let range = TextRange::new(
+                    clamp(warning.loc.location.start),
+                    clamp(warning.loc.location.stop),
+                );
+                (range, warning.reason.to_string())
+            })
+            .collect()
+    }
 }
 
 #[derive(Clone, Copy, Debug)]
@@ -218,6 +267,29 @@ mod tests {
         assert_eq!(func_tok.kind(), SyntaxKind::FuncKwd);
     }
 
+    #[test]
+    fn private_imports_are_reported() {
+        let text = "import visible, _hidden from some.module;\nimport * from other;\n".to_string();
+        let doc = DocumentState::new(text);
+        let errs = doc.private_import_ranges();
+        assert_eq!(errs.len(), 1);
+        assert_eq!(errs[0].0, TextRange::new(16.into(), 23.into()));
+        assert!(errs[0].1.contains("'_hidden' is private"));
+    }
+
+    #[test]
+    fn compiler_warnings_are_reported() {
+        let text = "func f() {\n    val unused = 1;\n    return 2;\n}\n".to_string();
+        let doc = DocumentState::new(text);
+        let warnings = doc.compiler_warning_ranges();
+        assert_eq!(warnings.len(), 1);
+        assert_eq!(warnings[0].0, TextRange::new(19.into(), 29.into()));
+        assert!(warnings[0].1.contains("'unused' is never read"));
+
+        let doc = DocumentState::new("func f() {".to_string());
+        assert!(doc.compiler_warning_ranges().is_empty());
+    }
+
     #[test]
     fn parse_errors_include_expected_tokens() {
         let text = "val x".to_string();
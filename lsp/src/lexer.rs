@@ -6,6 +6,10 @@ use logos::Logos;
 pub enum SyntaxKind {
     #[token("assert")]
     AssertKwd,
+    #[token("async")]
+    AsyncKwd,
+    #[token("await")]
+    AwaitKwd,
     #[token("break")]
     BreakKwd,
     #[token("case")]
@@ -22,6 +26,8 @@ pub enum SyntaxKind {
     EnumKwd,
     #[token("extension")]
     ExtensionKwd,
+    #[token("finally")]
+    FinallyKwd,
     #[token("flag")]
     FlagKwd,
     #[token("for")]
@@ -48,6 +54,10 @@ pub enum SyntaxKind {
     MixinKwd,
     #[token("operator")]
     OperatorKwd,
+    #[token("prop")]
+    PropKwd,
+    #[token("require")]
+    RequireKwd,
     #[token("return")]
     ReturnKwd,
     #[token("reverse")]
@@ -64,6 +74,8 @@ pub enum SyntaxKind {
     ValKwd,
     #[token("while")]
     WhileKwd,
+    #[token("yield")]
+    YieldKwd,
     #[token("and")]
     AndKwd,
 
@@ -177,6 +189,11 @@ pub enum SyntaxKind {
     #[regex(r#"'([^'\\]|\\.)*'"#)]
     StringLiteral,
 
+    // split into the pieces below by lex(), never handed to the parser as a whole
+    #[token("f\"", lex_format_string)]
+    #[token("f'", lex_format_string)]
+    FormatStringLiteral,
+
     #[regex(
         r#"[\p{XID_Start}\p{Emoji_Presentation}_$][\p{XID_Continue}\p{Emoji_Presentation}_$]*"#,
         priority = 1
@@ -192,6 +209,12 @@ pub enum SyntaxKind {
     // Error token for unrecognized input
     Error,
 
+    // pieces of a format string; the embedded expressions are lexed as regular tokens
+    // between a LeftBrace and a RightBrace
+    FormatStringStart,
+    FormatStringText,
+    FormatStringEnd,
+
     // compound types
     File,
     ErrorTree,
@@ -207,6 +230,7 @@ pub enum SyntaxKind {
     StmtFor,
     StmtMatch,
     StmtWhile,
+    LoopLabel,
     StmtImport,
     StmtAssert,
     ExprName,
@@ -215,15 +239,21 @@ pub enum SyntaxKind {
     ExprUnary,
     ExprParen,
     ExprLiteral,
+    ExprFormatString,
     ExprMember,
     ExprIndex,
+    ExprSlice,
     ExprTernary,
     ExprAssign,
     ExprType,
     ExprNonNull,
     ExprNullish,
+    ExprSafeNav,
     Mixin,
     MixinInclude,
+    MixinRequire,
+    Property,
+    PropertyAccessor,
     MixinEntry,
     Struct,
     StructEntry,
@@ -236,15 +266,144 @@ pub enum SyntaxKind {
     TryBlock,
     MatchRule,
     MatchPattern,
+    PatternList,
+    PatternStruct,
+    PatternField,
+    PatternEnumCase,
+    PatternBinding,
+    PatternLiteral,
     IdentList,
     QualifiedIdent,
     ImportPath,
     ArgList,
+    NamedArg,
     ListLiteral,
+    ListComprehension,
+    GeneratorExpr,
+    ComprehensionFor,
+    ComprehensionIf,
+    MapLiteral,
+    MapEntry,
     ModuleFlag,
     Eof,
 }
 
+// returns the index of the '}' matching the '{' at open, skipping over string literals
+fn find_closing_brace(bytes: &[u8], open: usize) -> Option<usize> {
+    let mut depth = 0;
+    let mut i = open;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'{' => depth += 1,
+            b'}' => {
+                depth -= 1;
+                if depth == 0 {
+                    return Some(i);
+                }
+            }
+            quote @ (b'"' | b'\'') => {
+                i += 1;
+                while i < bytes.len() && bytes[i] != quote {
+                    if bytes[i] == b'\\' {
+                        i += 1;
+                    }
+                    i += 1;
+                }
+            }
+            _ => {}
+        }
+        i += 1;
+    }
+
+    None
+}
+
+// consumes the rest of a format string, whose opening f" or f' has already been matched
+fn lex_format_string(lex: &mut logos::Lexer<SyntaxKind>) -> bool {
+    let quote = lex.slice().as_bytes()[1];
+    let rest = lex.remainder().as_bytes();
+    let mut i = 0;
+    while i < rest.len() {
+        match rest[i] {
+            b'{' if rest.get(i + 1) == Some(&b'{') => i += 1,
+            b'{' => match find_closing_brace(rest, i) {
+                Some(close) => i = close,
+                None => return false,
+            },
+            c if c == quote => {
+                lex.bump(i + 1);
+                return true;
+            }
+            _ => {}
+        }
+        i += 1;
+    }
+
+    false
+}
+
+fn split_format_string<'a>(
+    s: &'a str,
+    span: logos::Span,
+    tokens: &mut Vec<Result<(SyntaxKind, &'a str, logos::Span), LexError>>,
+) {
+    fn piece(
+        s: &str,
+        kind: SyntaxKind,
+        range: logos::Span,
+    ) -> Result<(SyntaxKind, &str, logos::Span), LexError> {
+        Ok((kind, &s[range.clone()], range))
+    }
+
+    let bytes = s.as_bytes();
+    let body_end = span.end - 1;
+    tokens.push(piece(
+        s,
+        SyntaxKind::FormatStringStart,
+        span.start..span.start + 2,
+    ));
+
+    let mut pos = span.start + 2;
+    let mut text_start = pos;
+    while pos < body_end {
+        if bytes[pos..body_end].starts_with(b"{{") || bytes[pos..body_end].starts_with(b"}}") {
+            pos += 2;
+            continue;
+        }
+        if bytes[pos] != b'{' {
+            pos += 1;
+            continue;
+        }
+
+        if text_start < pos {
+            tokens.push(piece(s, SyntaxKind::FormatStringText, text_start..pos));
+        }
+        let close = find_closing_brace(&bytes[..body_end], pos).unwrap_or(body_end);
+        tokens.push(piece(s, SyntaxKind::LeftBrace, pos..pos + 1));
+        for token in lex(&s[pos + 1..close]) {
+            tokens.push(match token {
+                Ok((kind, slice, range)) => {
+                    Ok((kind, slice, range.start + pos + 1..range.end + pos + 1))
+                }
+                Err(err) => Err(LexError {
+                    span: err.span.start + pos + 1..err.span.end + pos + 1,
+                    ..err
+                }),
+            });
+        }
+        if close < body_end {
+            tokens.push(piece(s, SyntaxKind::RightBrace, close..close + 1));
+        }
+        pos = close + 1;
+        text_start = pos;
+    }
+
+    if text_start < body_end {
+        tokens.push(piece(s, SyntaxKind::FormatStringText, text_start..body_end));
+    }
+    tokens.push(piece(s, SyntaxKind::FormatStringEnd, body_end..span.end));
+}
+
 pub fn lex(s: &str) -> Vec<Result<(SyntaxKind, &str, logos::Span), LexError>> {
     let mut lexer = SyntaxKind::lexer(s);
     let mut tokens = Vec::new();
@@ -252,6 +411,9 @@ pub fn lex(s: &str) -> Vec<Result<(SyntaxKind, &str, logos::Span), LexError>> {
     while let Some(token_result) = lexer.next() {
         let slice = lexer.slice();
         match token_result {
+            Ok(SyntaxKind::FormatStringLiteral) => {
+                split_format_string(s, lexer.span(), &mut tokens);
+            }
             Ok(token) => tokens.push(Ok((token, slice, lexer.span()))),
             Err(_) => {
                 let span = lexer.span();
@@ -320,6 +482,34 @@ mod tests {
         assert_eq!(tokens[6], SyntaxKind::StringLiteral);
     }
 
+    #[test]
+    fn test_format_string() {
+        use SyntaxKind::*;
+        let tokens = non_trivia_tokens(r#"f"a{x + 1}b{{c}}" f'{s["k"]}'"#);
+        assert_eq!(
+            tokens,
+            vec![
+                FormatStringStart,
+                FormatStringText,
+                LeftBrace,
+                Identifier,
+                Plus,
+                DecIntLiteral,
+                RightBrace,
+                FormatStringText,
+                FormatStringEnd,
+                FormatStringStart,
+                LeftBrace,
+                Identifier,
+                LeftBracket,
+                StringLiteral,
+                RightBracket,
+                RightBrace,
+                FormatStringEnd,
+            ]
+        );
+    }
+
     #[test]
     fn test_operators() {
         let tokens = non_trivia_tokens("+ - * / % == != <= >= << >> && ||");
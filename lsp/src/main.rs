@@ -53,6 +53,43 @@ fn to_lsp_range(doc: &DocumentState, range: TextRange) -> Range {
     )
 }
 
+fn diagnostics(doc: &DocumentState) -> Vec<Diagnostic> {
+    let diagnostic = |range, severity, source: &str, message| Diagnostic {
+        range: to_lsp_range(doc, range),
+        severity: Some(severity),
+        code: None,
+        code_description: None,
+        source: Some(source.into()),
+        message,
+        related_information: None,
+        tags: None,
+        data: None,
+    };
+
+    let mut v = Vec::new();
+    for (range, msg) in doc
+        .parse_error_ranges()
+        .into_iter()
+        .chain(doc.private_import_ranges())
+    {
+        v.push(diagnostic(
+            range,
+            DiagnosticSeverity::ERROR,
+            "aria-parser",
+            msg,
+        ));
+    }
+    for (range, msg) in doc.compiler_warning_ranges() {
+        v.push(diagnostic(
+            range,
+            DiagnosticSeverity::WARNING,
+            "aria-compiler",
+            msg,
+        ));
+    }
+    v
+}
+
 #[tower_lsp::async_trait]
 impl LanguageServer for Backend {
     async fn initialize(&self, _: InitializeParams) -> Result<InitializeResult> {
@@ -86,23 +123,7 @@ impl LanguageServer for Backend {
         let (diags, uri_clone) = {
             let mut docs = self.documents.lock();
             let doc = DocumentState::new(text);
-            let diags = {
-                let mut v = Vec::new();
-                for (range, msg) in doc.parse_error_ranges() {
-                    v.push(Diagnostic {
-                        range: to_lsp_range(&doc, range),
-                        severity: Some(DiagnosticSeverity::ERROR),
-                        code: None,
-                        code_description: None,
-                        source: Some("aria-parser".into()),
-                        message: msg,
-                        related_information: None,
-                        tags: None,
-                        data: None,
-                    });
-                }
-                v
-            };
+            let diags = diagnostics(&doc);
             docs.insert(uri.clone(), doc);
             (diags, uri.clone())
         };
@@ -160,23 +181,7 @@ impl LanguageServer for Backend {
                     }
                 }
                 doc.update_text(text);
-                let diags = {
-                    let mut v = Vec::new();
-                    for (range, msg) in doc.parse_error_ranges() {
-                        v.push(Diagnostic {
-                            range: to_lsp_range(doc, range),
-                            severity: Some(DiagnosticSeverity::ERROR),
-                            code: None,
-                            code_description: None,
-                            source: Some("aria-parser".into()),
-                            message: msg,
-                            related_information: None,
-                            tags: None,
-                            data: None,
-                        });
-                    }
-                    v
-                };
+                let diags = diagnostics(doc);
                 (Some(diags), Some(uri.clone()))
             } else {
                 (None, None)
@@ -87,7 +87,7 @@ pub fn parse(text: &str) -> Parse {
     fn prefix_binding_power(op: SyntaxKind) -> Option<((), u8)> {
         use SyntaxKind::*;
         match op {
-            Not | Minus => Some(((), 23)),
+            Not | Minus | AwaitKwd => Some(((), 23)),
             _ => None,
         }
     }
@@ -103,14 +103,17 @@ pub fn parse(text: &str) -> Parse {
                 | DecIntLiteral
                 | FloatLiteral
                 | StringLiteral
+                | FormatStringStart
                 | TrueKwd
                 | FalseKwd
                 | LeftParen
                 | LeftBracket
+                | LeftBrace
                 | Pipe
                 | LogicalOr
                 | Not
                 | Minus
+                | AwaitKwd
         )
     }
 
@@ -162,7 +165,7 @@ pub fn parse(text: &str) -> Parse {
                     MixinKwd => self.decl_mixin(),
                     EnumKwd => self.decl_enum(),
                     ExtensionKwd => self.decl_struct_or_ext(Extension, ExtensionKwd),
-                    FuncKwd => self.decl_func(),
+                    FuncKwd | AsyncKwd => self.decl_func(),
                     _ => self.stmt(),
                 }
             }
@@ -180,6 +183,67 @@ pub fn parse(text: &str) -> Parse {
             self.close(m, MixinInclude);
         }
 
+        fn mixin_require(&mut self) {
+            assert!(self.at(RequireKwd));
+            let m = self.open();
+
+            self.expect(RequireKwd);
+            self.expect(FuncKwd);
+            self.expect(Identifier);
+            if self.at(LeftParen) {
+                self.param_list(LeftParen, RightParen);
+            } else {
+                self.expect(LeftParen);
+            }
+            self.expect(Semicolon);
+
+            self.close(m, MixinRequire);
+        }
+
+        fn decl_prop(&mut self) {
+            assert!(self.at(PropKwd));
+            let m = self.open();
+
+            self.expect(PropKwd);
+            self.expect(Identifier);
+            self.expect(LeftBrace);
+
+            while !self.at(RightBrace) && !self.eof() {
+                if self.at_contextual_kwd("get") {
+                    self.prop_accessor(false);
+                } else if self.at_contextual_kwd("set") {
+                    self.prop_accessor(true);
+                } else {
+                    self.advance_with_error(PropertyAccessor);
+                }
+            }
+
+            self.expect(RightBrace);
+            self.close(m, Property);
+        }
+
+        fn prop_accessor(&mut self, is_setter: bool) {
+            let m = self.open();
+
+            self.expect(Identifier);
+            if is_setter && self.at(LeftParen) {
+                self.param_list(LeftParen, RightParen);
+            }
+            if !self.at(LeftBrace) {
+                self.expect(Arrow);
+            }
+            if self.at(LeftBrace) {
+                self.block();
+            } else if is_setter {
+                self.stmt();
+            } else {
+                let _ = self.expr();
+                self.expect(Semicolon);
+            }
+
+            self.close(m, PropertyAccessor);
+        }
+
         fn decl_struct_or_ext(&mut self, kind: SyntaxKind, kwd: SyntaxKind) {
             assert!(self.at(kwd));
             let m = self.open();
@@ -203,13 +267,15 @@ pub fn parse(text: &str) -> Parse {
         fn entry(&mut self, kind: SyntaxKind) {
             let m = self.open();
             match self.nth(0) {
-                FuncKwd => self.decl_func(),
+                FuncKwd | AsyncKwd => self.decl_func(),
                 OperatorKwd | ReverseKwd => self.decl_operator(),
                 StructKwd => self.decl_struct_or_ext(Struct, StructKwd),
                 EnumKwd => self.decl_enum(),
                 IncludeKwd => self.mixin_include(),
+                PropKwd => self.decl_prop(),
+                RequireKwd if kind == MixinEntry && self.nth(1) == FuncKwd => self.mixin_require(),
                 TypeKwd | InstanceKwd => {
-                    if self.nth(1) == FuncKwd {
+                    if matches!(self.nth(1), FuncKwd | AsyncKwd) {
                         self.decl_func();
                     } else {
                         self.decl_val();
@@ -327,6 +393,7 @@ pub fn parse(text: &str) -> Parse {
                 }
                 LeftBracket => {
                     self.advance();
+                    self.eat(Colon);
                     self.expect(RightBracket);
                     if self.at(Assign) {
                         self.expect(Assign)
@@ -349,6 +416,7 @@ pub fn parse(text: &str) -> Parse {
 
             self.parse_access_modifier();
 
+            self.eat(AsyncKwd);
             self.expect(FuncKwd);
             self.expect(Identifier);
 
@@ -463,13 +531,16 @@ pub fn parse(text: &str) -> Parse {
                 MatchKwd => self.stmt_match(),
                 WhileKwd => self.stmt_while(),
                 ForKwd => self.stmt_for(),
+                Identifier if self.nth(1) == Colon && self.nth(2) == WhileKwd => self.stmt_while(),
+                Identifier if self.nth(1) == Colon && self.nth(2) == ForKwd => self.stmt_for(),
                 ThrowKwd => self.stmt_kwd_with_expr(ThrowKwd),
+                YieldKwd => self.stmt_kwd_with_expr(YieldKwd),
                 ReturnKwd => self.stmt_return(),
                 LeftBrace => self.block(),
                 TryKwd => self.try_catch(),
                 StructKwd => self.decl_struct_or_ext(Struct, StructKwd),
                 EnumKwd => self.decl_enum(),
-                FuncKwd => self.decl_func(),
+                FuncKwd | AsyncKwd => self.decl_func(),
                 _ => self.stmt_expr(),
             }
         }
@@ -500,11 +571,16 @@ pub fn parse(text: &str) -> Parse {
         }
 
         fn stmt_for(&mut self) {
-            assert!(self.at(ForKwd));
             let m = self.open();
 
+            self.loop_label();
+            assert!(self.at(ForKwd));
             self.expect(ForKwd);
-            self.expect(Identifier);
+            if self.at_destructure_pattern() {
+                self.pattern();
+            } else {
+                self.expect(Identifier);
+            }
             self.expect(InKwd);
             let _ = self.expr();
             self.block();
@@ -576,19 +652,6 @@ pub fn parse(text: &str) -> Parse {
             let m = self.open();
 
             match self.nth(0) {
-                CaseKwd => {
-                    self.expect(CaseKwd);
-                    self.expect(Identifier);
-                    if self.at(LeftParen) {
-                        self.expect(LeftParen);
-                        self.expect(Identifier);
-                        if self.at(Colon) {
-                            self.expect(Colon);
-                            let _ = self.expr();
-                        }
-                        self.expect(RightParen);
-                    }
-                }
                 Equal | NotEqual | IsaKwd => {
                     self.advance(); // comparison operator
                     let _ = self.expr();
@@ -597,16 +660,116 @@ pub fn parse(text: &str) -> Parse {
                     self.advance(); // relational operator
                     let _ = self.expr();
                 }
-                _ => self.advance_with_error(MatchPattern),
+                _ => self.pattern(),
             }
 
             self.close(m, MatchPattern);
         }
 
+        // patterns that take a value apart, as opposed to just naming it
+        fn at_destructure_pattern(&self) -> bool {
+            match self.nth(0) {
+                LeftBracket | LeftBrace | CaseKwd => true,
+                Identifier => matches!(self.nth(1), LeftParen | LeftBrace | Dot),
+                _ => false,
+            }
+        }
+
+        fn pattern(&mut self) {
+            let m = self.open();
+
+            match self.nth(0) {
+                LeftBracket => {
+                    self.expect(LeftBracket);
+                    while !self.at(RightBracket) && !self.eof() {
+                        if self.eat(Ellipsis) {
+                            if self.at(Identifier) {
+                                self.expect(Identifier);
+                            }
+                        } else {
+                            self.pattern();
+                        }
+                        if !self.at(RightBracket) {
+                            self.expect(Comma);
+                        }
+                    }
+                    self.expect(RightBracket);
+                    self.close(m, PatternList);
+                }
+                LeftBrace => {
+                    self.pattern_struct_fields();
+                    self.close(m, PatternStruct);
+                }
+                CaseKwd => {
+                    self.expect(CaseKwd);
+                    self.expect(Identifier);
+                    if self.eat(LeftParen) {
+                        self.pattern();
+                        self.expect(RightParen);
+                    }
+                    self.close(m, PatternEnumCase);
+                }
+                Identifier if self.nth(1) == LeftParen => {
+                    self.expect(Identifier);
+                    self.expect(LeftParen);
+                    self.pattern();
+                    self.expect(RightParen);
+                    self.close(m, PatternEnumCase);
+                }
+                Identifier if matches!(self.nth(1), LeftBrace | Dot) => {
+                    self.expect(Identifier);
+                    while self.eat(Dot) {
+                        self.expect(Identifier);
+                    }
+                    self.pattern_struct_fields();
+                    self.close(m, PatternStruct);
+                }
+                _ if self.at(Identifier) => {
+                    self.expect(Identifier);
+                    if self.eat(Colon) {
+                        let _ = self.expr();
+                    }
+                    self.close(m, PatternBinding);
+                }
+                Minus | DecIntLiteral | HexIntLiteral | OctIntLiteral | BinIntLiteral
+                | FloatLiteral | StringLiteral => {
+                    self.eat(Minus);
+                    self.advance();
+                    self.close(m, PatternLiteral);
+                }
+                _ => {
+                    self.report_error(MatchPattern);
+                    self.advance();
+                    self.close(m, ErrorTree);
+                }
+            }
+        }
+
+        fn pattern_struct_fields(&mut self) {
+            self.expect(LeftBrace);
+            while !self.at(RightBrace) && !self.eof() {
+                if !self.at(Identifier) {
+                    self.advance_with_error(Identifier);
+                    continue;
+                }
+                let m = self.open();
+                self.expect(Identifier);
+                if self.eat(Colon) {
+                    self.pattern();
+                }
+                self.close(m, PatternField);
+                if !self.at(RightBrace) {
+                    self.expect(Comma);
+                }
+            }
+            self.expect(RightBrace);
+        }
+
         fn stmt_while(&mut self) {
-            assert!(self.at(WhileKwd));
             let m = self.open();
 
+            self.loop_label();
+            assert!(self.at(WhileKwd));
             self.expect(WhileKwd);
             let _ = self.expr();
             self.block();
@@ -626,9 +789,16 @@ pub fn parse(text: &str) -> Parse {
 
             self.expect(TryKwd);
             self.block();
-            self.expect(CatchKwd);
-            self.expect(Identifier);
-            self.block();
+            // either clause may be omitted, but not both
+            if self.at(CatchKwd) || !self.at(FinallyKwd) {
+                self.expect(CatchKwd);
+                self.expect(Identifier);
+                self.block();
+            }
+            if self.at(FinallyKwd) {
+                self.expect(FinallyKwd);
+                self.block();
+            }
 
             self.close(m, TryBlock);
         }
@@ -677,10 +847,14 @@ pub fn parse(text: &str) -> Parse {
             self.expect(ValKwd);
 
             loop {
-                self.expect(Identifier);
+                if self.at_destructure_pattern() {
+                    self.pattern();
+                } else {
+                    self.expect(Identifier);
 
-                if self.at(Colon) {
-                    self.type_annotation();
+                    if self.at(Colon) {
+                        self.type_annotation();
+                    }
                 }
 
                 self.expect(Assign);
@@ -703,11 +877,24 @@ pub fn parse(text: &str) -> Parse {
             let m = self.open();
 
             self.expect(kind);
+            // break and continue may name the loop they apply to
+            if self.nth(0) == Identifier {
+                self.expect(Identifier);
+            }
             self.expect(Semicolon);
 
             self.close(m, StmtReturn);
         }
 
+        fn loop_label(&mut self) {
+            if self.nth(0) == Identifier && self.nth(1) == Colon {
+                let m = self.open();
+                self.expect(Identifier);
+                self.expect(Colon);
+                self.close(m, LoopLabel);
+            }
+        }
+
         fn stmt_return(&mut self) {
             assert!(self.at(ReturnKwd));
             let m = self.open();
@@ -856,7 +1043,7 @@ pub fn parse(text: &str) -> Parse {
             }
             self.expect(Arrow);
 
-            if self.at(LeftBrace) {
+            if self.at(LeftBrace) && !self.is_map_literal_start() {
                 self.block();
             } else {
                 let _ = self.expr();
@@ -882,6 +1069,11 @@ pub fn parse(text: &str) -> Parse {
                     }
                 }
 
+                // "::" not followed by a case name separates slice bounds, e.g. "x[1::2]"
+                if op == DoubleColon && self.nth(1) != Identifier {
+                    break;
+                }
+
                 if let Some((l_bp, ())) = postfix_binding_power(op) {
                     if l_bp < min_bp {
                         break;
@@ -894,6 +1086,11 @@ pub fn parse(text: &str) -> Parse {
                             self.init_list();
                             self.close(m, ExprCall)
                         }
+                        LeftBracket if self.is_slice() => {
+                            let m = self.open_before(lhs);
+                            self.slice();
+                            self.close(m, ExprSlice)
+                        }
                         LeftBracket => {
                             let m = self.open_before(lhs);
                             self.expr_list(LeftBracket, RightBracket);
@@ -932,6 +1129,22 @@ pub fn parse(text: &str) -> Parse {
                     continue;
                 }
 
+                // "?." navigates into a Maybe; "x?.y" keeps the dot for the member
+                // access, while "x?.[i]" and "x?.(args)" consume it
+                if op == Question && self.nth(1) == Dot {
+                    let l_bp = 25u8;
+                    if l_bp < min_bp {
+                        break;
+                    }
+                    let m = self.open_before(lhs);
+                    self.expect(Question);
+                    if matches!(self.nth(1), LeftBracket | LeftParen) {
+                        self.expect(Dot);
+                    }
+                    lhs = self.close(m, ExprSafeNav);
+                    continue;
+                }
+
                 if op == Question && !self.is_ternary_question() {
                     let l_bp = 25u8;
                     if l_bp < min_bp {
@@ -1071,8 +1284,33 @@ pub fn parse(text: &str) -> Parse {
                 LeftParen => {
                     self.expect(LeftParen);
                     let _ = self.expr_bp(0);
-                    self.expect(RightParen);
-                    self.close(m, ExprParen)
+                    if self.at(ForKwd) {
+                        self.comprehension_clauses();
+                        self.expect(RightParen);
+                        self.close(m, GeneratorExpr)
+                    } else {
+                        self.expect(RightParen);
+                        self.close(m, ExprParen)
+                    }
+                }
+
+                LeftBracket if self.nth(1) != RightBracket => {
+                    self.expect(LeftBracket);
+                    let _ = self.expr();
+                    if self.at(ForKwd) {
+                        self.comprehension_clauses();
+                        self.expect(RightBracket);
+                        self.close(m, ListComprehension)
+                    } else {
+                        while self.at(Comma) && !self.eof() {
+                            self.expect(Comma);
+                            if !self.at(RightBracket) {
+                                self.expr();
+                            }
+                        }
+                        self.expect(RightBracket);
+                        self.close(m, ListLiteral)
+                    }
                 }
 
                 LeftBracket => {
@@ -1080,6 +1318,16 @@ pub fn parse(text: &str) -> Parse {
                     self.close(m, ListLiteral)
                 }
 
+                LeftBrace => {
+                    self.map_literal();
+                    self.close(m, MapLiteral)
+                }
+
+                FormatStringStart => {
+                    self.format_string();
+                    self.close(m, ExprFormatString)
+                }
+
                 op if prefix_binding_power(op).is_some() => {
                     let ((), r_bp) = prefix_binding_power(op).unwrap();
                     self.advance();
@@ -1115,13 +1363,172 @@ pub fn parse(text: &str) -> Parse {
             self.expect(right_delim);
         }
 
+        // the "for x in xs" and "if cond" clauses of a list comprehension or generator expression
+        fn comprehension_clauses(&mut self) {
+            while self.at(ForKwd) || self.at(IfKwd) {
+                let m = self.open();
+                if self.eat(IfKwd) {
+                    let _ = self.expr();
+                    self.close(m, ComprehensionIf);
+                } else {
+                    self.expect(ForKwd);
+                    if self.at_destructure_pattern() {
+                        self.pattern();
+                    } else {
+                        self.expect(Identifier);
+                    }
+                    self.expect(InKwd);
+                    let _ = self.expr();
+                    self.close(m, ComprehensionFor);
+                }
+            }
+        }
+
+        // a bracket that holds a top-level colon is a slice rather than an index
+        fn is_slice(&self) -> bool {
+            let mut depth = 0;
+            let mut i = 1;
+            loop {
+                match self.nth(i) {
+                    LeftParen | LeftBracket | LeftBrace => depth += 1,
+                    RightParen | RightBrace if depth > 0 => depth -= 1,
+                    RightBracket if depth > 0 => depth -= 1,
+                    Colon | DoubleColon if depth == 0 => return true,
+                    Question if depth == 0 => return false,
+                    RightParen | RightBracket | RightBrace | Semicolon | Eof => return false,
+                    _ => {}
+                }
+                i += 1;
+            }
+        }
+
+        // [start? : stop? (: step?)?], where "::" separates an omitted stop
+        fn slice(&mut self) {
+            self.expect(LeftBracket);
+
+            if !self.at(Colon) && !self.at(DoubleColon) {
+                let _ = self.expr();
+            }
+
+            if self.eat(DoubleColon) {
+                if !self.at(RightBracket) {
+                    let _ = self.expr();
+                }
+            } else {
+                self.expect(Colon);
+                if !self.at(Colon) && !self.at(RightBracket) {
+                    let _ = self.expr();
+                }
+                if self.eat(Colon) && !self.at(RightBracket) {
+                    let _ = self.expr();
+                }
+            }
+
+            self.expect(RightBracket);
+        }
+
+        fn format_string(&mut self) {
+            self.expect(FormatStringStart);
+
+            while !self.at(FormatStringEnd) && !self.eof() {
+                match self.nth(0) {
+                    FormatStringText => self.advance(),
+                    LeftBrace => {
+                        self.expect(LeftBrace);
+                        let _ = self.expr();
+                        self.expect(RightBrace);
+                    }
+                    _ => self.advance_with_error(FormatStringEnd),
+                }
+            }
+
+            self.expect(FormatStringEnd);
+        }
+
+        fn map_literal(&mut self) {
+            self.expect(LeftBrace);
+
+            while !self.at(RightBrace) && !self.eof() {
+                let m = self.open();
+                let _ = self.expr();
+                self.expect(Colon);
+                let _ = self.expr();
+                self.close(m, MapEntry);
+
+                if self.at(Comma) {
+                    self.expect(Comma);
+                } else {
+                    break;
+                }
+            }
+
+            self.expect(RightBrace);
+        }
+
+        // a brace after a lambda arrow opens a map literal only if its first
+        // entry is followed by a ':' (e.g. `|x| => {x: 1}`), otherwise it is a block
+        fn is_map_literal_start(&self) -> bool {
+            // skip the opening brace itself
+            let mut kinds = self.tokens[self.pos..]
+                .iter()
+                .map(|tok| tok.0)
+                .filter(|kind| !is_trivia(*kind))
+                .skip(1)
+                .peekable();
+
+            if let Some(first) = kinds.peek()
+                && self.is_keyword(*first)
+                && !matches!(first, TrueKwd | FalseKwd)
+            {
+                return false;
+            }
+
+            let mut depth = 0i32;
+            for kind in kinds {
+                match kind {
+                    LeftParen | LeftBracket | LeftBrace => depth += 1,
+                    RightParen | RightBracket | RightBrace if depth > 0 => depth -= 1,
+                    Colon if depth == 0 => return true,
+                    RightBrace | Semicolon | Question if depth == 0 => return false,
+                    _ => {}
+                }
+            }
+
+            false
+        }
+
         fn arg_list(&mut self) {
             assert!(self.at(LeftParen));
             let m = self.open();
-            self.expr_list(LeftParen, RightParen);
+            self.expect(LeftParen);
+
+            if !self.at(RightParen) {
+                self.call_arg();
+                while self.at(Comma) && !self.eof() {
+                    self.expect(Comma);
+                    if !self.at(RightParen) {
+                        self.call_arg();
+                    }
+                }
+            }
+
+            self.expect(RightParen);
             self.close(m, ArgList);
         }
 
+        // either an expression, or a keyword argument: name: expression
+        fn call_arg(&mut self) {
+            if self.at(Identifier) && self.nth(1) == Colon {
+                let m = self.open();
+                self.expect(Identifier);
+                self.expect(Colon);
+                self.expr();
+                self.close(m, NamedArg);
+            } else {
+                self.expr();
+            }
+        }
+
         fn open(&mut self) -> MarkOpened {
             let mark = MarkOpened {
                 index: self.events.len(),
@@ -1203,6 +1610,15 @@ pub fn parse(text: &str) -> Parse {
             self.nth(0) == kind || (kind == Identifier && self.is_keyword(self.nth(0)))
         }
 
+        // get and set are only keywords inside a property declaration
+        fn at_contextual_kwd(&self, kwd: &str) -> bool {
+            self.at(Identifier)
+                && self.tokens[self.pos..]
+                    .iter()
+                    .find(|tok| !is_trivia(tok.0))
+                    .is_some_and(|tok| tok.1 == kwd)
+        }
+
         fn eat(&mut self, kind: SyntaxKind) -> bool {
             if self.at(kind) {
                 self.advance();
@@ -1216,6 +1632,8 @@ pub fn parse(text: &str) -> Parse {
             matches!(
                 kind,
                 AssertKwd
+                    | AsyncKwd
+                    | AwaitKwd
                     | BreakKwd
                     | CaseKwd
                     | CatchKwd
@@ -1224,6 +1642,7 @@ pub fn parse(text: &str) -> Parse {
                     | ElsifKwd
                     | EnumKwd
                     | ExtensionKwd
+                    | FinallyKwd
                     | FlagKwd
                     | ForKwd
                     | FromKwd
@@ -1237,6 +1656,8 @@ pub fn parse(text: &str) -> Parse {
                     | MatchKwd
                     | MixinKwd
                     | OperatorKwd
+                    | PropKwd
+                    | RequireKwd
                     | ReturnKwd
                     | ReverseKwd
                     | StructKwd
@@ -1245,6 +1666,7 @@ pub fn parse(text: &str) -> Parse {
                     | TypeKwd
                     | ValKwd
                     | WhileKwd
+                    | YieldKwd
                     | AndKwd
                     | TrueKwd
                     | FalseKwd
@@ -1723,6 +2145,291 @@ mod tests {
         )
     }
 
+    #[test]
+    fn test_map_literal() {
+        expect_tree(
+            "func test() { val x = {1: 2}; }",
+            &[
+                "File@0..23",
+                "  Func@0..23",
+                "    FuncKwd@0..4 \"func\"",
+                "    Identifier@4..8 \"test\"",
+                "    ParamList@8..10",
+                "      LeftParen@8..9 \"(\"",
+                "      RightParen@9..10 \")\"",
+                "    Block@10..23",
+                "      LeftBrace@10..11 \"{\"",
+                "      StmtVal@11..22",
+                "        ValKwd@11..14 \"val\"",
+                "        Identifier@14..15 \"x\"",
+                "        Assign@15..16 \"=\"",
+                "        MapLiteral@16..21",
+                "          LeftBrace@16..17 \"{\"",
+                "          MapEntry@17..20",
+                "            ExprLiteral@17..18",
+                "              DecIntLiteral@17..18 \"1\"",
+                "            Colon@18..19 \":\"",
+                "            ExprLiteral@19..20",
+                "              DecIntLiteral@19..20 \"2\"",
+                "          RightBrace@20..21 \"}\"",
+                "        Semicolon@21..22 \";\"",
+                "      RightBrace@22..23 \"}\"",
+            ],
+        )
+    }
+
+    #[test]
+    fn test_format_string() {
+        expect_tree(
+            "func test() { val x = f\"a{y}\"; }",
+            &[
+                "File@0..25",
+                "  Func@0..25",
+                "    FuncKwd@0..4 \"func\"",
+                "    Identifier@4..8 \"test\"",
+                "    ParamList@8..10",
+                "      LeftParen@8..9 \"(\"",
+                "      RightParen@9..10 \")\"",
+                "    Block@10..25",
+                "      LeftBrace@10..11 \"{\"",
+                "      StmtVal@11..24",
+                "        ValKwd@11..14 \"val\"",
+                "        Identifier@14..15 \"x\"",
+                "        Assign@15..16 \"=\"",
+                "        ExprFormatString@16..23",
+                "          FormatStringStart@16..18 \"f\\\"\"",
+                "          FormatStringText@18..19 \"a\"",
+                "          LeftBrace@19..20 \"{\"",
+                "          ExprName@20..21",
+                "            Identifier@20..21 \"y\"",
+                "          RightBrace@21..22 \"}\"",
+                "          FormatStringEnd@22..23 \"\\\"\"",
+                "        Semicolon@23..24 \";\"",
+                "      RightBrace@24..25 \"}\"",
+            ],
+        )
+    }
+
+    #[test]
+    fn test_val_destructure() {
+        expect_tree(
+            "func test() { val [a, ...b] = x; }",
+            &[
+                "File@0..26",
+                "  Func@0..26",
+                "    FuncKwd@0..4 \"func\"",
+                "    Identifier@4..8 \"test\"",
+                "    ParamList@8..10",
+                "      LeftParen@8..9 \"(\"",
+                "      RightParen@9..10 \")\"",
+                "    Block@10..26",
+                "      LeftBrace@10..11 \"{\"",
+                "      StmtVal@11..25",
+                "        ValKwd@11..14 \"val\"",
+                "        PatternList@14..22",
+                "          LeftBracket@14..15 \"[\"",
+                "          PatternBinding@15..16",
+                "            Identifier@15..16 \"a\"",
+                "          Comma@16..17 \",\"",
+                "          Ellipsis@17..20 \"...\"",
+                "          Identifier@20..21 \"b\"",
+                "          RightBracket@21..22 \"]\"",
+                "        Assign@22..23 \"=\"",
+                "        ExprName@23..24",
+                "          Identifier@23..24 \"x\"",
+                "        Semicolon@24..25 \";\"",
+                "      RightBrace@25..26 \"}\"",
+            ],
+        )
+    }
+
+    #[test]
+    fn test_try_finally() {
+        expect_tree(
+            "func test() { try { } finally { } }",
+            &[
+                "File@0..26",
+                "  Func@0..26",
+                "    FuncKwd@0..4 \"func\"",
+                "    Identifier@4..8 \"test\"",
+                "    ParamList@8..10",
+                "      LeftParen@8..9 \"(\"",
+                "      RightParen@9..10 \")\"",
+                "    Block@10..26",
+                "      LeftBrace@10..11 \"{\"",
+                "      TryBlock@11..25",
+                "        TryKwd@11..14 \"try\"",
+                "        Block@14..16",
+                "          LeftBrace@14..15 \"{\"",
+                "          RightBrace@15..16 \"}\"",
+                "        FinallyKwd@16..23 \"finally\"",
+                "        Block@23..25",
+                "          LeftBrace@23..24 \"{\"",
+                "          RightBrace@24..25 \"}\"",
+                "      RightBrace@25..26 \"}\"",
+            ],
+        )
+    }
+
+    #[test]
+    fn test_yield() {
+        expect_tree(
+            "func test() { yield 1; }",
+            &[
+                "File@0..19",
+                "  Func@0..19",
+                "    FuncKwd@0..4 \"func\"",
+                "    Identifier@4..8 \"test\"",
+                "    ParamList@8..10",
+                "      LeftParen@8..9 \"(\"",
+                "      RightParen@9..10 \")\"",
+                "    Block@10..19",
+                "      LeftBrace@10..11 \"{\"",
+                "      StmtAssert@11..18",
+                "        YieldKwd@11..16 \"yield\"",
+                "        ExprLiteral@16..17",
+                "          DecIntLiteral@16..17 \"1\"",
+                "        Semicolon@17..18 \";\"",
+                "      RightBrace@18..19 \"}\"",
+            ],
+        )
+    }
+
+    #[test]
+    fn test_async_await() {
+        expect_tree(
+            "async func f() { await g(); }",
+            &[
+                "File@0..23",
+                "  Func@0..23",
+                "    AsyncKwd@0..5 \"async\"",
+                "    FuncKwd@5..9 \"func\"",
+                "    Identifier@9..10 \"f\"",
+                "    ParamList@10..12",
+                "      LeftParen@10..11 \"(\"",
+                "      RightParen@11..12 \")\"",
+                "    Block@12..23",
+                "      LeftBrace@12..13 \"{\"",
+                "      StmtExpr@13..22",
+                "        ExprUnary@13..21",
+                "          AwaitKwd@13..18 \"await\"",
+                "          ExprCall@18..21",
+                "            ExprName@18..19",
+                "              Identifier@18..19 \"g\"",
+                "            ArgList@19..21",
+                "              LeftParen@19..20 \"(\"",
+                "              RightParen@20..21 \")\"",
+                "        Semicolon@21..22 \";\"",
+                "      RightBrace@22..23 \"}\"",
+            ],
+        )
+    }
+
+    #[test]
+    fn test_mixin_require() {
+        expect_tree(
+            "mixin M { require func next(); }",
+            &[
+                "File@0..26",
+                "  Mixin@0..26",
+                "    MixinKwd@0..5 \"mixin\"",
+                "    QualifiedIdent@5..6",
+                "      Identifier@5..6 \"M\"",
+                "    LeftBrace@6..7 \"{\"",
+                "    StructEntry@7..25",
+                "      MixinRequire@7..25",
+                "        RequireKwd@7..14 \"require\"",
+                "        FuncKwd@14..18 \"func\"",
+                "        Identifier@18..22 \"next\"",
+                "        ParamList@22..24",
+                "          LeftParen@22..23 \"(\"",
+                "          RightParen@23..24 \")\"",
+                "        Semicolon@24..25 \";\"",
+                "    RightBrace@25..26 \"}\"",
+            ],
+        )
+    }
+
+    #[test]
+    fn test_property_decl() {
+        expect_tree(
+            "struct S { prop x { get => 1; set(v) => this.y = v; } }",
+            &[
+                "File@0..40",
+                "  Struct@0..40",
+                "    StructKwd@0..6 \"struct\"",
+                "    QualifiedIdent@6..7",
+                "      Identifier@6..7 \"S\"",
+                "    LeftBrace@7..8 \"{\"",
+                "    StructEntry@8..39",
+                "      Property@8..39",
+                "        PropKwd@8..12 \"prop\"",
+                "        Identifier@12..13 \"x\"",
+                "        LeftBrace@13..14 \"{\"",
+                "        PropertyAccessor@14..21",
+                "          Identifier@14..17 \"get\"",
+                "          Arrow@17..19 \"=>\"",
+                "          ExprLiteral@19..20",
+                "            DecIntLiteral@19..20 \"1\"",
+                "          Semicolon@20..21 \";\"",
+                "        PropertyAccessor@21..38",
+                "          Identifier@21..24 \"set\"",
+                "          ParamList@24..27",
+                "            LeftParen@24..25 \"(\"",
+                "            Param@25..26",
+                "              Identifier@25..26 \"v\"",
+                "            RightParen@26..27 \")\"",
+                "          Arrow@27..29 \"=>\"",
+                "          StmtExpr@29..38",
+                "            ExprAssign@29..37",
+                "              ExprMember@29..35",
+                "                ExprName@29..33",
+                "                  Identifier@29..33 \"this\"",
+                "                Dot@33..34 \".\"",
+                "                Identifier@34..35 \"y\"",
+                "              Assign@35..36 \"=\"",
+                "              ExprName@36..37",
+                "                Identifier@36..37 \"v\"",
+                "            Semicolon@37..38 \";\"",
+                "        RightBrace@38..39 \"}\"",
+                "    RightBrace@39..40 \"}\"",
+            ],
+        )
+    }
+
+    #[test]
+    fn test_labeled_loop() {
+        expect_tree(
+            "func f() { outer: while x { break outer; } }",
+            &[
+                "File@0..34",
+                "  Func@0..34",
+                "    FuncKwd@0..4 \"func\"",
+                "    Identifier@4..5 \"f\"",
+                "    ParamList@5..7",
+                "      LeftParen@5..6 \"(\"",
+                "      RightParen@6..7 \")\"",
+                "    Block@7..34",
+                "      LeftBrace@7..8 \"{\"",
+                "      StmtWhile@8..33",
+                "        LoopLabel@8..14",
+                "          Identifier@8..13 \"outer\"",
+                "          Colon@13..14 \":\"",
+                "        WhileKwd@14..19 \"while\"",
+                "        ExprName@19..20",
+                "          Identifier@19..20 \"x\"",
+                "        Block@20..33",
+                "          LeftBrace@20..21 \"{\"",
+                "          StmtReturn@21..32",
+                "            BreakKwd@21..26 \"break\"",
+                "            Identifier@26..31 \"outer\"",
+                "            Semicolon@31..32 \";\"",
+                "          RightBrace@32..33 \"}\"",
+                "      RightBrace@33..34 \"}\"",
+            ],
+        );
+    }
+
     #[test]
     fn test_unary_expr() {
         expect_tree(
@@ -1811,6 +2518,113 @@ mod tests {
         )
     }
 
+    #[test]
+    fn test_slice() {
+        expect_tree(
+            "func test() { val x = arr[1::2]; }",
+            &[
+                "File@0..27",
+                "  Func@0..27",
+                "    FuncKwd@0..4 \"func\"",
+                "    Identifier@4..8 \"test\"",
+                "    ParamList@8..10",
+                "      LeftParen@8..9 \"(\"",
+                "      RightParen@9..10 \")\"",
+                "    Block@10..27",
+                "      LeftBrace@10..11 \"{\"",
+                "      StmtVal@11..26",
+                "        ValKwd@11..14 \"val\"",
+                "        Identifier@14..15 \"x\"",
+                "        Assign@15..16 \"=\"",
+                "        ExprSlice@16..25",
+                "          ExprName@16..19",
+                "            Identifier@16..19 \"arr\"",
+                "          LeftBracket@19..20 \"[\"",
+                "          ExprLiteral@20..21",
+                "            DecIntLiteral@20..21 \"1\"",
+                "          DoubleColon@21..23 \"::\"",
+                "          ExprLiteral@23..24",
+                "            DecIntLiteral@23..24 \"2\"",
+                "          RightBracket@24..25 \"]\"",
+                "        Semicolon@25..26 \";\"",
+                "      RightBrace@26..27 \"}\"",
+            ],
+        )
+    }
+
+    #[test]
+    fn test_safe_navigation() {
+        expect_tree(
+            "func test() { val x = a?.[0]; }",
+            &[
+                "File@0..24",
+                "  Func@0..24",
+                "    FuncKwd@0..4 \"func\"",
+                "    Identifier@4..8 \"test\"",
+                "    ParamList@8..10",
+                "      LeftParen@8..9 \"(\"",
+                "      RightParen@9..10 \")\"",
+                "    Block@10..24",
+                "      LeftBrace@10..11 \"{\"",
+                "      StmtVal@11..23",
+                "        ValKwd@11..14 \"val\"",
+                "        Identifier@14..15 \"x\"",
+                "        Assign@15..16 \"=\"",
+                "        ExprIndex@16..22",
+                "          ExprSafeNav@16..19",
+                "            ExprName@16..17",
+                "              Identifier@16..17 \"a\"",
+                "            Question@17..18 \"?\"",
+                "            Dot@18..19 \".\"",
+                "          LeftBracket@19..20 \"[\"",
+                "          ExprLiteral@20..21",
+                "            DecIntLiteral@20..21 \"0\"",
+                "          RightBracket@21..22 \"]\"",
+                "        Semicolon@22..23 \";\"",
+                "      RightBrace@23..24 \"}\"",
+            ],
+        )
+    }
+
+    #[test]
+    fn test_list_comprehension() {
+        expect_tree(
+            "func test() { val x = [a for a in b if a]; }",
+            &[
+                "File@0..31",
+                "  Func@0..31",
+                "    FuncKwd@0..4 \"func\"",
+                "    Identifier@4..8 \"test\"",
+                "    ParamList@8..10",
+                "      LeftParen@8..9 \"(\"",
+                "      RightParen@9..10 \")\"",
+                "    Block@10..31",
+                "      LeftBrace@10..11 \"{\"",
+                "      StmtVal@11..30",
+                "        ValKwd@11..14 \"val\"",
+                "        Identifier@14..15 \"x\"",
+                "        Assign@15..16 \"=\"",
+                "        ListComprehension@16..29",
+                "          LeftBracket@16..17 \"[\"",
+                "          ExprName@17..18",
+                "            Identifier@17..18 \"a\"",
+                "          ComprehensionFor@18..25",
+                "            ForKwd@18..21 \"for\"",
+                "            Identifier@21..22 \"a\"",
+                "            InKwd@22..24 \"in\"",
+                "            ExprName@24..25",
+                "              Identifier@24..25 \"b\"",
+                "          ComprehensionIf@25..28",
+                "            IfKwd@25..27 \"if\"",
+                "            ExprName@27..28",
+                "              Identifier@27..28 \"a\"",
+                "          RightBracket@28..29 \"]\"",
+                "        Semicolon@29..30 \";\"",
+                "      RightBrace@30..31 \"}\"",
+            ],
+        )
+    }
+
     #[test]
     fn test_function_call() {
         expect_tree(
@@ -1846,6 +2660,44 @@ mod tests {
         )
     }
 
+    #[test]
+    fn test_function_call_keyword_args() {
+        expect_tree(
+            "func test() { val x = foo(1, b: 2); }",
+            &[
+                "File@0..28",
+                "  Func@0..28",
+                "    FuncKwd@0..4 \"func\"",
+                "    Identifier@4..8 \"test\"",
+                "    ParamList@8..10",
+                "      LeftParen@8..9 \"(\"",
+                "      RightParen@9..10 \")\"",
+                "    Block@10..28",
+                "      LeftBrace@10..11 \"{\"",
+                "      StmtVal@11..27",
+                "        ValKwd@11..14 \"val\"",
+                "        Identifier@14..15 \"x\"",
+                "        Assign@15..16 \"=\"",
+                "        ExprCall@16..26",
+                "          ExprName@16..19",
+                "            Identifier@16..19 \"foo\"",
+                "          ArgList@19..26",
+                "            LeftParen@19..20 \"(\"",
+                "            ExprLiteral@20..21",
+                "              DecIntLiteral@20..21 \"1\"",
+                "            Comma@21..22 \",\"",
+                "            NamedArg@22..25",
+                "              Identifier@22..23 \"b\"",
+                "              Colon@23..24 \":\"",
+                "              ExprLiteral@24..25",
+                "                DecIntLiteral@24..25 \"2\"",
+                "            RightParen@25..26 \")\"",
+                "        Semicolon@26..27 \";\"",
+                "      RightBrace@27..28 \"}\"",
+            ],
+        )
+    }
+
     #[test]
     fn test_chained_postfix() {
         expect_tree(
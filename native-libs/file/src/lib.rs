@@ -19,6 +19,7 @@ use std::{
     fs::{File, OpenOptions},
     io::{Read, Seek, Write},
     rc::Rc,
+    sync::mpsc,
 };
 
 const FILE_MODE_READ: i64 = 1;
@@ -477,6 +478,137 @@ impl BuiltinFunctionImpl for Flush {
     }
 }
 
+// what a background read or write produced: the contents of the file for a
+// read, nothing for a write, or an error message; it is built without
+// touching the VM, so that it can run on a separate thread
+type Outcome = Result<Option<String>, String>;
+
+// runs the operation on a separate thread, and returns a handle for
+// PollAsync to check on it
+fn run_async(op: impl FnOnce() -> Outcome + Send + 'static) -> RuntimeValue {
+    let (sender, receiver) = mpsc::channel::<Outcome>();
+    std::thread::spawn(move || {
+        // the receiver is gone if the Aria program stopped waiting for it
+        let _ = sender.send(op());
+    });
+    RuntimeValue::Opaque(OpaqueValue::new(receiver))
+}
+
+#[derive(Default)]
+struct ReadPathAsync {}
+impl BuiltinFunctionImpl for ReadPathAsync {
+    fn eval(
+        &self,
+        frame: &mut Frame,
+        _: &mut crate::vm::VirtualMachine,
+    ) -> crate::vm::ExecutionResult<RunloopExit> {
+        let _ = VmGlobals::extract_arg(frame, |x: RuntimeValue| x.as_struct().cloned())?;
+        let the_path = VmGlobals::extract_arg(frame, |x: RuntimeValue| x.as_string().cloned())?;
+
+        let the_path = the_path.raw_value().to_owned();
+        frame.stack.push(run_async(move || {
+            std::fs::read_to_string(the_path)
+                .map(Some)
+                .map_err(|e| format!("Failed to read file: {e}"))
+        }));
+        Ok(RunloopExit::Ok(()))
+    }
+
+    fn attrib_byte(&self) -> u8 {
+        FUNC_IS_METHOD | METHOD_ATTRIBUTE_TYPE
+    }
+
+    fn arity(&self) -> haxby_vm::arity::Arity {
+        haxby_vm::arity::Arity::required(2)
+    }
+
+    fn name(&self) -> &str {
+        "_read_path_async"
+    }
+}
+
+#[derive(Default)]
+struct WritePathAsync {}
+impl BuiltinFunctionImpl for WritePathAsync {
+    fn eval(
+        &self,
+        frame: &mut Frame,
+        _: &mut crate::vm::VirtualMachine,
+    ) -> crate::vm::ExecutionResult<RunloopExit> {
+        let _ = VmGlobals::extract_arg(frame, |x: RuntimeValue| x.as_struct().cloned())?;
+        let the_path = VmGlobals::extract_arg(frame, |x: RuntimeValue| x.as_string().cloned())?;
+        let the_text = VmGlobals::extract_arg(frame, |x: RuntimeValue| x.as_string().cloned())?;
+
+        let the_path = the_path.raw_value().to_owned();
+        let the_text = the_text.raw_value().to_owned();
+        frame.stack.push(run_async(move || {
+            std::fs::write(the_path, the_text)
+                .map(|_| None)
+                .map_err(|e| format!("Failed to write file: {e}"))
+        }));
+        Ok(RunloopExit::Ok(()))
+    }
+
+    fn attrib_byte(&self) -> u8 {
+        FUNC_IS_METHOD | METHOD_ATTRIBUTE_TYPE
+    }
+
+    fn arity(&self) -> haxby_vm::arity::Arity {
+        haxby_vm::arity::Arity::required(3)
+    }
+
+    fn name(&self) -> &str {
+        "_write_path_async"
+    }
+}
+
+// returns Maybe::Some(value) once the operation started by _read_path_async
+// or _write_path_async has finished, or Maybe::None while it is still
+// running; a failed operation throws an IOError, as its blocking version would
+#[derive(Default)]
+struct PollAsync {}
+impl BuiltinFunctionImpl for PollAsync {
+    fn eval(
+        &self,
+        frame: &mut Frame,
+        vm: &mut crate::vm::VirtualMachine,
+    ) -> crate::vm::ExecutionResult<RunloopExit> {
+        let the_struct = VmGlobals::extract_arg(frame, |x: RuntimeValue| x.as_struct().cloned())?;
+        let receiver =
+            VmGlobals::extract_arg(frame, |x| x.as_opaque_concrete::<mpsc::Receiver<Outcome>>())?;
+
+        let outcome = match receiver.try_recv() {
+            Ok(outcome) => outcome,
+            Err(mpsc::TryRecvError::Empty) => {
+                frame.stack.push(vm.globals.create_maybe_none()?);
+                return Ok(RunloopExit::Ok(()));
+            }
+            Err(mpsc::TryRecvError::Disconnected) => {
+                Err("file operation finished without a result".to_owned())
+            }
+        };
+        let value = match outcome {
+            Ok(Some(content)) => RuntimeValue::String(content.into()),
+            Ok(None) => vm.globals.create_unit_object()?,
+            Err(message) => return throw_io_error(&the_struct, message, &mut vm.globals),
+        };
+        frame.stack.push(vm.globals.create_maybe_some(value)?);
+        Ok(RunloopExit::Ok(()))
+    }
+
+    fn attrib_byte(&self) -> u8 {
+        FUNC_IS_METHOD | METHOD_ATTRIBUTE_TYPE
+    }
+
+    fn arity(&self) -> haxby_vm::arity::Arity {
+        haxby_vm::arity::Arity::required(2)
+    }
+
+    fn name(&self) -> &str {
+        "_poll_async"
+    }
+}
+
 #[unsafe(no_mangle)]
 #[allow(clippy::not_unsafe_ptr_arg_deref)]
 pub extern "C" fn dylib_haxby_inject(
@@ -508,6 +640,9 @@ pub extern "C" fn dylib_haxby_inject(
             file_struct.insert_builtin::<SetPos>(&mut vm.globals);
             file_struct.insert_builtin::<Flush>(&mut vm.globals);
             file_struct.insert_builtin::<GetSize>(&mut vm.globals);
+            file_struct.insert_builtin::<ReadPathAsync>(&mut vm.globals);
+            file_struct.insert_builtin::<WritePathAsync>(&mut vm.globals);
+            file_struct.insert_builtin::<PollAsync>(&mut vm.globals);
 
             LoadResult::success()
         }
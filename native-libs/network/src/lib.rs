@@ -1,22 +1,36 @@
 // SPDX-License-Identifier: Apache-2.0
+use std::sync::mpsc;
+
 use haxby_opcodes::function_attribs::FUNC_IS_METHOD;
 use haxby_vm::{
+    builtins::VmGlobals,
     error::dylib_load::LoadResult,
+    frame::Frame,
     runtime_module::RuntimeModule,
-    runtime_value::{RuntimeValue, list::List, object::Object},
-    vm::ExecutionResult,
+    runtime_value::{
+        RuntimeValue, function::BuiltinFunctionImpl, list::List, object::Object,
+        opaque::OpaqueValue,
+    },
+    vm::{ExecutionResult, RunloopExit, VirtualMachine},
 };
 
-#[derive(Default)]
-struct RequestGet {}
-impl haxby_vm::runtime_value::function::BuiltinFunctionImpl for RequestGet {
-    fn eval(
-        &self,
-        frame: &mut haxby_vm::frame::Frame,
-        vm: &mut haxby_vm::vm::VirtualMachine,
-    ) -> haxby_vm::vm::ExecutionResult<haxby_vm::vm::RunloopExit> {
-        let this = haxby_vm::builtins::VmGlobals::extract_arg(frame, |x| x.as_object().cloned())?;
-        let headers = haxby_vm::builtins::VmGlobals::extract_arg(frame, |x| x.as_list().cloned())?;
+// what a request produced: the status code, headers and content of the
+// response, or an error message; it is built without touching the VM, so that
+// async requests can run on a separate thread
+type Outcome = Result<(u16, Vec<(String, String)>, String), String>;
+
+struct RequestParams {
+    url: String,
+    timeout: f64,
+    headers: Vec<(String, String)>,
+}
+
+impl RequestParams {
+    fn new(
+        this: &Object,
+        headers: &List,
+        vm: &mut VirtualMachine,
+    ) -> ExecutionResult<RequestParams> {
         let url_sym = vm
             .globals
             .intern_symbol("url")
@@ -25,30 +39,6 @@ impl haxby_vm::runtime_value::function::BuiltinFunctionImpl for RequestGet {
             .globals
             .intern_symbol("timeout")
             .expect("too many symbols interned");
-        let response_sym = vm
-            .globals
-            .intern_symbol("Response")
-            .expect("too many symbols interned");
-        let error_sym = vm
-            .globals
-            .intern_symbol("Error")
-            .expect("too many symbols interned");
-        let status_code_sym = vm
-            .globals
-            .intern_symbol("status_code")
-            .expect("too many symbols interned");
-        let headers_sym = vm
-            .globals
-            .intern_symbol("headers")
-            .expect("too many symbols interned");
-        let content_sym = vm
-            .globals
-            .intern_symbol("content")
-            .expect("too many symbols interned");
-        let msg_sym = vm
-            .globals
-            .intern_symbol("msg")
-            .expect("too many symbols interned");
 
         let this_url = this.extract_field(&vm.globals, url_sym, |field: RuntimeValue| {
             field.as_string().cloned()
@@ -57,21 +47,8 @@ impl haxby_vm::runtime_value::function::BuiltinFunctionImpl for RequestGet {
             this.extract_field(&vm.globals, timeout_sym, |field: RuntimeValue| {
                 field.as_float().cloned()
             })?;
-        let as_struct = this.get_struct();
-        let this_response =
-            as_struct.extract_field(&vm.globals, response_sym, |field: RuntimeValue| {
-                field.as_struct().cloned()
-            })?;
-        let this_error =
-            as_struct.extract_field(&vm.globals, error_sym, |field: RuntimeValue| {
-                field.as_struct().cloned()
-            })?;
 
-        let mut client = reqwest::blocking::Client::new()
-            .get(this_url.raw_value())
-            .timeout(std::time::Duration::from_secs_f64(
-                *this_timeout.raw_value(),
-            ));
+        let mut header_pairs = vec![];
         for i in 0..headers.len() {
             let header = headers.get_at(i).unwrap();
             if let Some(list) = header.as_list()
@@ -80,74 +57,152 @@ impl haxby_vm::runtime_value::function::BuiltinFunctionImpl for RequestGet {
                 let key = list.get_at(0).unwrap();
                 let value = list.get_at(1).unwrap();
                 if let (Some(key), Some(value)) = (key.as_string(), value.as_string()) {
-                    client = client.header(key.raw_value(), value.raw_value());
+                    header_pairs.push((key.raw_value().to_owned(), value.raw_value().to_owned()));
                 }
             }
         }
 
-        match client.send() {
-            Ok(r) => {
-                let response_obj = RuntimeValue::Object(Object::new(&this_response));
-                let _ = response_obj.write_attribute(
-                    status_code_sym,
-                    haxby_vm::runtime_value::RuntimeValue::Integer(
-                        (r.status().as_u16() as i64).into(),
-                    ),
-                    &mut vm.globals,
-                );
-                let header_list = List::from(&[]);
-                for header in r.headers() {
-                    let header_kvp = List::from(&[
-                        RuntimeValue::String(header.0.as_str().into()),
-                        RuntimeValue::String(header.1.to_str().unwrap_or("<err>").into()),
-                    ]);
-                    header_list.append(RuntimeValue::List(header_kvp));
-                }
-                let _ = response_obj.write_attribute(
-                    headers_sym,
-                    RuntimeValue::List(header_list),
-                    &mut vm.globals,
-                );
-                match r.text() {
-                    Ok(content) => {
-                        let _ = response_obj.write_attribute(
-                            content_sym,
-                            RuntimeValue::String(content.into()),
-                            &mut vm.globals,
-                        );
-                    }
-                    _ => {
-                        let error_obj = RuntimeValue::Object(Object::new(&this_error));
-                        let _ = error_obj.write_attribute(
-                            msg_sym,
-                            RuntimeValue::String("content is not a valid String".into()),
-                            &mut vm.globals,
-                        );
-                        let result_err = vm.globals.create_result_err(error_obj)?;
-                        frame.stack.push(result_err);
-                        return ExecutionResult::Ok(haxby_vm::vm::RunloopExit::Ok(()));
-                    }
-                }
+        Ok(RequestParams {
+            url: this_url.raw_value().to_owned(),
+            timeout: *this_timeout.raw_value(),
+            headers: header_pairs,
+        })
+    }
+
+    // a None payload sends a GET, otherwise a POST with the payload as body
+    fn send(self, payload: Option<String>) -> Outcome {
+        let client = reqwest::blocking::Client::new();
+        let mut client = match payload {
+            Some(payload) => client.post(&self.url).body(payload),
+            None => client.get(&self.url),
+        }
+        .timeout(std::time::Duration::from_secs_f64(self.timeout));
+        for (key, value) in &self.headers {
+            client = client.header(key, value);
+        }
 
-                let result_ok = vm.globals.create_result_ok(response_obj.clone())?;
+        let r = client.send().map_err(|e| e.to_string())?;
+        let status_code = r.status().as_u16();
+        let headers = r
+            .headers()
+            .iter()
+            .map(|header| {
+                (
+                    header.0.as_str().to_owned(),
+                    header.1.to_str().unwrap_or("<err>").to_owned(),
+                )
+            })
+            .collect();
+        let content = r
+            .text()
+            .map_err(|_| "content is not a valid String".to_owned())?;
+        Ok((status_code, headers, content))
+    }
+}
 
-                frame.stack.push(result_ok);
-                Ok(haxby_vm::vm::RunloopExit::Ok(()))
-            }
-            Err(e) => {
-                let error_obj = RuntimeValue::Object(Object::new(&this_error));
-                let _ = error_obj.write_attribute(
-                    msg_sym,
-                    RuntimeValue::String(e.to_string().into()),
-                    &mut vm.globals,
-                );
-                let result_err = vm.globals.create_result_err(error_obj)?;
-
-                frame.stack.push(result_err);
-                ExecutionResult::Ok(haxby_vm::vm::RunloopExit::Ok(()))
+// turns the outcome into a Result of Request.Response or Request.Error
+fn outcome_to_result(
+    this: &Object,
+    outcome: Outcome,
+    vm: &mut VirtualMachine,
+) -> ExecutionResult<RuntimeValue> {
+    let response_sym = vm
+        .globals
+        .intern_symbol("Response")
+        .expect("too many symbols interned");
+    let error_sym = vm
+        .globals
+        .intern_symbol("Error")
+        .expect("too many symbols interned");
+    let status_code_sym = vm
+        .globals
+        .intern_symbol("status_code")
+        .expect("too many symbols interned");
+    let headers_sym = vm
+        .globals
+        .intern_symbol("headers")
+        .expect("too many symbols interned");
+    let content_sym = vm
+        .globals
+        .intern_symbol("content")
+        .expect("too many symbols interned");
+    let msg_sym = vm
+        .globals
+        .intern_symbol("msg")
+        .expect("too many symbols interned");
+
+    let as_struct = this.get_struct();
+    match outcome {
+        Ok((status_code, headers, content)) => {
+            let this_response =
+                as_struct.extract_field(&vm.globals, response_sym, |field: RuntimeValue| {
+                    field.as_struct().cloned()
+                })?;
+            let response_obj = RuntimeValue::Object(Object::new(&this_response));
+            let _ = response_obj.write_attribute(
+                status_code_sym,
+                RuntimeValue::Integer((status_code as i64).into()),
+                &mut vm.globals,
+            );
+            let header_list = List::from(&[]);
+            for (key, value) in headers {
+                let header_kvp = List::from(&[
+                    RuntimeValue::String(key.into()),
+                    RuntimeValue::String(value.into()),
+                ]);
+                header_list.append(RuntimeValue::List(header_kvp));
             }
+            let _ = response_obj.write_attribute(
+                headers_sym,
+                RuntimeValue::List(header_list),
+                &mut vm.globals,
+            );
+            let _ = response_obj.write_attribute(
+                content_sym,
+                RuntimeValue::String(content.into()),
+                &mut vm.globals,
+            );
+            Ok(vm.globals.create_result_ok(response_obj)?)
+        }
+        Err(msg) => {
+            let this_error =
+                as_struct.extract_field(&vm.globals, error_sym, |field: RuntimeValue| {
+                    field.as_struct().cloned()
+                })?;
+            let error_obj = RuntimeValue::Object(Object::new(&this_error));
+            let _ = error_obj.write_attribute(
+                msg_sym,
+                RuntimeValue::String(msg.into()),
+                &mut vm.globals,
+            );
+            Ok(vm.globals.create_result_err(error_obj)?)
         }
     }
+}
+
+// runs the request on a separate thread, and returns a handle for
+// RequestPollAsync to check on it
+fn send_async(params: RequestParams, payload: Option<String>) -> RuntimeValue {
+    let (sender, receiver) = mpsc::channel::<Outcome>();
+    std::thread::spawn(move || {
+        // the receiver is gone if the Aria program stopped waiting for it
+        let _ = sender.send(params.send(payload));
+    });
+    RuntimeValue::Opaque(OpaqueValue::new(receiver))
+}
+
+#[derive(Default)]
+struct RequestGet {}
+impl BuiltinFunctionImpl for RequestGet {
+    fn eval(&self, frame: &mut Frame, vm: &mut VirtualMachine) -> ExecutionResult<RunloopExit> {
+        let this = VmGlobals::extract_arg(frame, |x| x.as_object().cloned())?;
+        let headers = VmGlobals::extract_arg(frame, |x| x.as_list().cloned())?;
+
+        let params = RequestParams::new(&this, &headers, vm)?;
+        let result = outcome_to_result(&this, params.send(None), vm)?;
+        frame.stack.push(result);
+        Ok(RunloopExit::Ok(()))
+    }
 
     fn attrib_byte(&self) -> u8 {
         FUNC_IS_METHOD
@@ -164,149 +219,108 @@ impl haxby_vm::runtime_value::function::BuiltinFunctionImpl for RequestGet {
 
 #[derive(Default)]
 struct RequestPost {}
-impl haxby_vm::runtime_value::function::BuiltinFunctionImpl for RequestPost {
-    fn eval(
-        &self,
-        frame: &mut haxby_vm::frame::Frame,
-        vm: &mut haxby_vm::vm::VirtualMachine,
-    ) -> haxby_vm::vm::ExecutionResult<haxby_vm::vm::RunloopExit> {
-        let this = haxby_vm::builtins::VmGlobals::extract_arg(frame, |x| x.as_object().cloned())?;
-        let headers = haxby_vm::builtins::VmGlobals::extract_arg(frame, |x| x.as_list().cloned())?;
-        let payload =
-            haxby_vm::builtins::VmGlobals::extract_arg(frame, |x| x.as_string().cloned())?;
+impl BuiltinFunctionImpl for RequestPost {
+    fn eval(&self, frame: &mut Frame, vm: &mut VirtualMachine) -> ExecutionResult<RunloopExit> {
+        let this = VmGlobals::extract_arg(frame, |x| x.as_object().cloned())?;
+        let headers = VmGlobals::extract_arg(frame, |x| x.as_list().cloned())?;
+        let payload = VmGlobals::extract_arg(frame, |x| x.as_string().cloned())?;
 
-        let url_sym = vm
-            .globals
-            .intern_symbol("url")
-            .expect("too many symbols interned");
-        let timeout_sym = vm
-            .globals
-            .intern_symbol("timeout")
-            .expect("too many symbols interned");
-        let response_sym = vm
-            .globals
-            .intern_symbol("Response")
-            .expect("too many symbols interned");
-        let error_sym = vm
-            .globals
-            .intern_symbol("Error")
-            .expect("too many symbols interned");
-        let status_code_sym = vm
-            .globals
-            .intern_symbol("status_code")
-            .expect("too many symbols interned");
-        let headers_sym = vm
-            .globals
-            .intern_symbol("headers")
-            .expect("too many symbols interned");
-        let content_sym = vm
-            .globals
-            .intern_symbol("content")
-            .expect("too many symbols interned");
-        let msg_sym = vm
-            .globals
-            .intern_symbol("msg")
-            .expect("too many symbols interned");
+        let params = RequestParams::new(&this, &headers, vm)?;
+        let outcome = params.send(Some(payload.raw_value().to_owned()));
+        let result = outcome_to_result(&this, outcome, vm)?;
+        frame.stack.push(result);
+        Ok(RunloopExit::Ok(()))
+    }
 
-        let this_url = this.extract_field(&vm.globals, url_sym, |field: RuntimeValue| {
-            field.as_string().cloned()
-        })?;
-        let this_timeout =
-            this.extract_field(&vm.globals, timeout_sym, |field: RuntimeValue| {
-                field.as_float().cloned()
-            })?;
-        let as_struct = this.get_struct();
-        let this_response =
-            as_struct.extract_field(&vm.globals, response_sym, |field: RuntimeValue| {
-                field.as_struct().cloned()
-            })?;
-        let this_error =
-            as_struct.extract_field(&vm.globals, error_sym, |field: RuntimeValue| {
-                field.as_struct().cloned()
-            })?;
+    fn attrib_byte(&self) -> u8 {
+        FUNC_IS_METHOD
+    }
 
-        let mut client = reqwest::blocking::Client::new()
-            .post(this_url.raw_value())
-            .body(payload.raw_value().to_owned())
-            .timeout(std::time::Duration::from_secs_f64(
-                *this_timeout.raw_value(),
-            ));
-        for i in 0..headers.len() {
-            let header = headers.get_at(i).unwrap();
-            if let Some(list) = header.as_list()
-                && list.len() == 2
-            {
-                let key = list.get_at(0).unwrap();
-                let value = list.get_at(1).unwrap();
-                if let (Some(key), Some(value)) = (key.as_string(), value.as_string()) {
-                    client = client.header(key.raw_value(), value.raw_value());
-                }
-            }
-        }
+    fn arity(&self) -> haxby_vm::arity::Arity {
+        haxby_vm::arity::Arity::required(3)
+    }
 
-        match client.send() {
-            Ok(r) => {
-                let response_obj = RuntimeValue::Object(Object::new(&this_response));
-                let _ = response_obj.write_attribute(
-                    status_code_sym,
-                    haxby_vm::runtime_value::RuntimeValue::Integer(
-                        (r.status().as_u16() as i64).into(),
-                    ),
-                    &mut vm.globals,
-                );
-                let header_list = List::from(&[]);
-                for header in r.headers() {
-                    let header_kvp = List::from(&[
-                        RuntimeValue::String(header.0.as_str().into()),
-                        RuntimeValue::String(header.1.to_str().unwrap_or("<err>").into()),
-                    ]);
-                    header_list.append(RuntimeValue::List(header_kvp));
-                }
-                let _ = response_obj.write_attribute(
-                    headers_sym,
-                    RuntimeValue::List(header_list),
-                    &mut vm.globals,
-                );
-                match r.text() {
-                    Ok(content) => {
-                        let _ = response_obj.write_attribute(
-                            content_sym,
-                            RuntimeValue::String(content.into()),
-                            &mut vm.globals,
-                        );
-                    }
-                    _ => {
-                        let error_obj = RuntimeValue::Object(Object::new(&this_error));
-                        let _ = error_obj.write_attribute(
-                            msg_sym,
-                            RuntimeValue::String("content is not a valid String".into()),
-                            &mut vm.globals,
-                        );
-                        let result_err = vm.globals.create_result_err(error_obj)?;
-
-                        frame.stack.push(result_err);
-                        return ExecutionResult::Ok(haxby_vm::vm::RunloopExit::Ok(()));
-                    }
-                }
+    fn name(&self) -> &str {
+        "_post"
+    }
+}
+
+#[derive(Default)]
+struct RequestGetAsync {}
+impl BuiltinFunctionImpl for RequestGetAsync {
+    fn eval(&self, frame: &mut Frame, vm: &mut VirtualMachine) -> ExecutionResult<RunloopExit> {
+        let this = VmGlobals::extract_arg(frame, |x| x.as_object().cloned())?;
+        let headers = VmGlobals::extract_arg(frame, |x| x.as_list().cloned())?;
+
+        let params = RequestParams::new(&this, &headers, vm)?;
+        frame.stack.push(send_async(params, None));
+        Ok(RunloopExit::Ok(()))
+    }
+
+    fn attrib_byte(&self) -> u8 {
+        FUNC_IS_METHOD
+    }
+
+    fn arity(&self) -> haxby_vm::arity::Arity {
+        haxby_vm::arity::Arity::required(2)
+    }
+
+    fn name(&self) -> &str {
+        "_get_async"
+    }
+}
+
+#[derive(Default)]
+struct RequestPostAsync {}
+impl BuiltinFunctionImpl for RequestPostAsync {
+    fn eval(&self, frame: &mut Frame, vm: &mut VirtualMachine) -> ExecutionResult<RunloopExit> {
+        let this = VmGlobals::extract_arg(frame, |x| x.as_object().cloned())?;
+        let headers = VmGlobals::extract_arg(frame, |x| x.as_list().cloned())?;
+        let payload = VmGlobals::extract_arg(frame, |x| x.as_string().cloned())?;
+
+        let params = RequestParams::new(&this, &headers, vm)?;
+        frame
+            .stack
+            .push(send_async(params, Some(payload.raw_value().to_owned())));
+        Ok(RunloopExit::Ok(()))
+    }
+
+    fn attrib_byte(&self) -> u8 {
+        FUNC_IS_METHOD
+    }
+
+    fn arity(&self) -> haxby_vm::arity::Arity {
+        haxby_vm::arity::Arity::required(3)
+    }
+
+    fn name(&self) -> &str {
+        "_post_async"
+    }
+}
 
-                let result_ok = vm.globals.create_result_ok(response_obj.clone())?;
+// returns Maybe::Some(Result) once the request started by _get_async or
+// _post_async has finished, or Maybe::None while it is still running
+#[derive(Default)]
+struct RequestPollAsync {}
+impl BuiltinFunctionImpl for RequestPollAsync {
+    fn eval(&self, frame: &mut Frame, vm: &mut VirtualMachine) -> ExecutionResult<RunloopExit> {
+        let this = VmGlobals::extract_arg(frame, |x| x.as_object().cloned())?;
+        let receiver =
+            VmGlobals::extract_arg(frame, |x| x.as_opaque_concrete::<mpsc::Receiver<Outcome>>())?;
 
-                frame.stack.push(result_ok);
-                Ok(haxby_vm::vm::RunloopExit::Ok(()))
+        let outcome = match receiver.try_recv() {
+            Ok(outcome) => outcome,
+            Err(mpsc::TryRecvError::Empty) => {
+                frame.stack.push(vm.globals.create_maybe_none()?);
+                return Ok(RunloopExit::Ok(()));
             }
-            Err(e) => {
-                let error_obj = RuntimeValue::Object(Object::new(&this_error));
-                let _ = error_obj.write_attribute(
-                    msg_sym,
-                    RuntimeValue::String(e.to_string().into()),
-                    &mut vm.globals,
-                );
-                let result_err = vm.globals.create_result_err(error_obj)?;
-
-                frame.stack.push(result_err);
-                ExecutionResult::Ok(haxby_vm::vm::RunloopExit::Ok(()))
+            Err(mpsc::TryRecvError::Disconnected) => {
+                Err("request finished without a response".to_owned())
             }
-        }
+        };
+        let result = outcome_to_result(&this, outcome, vm)?;
+        frame.stack.push(vm.globals.create_maybe_some(result)?);
+        Ok(RunloopExit::Ok(()))
     }
 
     fn attrib_byte(&self) -> u8 {
@@ -314,11 +328,11 @@ impl haxby_vm::runtime_value::function::BuiltinFunctionImpl for RequestPost {
     }
 
     fn arity(&self) -> haxby_vm::arity::Arity {
-        haxby_vm::arity::Arity::required(3)
+        haxby_vm::arity::Arity::required(2)
     }
 
     fn name(&self) -> &str {
-        "_post"
+        "_poll_async"
     }
 }
 
@@ -346,6 +360,9 @@ pub extern "C" fn dylib_haxby_inject(
 
             request.insert_builtin::<RequestGet>(&mut vm.globals);
             request.insert_builtin::<RequestPost>(&mut vm.globals);
+            request.insert_builtin::<RequestGetAsync>(&mut vm.globals);
+            request.insert_builtin::<RequestPostAsync>(&mut vm.globals);
+            request.insert_builtin::<RequestPollAsync>(&mut vm.globals);
 
             LoadResult::success()
         }
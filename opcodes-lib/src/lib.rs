@@ -51,31 +51,44 @@ pub const OPCODE_JUMP_FALSE: u8 = 64;
 pub const OPCODE_JUMP_CONDITIONALLY: u8 = 65;
 pub const OPCODE_JUMP_IF_ARG_SUPPLIED: u8 = 66;
 // ...
+pub const OPCODE_AWAIT: u8 = 71;
 pub const OPCODE_TRY_ENTER: u8 = 72;
 pub const OPCODE_TRY_EXIT: u8 = 73;
 pub const OPCODE_THROW: u8 = 74;
 pub const OPCODE_CALL: u8 = 75;
 pub const OPCODE_RETURN: u8 = 76;
 pub const OPCODE_RETURN_UNIT: u8 = 77;
-// ...
+pub const OPCODE_YIELD: u8 = 78;
+pub const OPCODE_CALL_NAMED: u8 = 79;
 pub const OPCODE_BUILD_LIST: u8 = 80;
 pub const OPCODE_BUILD_FUNCTION: u8 = 81;
 pub const OPCODE_STORE_UPLEVEL: u8 = 82;
 pub const OPCODE_BUILD_STRUCT: u8 = 83;
 pub const OPCODE_BUILD_ENUM: u8 = 84;
 pub const OPCODE_BUILD_MIXIN: u8 = 85;
+pub const OPCODE_BUILD_MAP: u8 = 86;
 pub const OPCODE_BIND_CASE: u8 = 87;
 pub const OPCODE_INCLUDE_MIXIN: u8 = 88;
 pub const OPCODE_NEW_ENUM_VAL: u8 = 89;
 pub const OPCODE_ENUM_CHECK_IS_CASE: u8 = 90;
 pub const OPCODE_ENUM_TRY_EXTRACT_PAYLOAD: u8 = 91;
 pub const OPCODE_TRY_UNWRAP_PROTOCOL: u8 = 92;
-// ..
+pub const OPCODE_BUILD_STRING: u8 = 93;
+pub const OPCODE_LIST_CHECK_LEN: u8 = 94;
+pub const OPCODE_LIST_TAIL: u8 = 95;
+pub const OPCODE_READ_SLICE: u8 = 96;
+pub const OPCODE_REQUIRE_MEMBER: u8 = 97;
+pub const OPCODE_CHECK_MIXIN_CONTRACTS: u8 = 98;
+pub const OPCODE_BIND_GETTER: u8 = 99;
 pub const OPCODE_READ_ATTRIBUTE_SYMBOL: u8 = 100;
 pub const OPCODE_WRITE_ATTRIBUTE_SYMBOL: u8 = 101;
 pub const OPCODE_NEW_ENUM_VAL_SYMBOL: u8 = 102;
 pub const OPCODE_ENUM_CHECK_IS_CASE_SYMBOL: u8 = 103;
 pub const OPCODE_BIND_CASE_SYMBOL: u8 = 104;
+pub const OPCODE_BIND_SETTER: u8 = 105;
+pub const OPCODE_TAIL_CALL: u8 = 106;
+pub const OPCODE_TRY_READ_ATTRIBUTE: u8 = 107;
+pub const OPCODE_TRY_READ_ATTRIBUTE_SYMBOL: u8 = 108;
 // ...
 pub const OPCODE_IMPORT: u8 = 250;
 pub const OPCODE_LIFT_MODULE: u8 = 251;
@@ -88,6 +101,8 @@ pub mod function_attribs {
     pub const FUNC_IS_METHOD:            u8 = 1_u8 << 0;
     pub const METHOD_ATTRIBUTE_TYPE:     u8 = 1_u8 << 1;
     pub const FUNC_ACCEPTS_VARARG:       u8 = 1_u8 << 2;
+    pub const FUNC_IS_GENERATOR:         u8 = 1_u8 << 3;
+    pub const FUNC_IS_ASYNC:             u8 = 1_u8 << 4;
 }
 
 #[allow(unused_imports)]
@@ -138,6 +153,7 @@ pub enum BuiltinTypeId {
     Float = 10,
     List = 11,
     Type = 12,
+    Map = 13,
 }
 
 impl BuiltinTypeId {
@@ -146,7 +162,7 @@ impl BuiltinTypeId {
     }
 
     pub fn last() -> Self {
-        BuiltinTypeId::Type
+        BuiltinTypeId::Map
     }
 
     pub fn name(&self) -> &'static str {
@@ -164,6 +180,7 @@ impl BuiltinTypeId {
             BuiltinTypeId::Unit => "Unit",
             BuiltinTypeId::Result => "Result",
             BuiltinTypeId::Type => "Type",
+            BuiltinTypeId::Map => "Map",
         }
     }
 }
@@ -186,6 +203,7 @@ impl TryFrom<u8> for BuiltinTypeId {
             10 => Ok(BuiltinTypeId::Float),
             11 => Ok(BuiltinTypeId::List),
             12 => Ok(BuiltinTypeId::Type),
+            13 => Ok(BuiltinTypeId::Map),
             _ => Err(()),
         }
     }
@@ -199,6 +217,24 @@ pub mod enum_case_attribs {
 #[allow(unused_imports)]
 use enum_case_attribs::*;
 
+#[rustfmt::skip]
+pub mod list_check_len_attribs {
+    pub const LEN_AT_LEAST:                u8 = 1_u8 << 0;
+}
+
+#[allow(unused_imports)]
+use list_check_len_attribs::*;
+
+#[rustfmt::skip]
+pub mod read_slice_attribs {
+    pub const HAS_START:                   u8 = 1_u8 << 0;
+    pub const HAS_STOP:                    u8 = 1_u8 << 1;
+    pub const HAS_STEP:                    u8 = 1_u8 << 2;
+}
+
+#[allow(unused_imports)]
+use read_slice_attribs::*;
+
 #[rustfmt::skip]
 pub mod try_unwrap_protocol_mode {
     pub const PROPAGATE_ERROR:  u8 = 1;
@@ -245,9 +281,12 @@ pub enum Opcode {
     TypedefNamed(u16),
     ReadIndex(u8),
     WriteIndex(u8),
+    ReadSlice(u8),
     ReadAttribute(u16),
     WriteAttribute(u16),
     ReadAttributeSymbol(u32),
+    TryReadAttribute(u16),
+    TryReadAttributeSymbol(u32),
     WriteAttributeSymbol(u32),
     ReadUplevel(u8),
     LogicalAnd,
@@ -261,8 +300,12 @@ pub enum Opcode {
     JumpConditionally(u16, u16),
     JumpIfArgSupplied(u8, u16),
     Call(u8),
+    CallNamed(u8, u8),
+    TailCall(u8),
     Return,
     ReturnUnit,
+    Yield,
+    Await,
     TryEnter(u16),
     TryExit,
     Throw,
@@ -272,9 +315,17 @@ pub enum Opcode {
     BuildStruct,
     BuildEnum,
     BuildMixin,
+    BuildMap(u32),
+    BuildString(u32),
+    ListCheckLen(u8, u32),
+    ListTail(u32),
     BindCase(u8, u16),
     BindCaseSymbol(u8, u32),
     IncludeMixin,
+    RequireMember,
+    CheckMixinContracts,
+    BindGetter,
+    BindSetter,
     NewEnumVal(u8, u16),
     NewEnumValSymbol(u8, u32),
     EnumCheckIsCase(u16),
@@ -322,9 +373,12 @@ impl std::fmt::Display for Opcode {
             Self::TypedefNamed(arg0) => write!(f, "TYPEDEF_NAMED @{arg0}"),
             Self::ReadIndex(arg0) => write!(f, "READ_INDEX {arg0}"),
             Self::WriteIndex(arg0) => write!(f, "WRITE_INDEX {arg0}"),
+            Self::ReadSlice(arg0) => write!(f, "READ_SLICE {arg0}"),
             Self::ReadAttribute(arg0) => write!(f, "READ_ATTRIB @{arg0}"),
             Self::WriteAttribute(arg0) => write!(f, "WRITE_ATTRIB @{arg0}"),
             Self::ReadAttributeSymbol(arg0) => write!(f, "READ_ATTRIB_SYM #{arg0}"),
+            Self::TryReadAttribute(arg0) => write!(f, "TRY_READ_ATTRIB @{arg0}"),
+            Self::TryReadAttributeSymbol(arg0) => write!(f, "TRY_READ_ATTRIB_SYM #{arg0}"),
             Self::WriteAttributeSymbol(arg0) => write!(f, "WRITE_ATTRIB_SYM #{arg0}"),
             Self::ReadUplevel(arg0) => write!(f, "READ_UPLEVEL {arg0}"),
             Self::LogicalAnd => write!(f, "ANDL"),
@@ -342,8 +396,12 @@ impl std::fmt::Display for Opcode {
             Self::JumpConditionally(arg0, arg1) => write!(f, "JUMP_CONDITIONALLY {arg0} {arg1}"),
             Self::JumpIfArgSupplied(arg0, arg1) => write!(f, "JUMP_IF_ARG_SUPPLIED {arg0} {arg1}"),
             Self::Call(arg0) => write!(f, "CALL {arg0}"),
+            Self::CallNamed(arg0, arg1) => write!(f, "CALL_NAMED {arg0} {arg1}"),
+            Self::TailCall(arg0) => write!(f, "TAIL_CALL {arg0}"),
             Self::Return => write!(f, "RETURN"),
             Self::ReturnUnit => write!(f, "RETURN_UNIT"),
+            Self::Yield => write!(f, "YIELD"),
+            Self::Await => write!(f, "AWAIT"),
             Self::TryEnter(arg0) => write!(f, "ENTER_TRY {arg0}"),
             Self::TryExit => write!(f, "EXIT_TRY"),
             Self::Throw => write!(f, "THROW"),
@@ -353,9 +411,17 @@ impl std::fmt::Display for Opcode {
             Self::BuildStruct => write!(f, "BUILD_STRUCT"),
             Self::BuildEnum => write!(f, "BUILD_ENUM"),
             Self::BuildMixin => write!(f, "BUILD_MIXIN"),
+            Self::BuildMap(arg0) => write!(f, "BUILD_MAP {arg0}"),
+            Self::BuildString(arg0) => write!(f, "BUILD_STRING {arg0}"),
+            Self::ListCheckLen(arg0, arg1) => write!(f, "LIST_CHECK_LEN {arg0} {arg1}"),
+            Self::ListTail(arg0) => write!(f, "LIST_TAIL {arg0}"),
             Self::BindCase(arg0, arg1) => write!(f, "BIND_CASE {arg0} @{arg1}"),
             Self::BindCaseSymbol(arg0, arg1) => write!(f, "BIND_CASE_SYM {arg0} #{arg1}"),
             Self::IncludeMixin => write!(f, "INCLUDE_MIXIN"),
+            Self::RequireMember => write!(f, "REQUIRE_MEMBER"),
+            Self::CheckMixinContracts => write!(f, "CHECK_MIXIN_CONTRACTS"),
+            Self::BindGetter => write!(f, "BIND_GETTER"),
+            Self::BindSetter => write!(f, "BIND_SETTER"),
             Self::NewEnumVal(arg0, arg1) => write!(f, "NEW_ENUM_VAL {arg0} @{arg1}"),
             Self::NewEnumValSymbol(arg0, arg1) => write!(f, "NEW_ENUM_VAL_SYM {arg0} #{arg1}"),
             Self::EnumCheckIsCase(arg0) => write!(f, "ENUM_CHECK_IS_CASE @{arg0}"),
@@ -8,6 +8,7 @@ use crate::grammar::{HaxbyParser, Rule};
 mod derive;
 mod nodes;
 pub mod prettyprint;
+mod recovery;
 
 #[derive(Debug, Clone, PartialEq, Eq, Copy)]
 pub struct Location {
@@ -19,6 +20,9 @@ pub struct Location {
 pub struct SourceBuffer {
     pub content: Rc<String>,
     pub name: String,
+    // set when the content was left out (e.g. in a module bundle), so that
+    // positions can still be turned into line numbers
+    pub line_starts: Option<Rc<Vec<usize>>>,
 }
 
 impl AsRef<str> for SourceBuffer {
@@ -32,6 +36,7 @@ impl SourceBuffer {
         Self {
             content: Rc::new(input.to_owned()),
             name: String::from("<stdin>"),
+            line_starts: None,
         }
     }
 
@@ -39,6 +44,7 @@ impl SourceBuffer {
         Self {
             content: Rc::new(input.to_owned()),
             name: String::from(name),
+            line_starts: None,
         }
     }
 
@@ -51,6 +57,7 @@ impl SourceBuffer {
         Ok(Self {
             content: Rc::new(content),
             name: path,
+            line_starts: None,
         })
     }
 
@@ -67,9 +74,32 @@ impl SourceBuffer {
         Ok(Self {
             content: Rc::new(content),
             name: path,
+            line_starts: None,
         })
     }
 
+    pub fn without_content(name: &str, line_starts: Vec<usize>) -> Self {
+        Self {
+            content: Rc::new(String::new()),
+            name: String::from(name),
+            line_starts: Some(Rc::new(line_starts)),
+        }
+    }
+
+    pub fn has_content(&self) -> bool {
+        self.line_starts.is_none()
+    }
+
+    // the offset at which each line begins, starting with 0 for the first one
+    pub fn line_starts(&self) -> Vec<usize> {
+        match &self.line_starts {
+            Some(line_starts) => line_starts.as_ref().clone(),
+            None => std::iter::once(0)
+                .chain(self.content.match_indices('\n').map(|(idx, _)| idx + 1))
+                .collect(),
+        }
+    }
+
     pub fn as_str(&self) -> String {
         self.content.as_ref().to_owned()
     }
@@ -101,7 +131,12 @@ impl SourceBuffer {
     }
 
     pub fn line_index_for_position(&self, pos: usize) -> usize {
-        self.content[..pos].chars().filter(|&c| c == '\n').count()
+        match &self.line_starts {
+            Some(line_starts) => line_starts
+                .partition_point(|&start| start <= pos)
+                .saturating_sub(1),
+            None => self.content[..pos].chars().filter(|&c| c == '\n').count(),
+        }
     }
 
     pub fn pointer_to_whole_buffer(&self) -> SourcePointer {
@@ -186,6 +221,19 @@ pub struct StringLiteral {
     pub value: String,
 }
 
+#[allow(clippy::large_enum_variant)]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FormatStringPart {
+    Text(String),
+    Expression(Expression),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FormatStringLiteral {
+    pub loc: SourcePointer,
+    pub parts: Vec<FormatStringPart>,
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Identifier {
     pub loc: SourcePointer,
@@ -228,6 +276,61 @@ pub struct ListLiteral {
     pub items: ExpressionList,
 }
 
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MapLiteralEntry {
+    pub loc: SourcePointer,
+    pub key: Expression,
+    pub value: Expression,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MapLiteral {
+    pub loc: SourcePointer,
+    pub entries: Vec<MapLiteralEntry>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ComprehensionFor {
+    pub loc: SourcePointer,
+    pub pattern: Pattern,
+    pub expr: Expression,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ComprehensionIf {
+    pub loc: SourcePointer,
+    pub cond: Expression,
+}
+
+#[allow(clippy::large_enum_variant)]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ComprehensionClause {
+    ComprehensionFor(ComprehensionFor),
+    ComprehensionIf(ComprehensionIf),
+}
+
+// [expr for x in iter if cond]; the first clause is always a for
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ListComprehension {
+    pub loc: SourcePointer,
+    pub expr: Box<Expression>,
+    pub clauses: Vec<ComprehensionClause>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AwaitExpression {
+    pub loc: SourcePointer,
+    pub val: Box<UnaryOperation>,
+}
+
+// (expr for x in iter if cond), which produces its values lazily
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GeneratorExpression {
+    pub loc: SourcePointer,
+    pub expr: Box<Expression>,
+    pub clauses: Vec<ComprehensionClause>,
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct ParenExpression {
     pub loc: SourcePointer,
@@ -245,23 +348,33 @@ impl From<&Expression> for ParenExpression {
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum Primary {
+    AwaitExpression(AwaitExpression),
     IntLiteral(IntLiteral),
     FloatLiteral(FloatLiteral),
     Identifier(Identifier),
     ListLiteral(ListLiteral),
+    ListComprehension(ListComprehension),
+    MapLiteral(MapLiteral),
     StringLiteral(StringLiteral),
+    FormatStringLiteral(FormatStringLiteral),
     ParenExpression(ParenExpression),
+    GeneratorExpression(GeneratorExpression),
 }
 
 impl Primary {
     pub fn loc(&self) -> &SourcePointer {
         match self {
+            Self::AwaitExpression(ae) => &ae.loc,
             Self::IntLiteral(il) => &il.loc,
             Self::FloatLiteral(fp) => &fp.loc,
             Self::Identifier(id) => &id.loc,
             Self::ListLiteral(ll) => &ll.loc,
+            Self::ListComprehension(lc) => &lc.loc,
+            Self::MapLiteral(ml) => &ml.loc,
             Self::StringLiteral(sl) => &sl.loc,
+            Self::FormatStringLiteral(fl) => &fl.loc,
             Self::ParenExpression(pe) => &pe.loc,
+            Self::GeneratorExpression(ge) => &ge.loc,
         }
     }
 
@@ -302,10 +415,26 @@ pub struct PostfixTermIndex {
     pub index: ExpressionList,
 }
 
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PostfixTermSlice {
+    pub loc: SourcePointer,
+    pub start: Option<Expression>,
+    pub stop: Option<Expression>,
+    pub step: Option<Expression>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KeywordArgument {
+    pub loc: SourcePointer,
+    pub name: Identifier,
+    pub value: Expression,
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct PostfixTermCall {
     pub loc: SourcePointer,
     pub args: ExpressionList,
+    pub keyword_args: Vec<KeywordArgument>,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -369,14 +498,22 @@ pub struct PostfixTermTryProtocol {
     pub mode: TryProtocolMode,
 }
 
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PostfixTermSafeNav {
+    pub loc: SourcePointer,
+    pub term: Box<PostfixTerm>,
+}
+
 #[allow(clippy::large_enum_variant)]
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum PostfixTerm {
     PostfixTermAttribute(PostfixTermAttribute),
     PostfixTermIndex(PostfixTermIndex),
+    PostfixTermSlice(PostfixTermSlice),
     PostfixTermCall(PostfixTermCall),
     PostfixTermObjectWrite(PostfixTermObjectWrite),
     PostfixTermEnumCase(PostfixTermEnumCase),
+    PostfixTermSafeNav(PostfixTermSafeNav),
     PostfixTermTryProtocol(PostfixTermTryProtocol),
 }
 
@@ -417,6 +554,7 @@ impl PostfixExpression {
                 loc: base.loc().clone(),
                 expressions: args.to_vec(),
             },
+            keyword_args: vec![],
         });
         Self {
             loc: base.loc().clone(),
@@ -768,6 +906,7 @@ impl Expression {
         let call = PostfixTerm::PostfixTermCall(PostfixTermCall {
             loc: loc.clone(),
             args,
+            keyword_args: vec![],
         });
 
         let pfe = PostfixExpression {
@@ -876,6 +1015,13 @@ pub struct ValDeclStatement {
     pub decls: Vec<ValDeclEntry>,
 }
 
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ValDestructureStatement {
+    pub loc: SourcePointer,
+    pub pattern: Pattern,
+    pub val: Expression,
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct AssignStatement {
     pub loc: SourcePointer,
@@ -947,17 +1093,78 @@ impl MatchPatternComp {
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
-pub struct MatchPatternEnumCase {
+pub struct PatternWildcard {
+    pub loc: SourcePointer,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PatternLiteral {
+    pub loc: SourcePointer,
+    pub value: Primary,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PatternRest {
+    pub loc: SourcePointer,
+    pub name: Option<Identifier>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PatternList {
+    pub loc: SourcePointer,
+    pub items: Vec<Pattern>,
+    pub rest: Option<PatternRest>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PatternStructField {
+    pub loc: SourcePointer,
+    pub name: Identifier,
+    pub pattern: Option<Pattern>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PatternStruct {
+    pub loc: SourcePointer,
+    pub ty: Option<Expression>,
+    pub fields: Vec<PatternStructField>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PatternEnumCase {
     pub loc: SourcePointer,
     pub case: Identifier,
-    pub payload: Option<DeclarationId>,
+    pub payload: Option<Box<Pattern>>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Pattern {
+    PatternWildcard(PatternWildcard),
+    PatternLiteral(PatternLiteral),
+    PatternList(PatternList),
+    PatternEnumCase(PatternEnumCase),
+    PatternStruct(PatternStruct),
+    DeclarationId(DeclarationId),
+}
+
+impl Pattern {
+    pub fn loc(&self) -> &SourcePointer {
+        match self {
+            Self::PatternWildcard(w) => &w.loc,
+            Self::PatternLiteral(l) => &l.loc,
+            Self::PatternList(l) => &l.loc,
+            Self::PatternEnumCase(c) => &c.loc,
+            Self::PatternStruct(s) => &s.loc,
+            Self::DeclarationId(d) => &d.loc,
+        }
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum MatchPattern {
     MatchPatternComp(MatchPatternComp),
     MatchPatternRel(MatchPatternRel),
-    MatchPatternEnumCase(MatchPatternEnumCase),
+    Pattern(Pattern),
 }
 
 impl MatchPattern {
@@ -965,7 +1172,7 @@ impl MatchPattern {
         match self {
             Self::MatchPatternComp(e) => &e.loc,
             Self::MatchPatternRel(e) => &e.loc,
-            Self::MatchPatternEnumCase(c) => &c.loc,
+            Self::Pattern(p) => p.loc(),
         }
     }
 }
@@ -993,12 +1200,12 @@ impl MatchRule {
             loc: loc.clone(),
             value: case.to_owned(),
         };
-        let payload = payload.map(|p| DeclarationId::from(&p));
-        let case_pattern = MatchPattern::MatchPatternEnumCase(MatchPatternEnumCase {
+        let payload = payload.map(|p| Box::new(Pattern::DeclarationId(DeclarationId::from(&p))));
+        let case_pattern = MatchPattern::Pattern(Pattern::PatternEnumCase(PatternEnumCase {
             loc: enumm.loc.clone(),
             case,
             payload,
-        });
+        }));
         let isa_pattern = MatchPattern::MatchPatternComp(MatchPatternComp {
             loc: loc.clone(),
             op: CompSymbol::Isa,
@@ -1023,6 +1230,7 @@ pub struct MatchStatement {
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct WhileStatement {
     pub loc: SourcePointer,
+    pub label: Option<Identifier>,
     pub cond: Expression,
     pub then: CodeBlock,
     pub els: Option<ElsePiece>,
@@ -1031,7 +1239,8 @@ pub struct WhileStatement {
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct ForStatement {
     pub loc: SourcePointer,
-    pub id: Identifier,
+    pub label: Option<Identifier>,
+    pub pattern: Pattern,
     pub expr: Expression,
     pub then: CodeBlock,
     pub els: Option<ElsePiece>,
@@ -1058,6 +1267,12 @@ pub struct ThrowStatement {
     pub val: Expression,
 }
 
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct YieldStatement {
+    pub loc: SourcePointer,
+    pub val: Expression,
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct AssertStatement {
     pub loc: SourcePointer,
@@ -1067,25 +1282,41 @@ pub struct AssertStatement {
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct BreakStatement {
     pub loc: SourcePointer,
+    pub label: Option<Identifier>,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct ContinueStatement {
     pub loc: SourcePointer,
+    pub label: Option<Identifier>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CatchPiece {
+    pub loc: SourcePointer,
+    pub id: Identifier,
+    pub then: CodeBlock,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FinallyPiece {
+    pub loc: SourcePointer,
+    pub then: CodeBlock,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct TryBlock {
     pub loc: SourcePointer,
     pub body: CodeBlock,
-    pub id: Identifier,
-    pub catch: CodeBlock,
+    pub catch: Option<CatchPiece>,
+    pub finally: Option<FinallyPiece>,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 #[allow(clippy::large_enum_variant)]
 pub enum Statement {
     ValDeclStatement(ValDeclStatement),
+    ValDestructureStatement(ValDestructureStatement),
     AssignStatement(AssignStatement),
     WriteOpEqStatement(WriteOpEqStatement),
     IfStatement(IfStatement),
@@ -1095,6 +1326,7 @@ pub enum Statement {
     CodeBlock(CodeBlock),
     ReturnStatement(ReturnStatement),
     ThrowStatement(ThrowStatement),
+    YieldStatement(YieldStatement),
     TryBlock(TryBlock),
     AssertStatement(AssertStatement),
     ExpressionStatement(ExpressionStatement),
@@ -1109,6 +1341,7 @@ impl Statement {
     pub fn loc(&self) -> &SourcePointer {
         match self {
             Self::ValDeclStatement(a) => &a.loc,
+            Self::ValDestructureStatement(a) => &a.loc,
             Self::AssignStatement(a) => &a.loc,
             Self::WriteOpEqStatement(a) => &a.loc,
             Self::IfStatement(a) => &a.loc,
@@ -1118,6 +1351,7 @@ impl Statement {
             Self::CodeBlock(a) => &a.loc,
             Self::ReturnStatement(a) => &a.loc,
             Self::ThrowStatement(a) => &a.loc,
+            Self::YieldStatement(a) => &a.loc,
             Self::TryBlock(a) => &a.loc,
             Self::AssertStatement(a) => &a.loc,
             Self::ExpressionStatement(a) => &a.loc,
@@ -1200,6 +1434,7 @@ impl ArgumentList {
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct FunctionDecl {
     pub loc: SourcePointer,
+    pub is_async: bool,
     pub name: Identifier,
     pub args: ArgumentList,
     pub body: FunctionBody,
@@ -1215,6 +1450,7 @@ pub enum MethodAccess {
 pub struct MethodDecl {
     pub loc: SourcePointer,
     pub access: MethodAccess,
+    pub is_async: bool,
     pub name: Identifier,
     pub args: ArgumentList,
     pub body: FunctionBody,
@@ -1241,6 +1477,7 @@ pub enum OperatorSymbol {
     Call,
     GetSquareBrackets,
     SetSquareBrackets,
+    GetSlice,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -1258,6 +1495,16 @@ pub struct MixinIncludeDecl {
     pub what: Expression,
 }
 
+// the accessors are instance methods named after the property; the getter
+// takes no arguments, and the setter takes the value being written
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PropertyDecl {
+    pub loc: SourcePointer,
+    pub name: Identifier,
+    pub getter: Option<MethodDecl>,
+    pub setter: Option<MethodDecl>,
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum StructEntry {
     Method(Box<MethodDecl>),
@@ -1266,6 +1513,7 @@ pub enum StructEntry {
     Struct(Box<StructDecl>),
     Enum(Box<EnumDecl>),
     MixinInclude(Box<MixinIncludeDecl>),
+    Property(Box<PropertyDecl>),
 }
 
 impl StructEntry {
@@ -1277,6 +1525,7 @@ impl StructEntry {
             Self::Struct(s) => &s.loc,
             Self::Enum(e) => &e.loc,
             Self::MixinInclude(m) => &m.loc,
+            Self::Property(p) => &p.loc,
         }
     }
 }
@@ -1289,10 +1538,18 @@ pub struct StructDecl {
     pub body: Vec<StructEntry>,
 }
 
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RequireDecl {
+    pub loc: SourcePointer,
+    pub name: Identifier,
+    pub args: ArgumentList,
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct MixinDecl {
     pub loc: SourcePointer,
     pub name: Identifier,
+    pub requirements: Vec<RequireDecl>,
     pub body: Vec<StructEntry>,
 }
 
@@ -1394,6 +1651,7 @@ pub struct ImportFromStatement {
 pub enum ModuleFlag {
     NoStandardLibrary,
     UsesDylib(String),
+    ExhaustiveMatch,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -1412,6 +1670,7 @@ impl ModuleFlags {
 pub enum TopLevelEntry {
     ExpressionStatement(ExpressionStatement),
     ValDeclStatement(ValDeclStatement),
+    ValDestructureStatement(ValDestructureStatement),
     WriteOpEqStatement(WriteOpEqStatement),
     AssignStatement(AssignStatement),
     FunctionDecl(FunctionDecl),
@@ -1449,7 +1708,8 @@ impl std::fmt::Display for ParserError {
     }
 }
 
-pub type ParserResult<T> = Result<T, ParserError>;
+// a module with syntax errors reports all of them, not just the first one
+pub type ParserResult<T> = Result<T, Vec<ParserError>>;
 
 pub fn source_to_ast(source: &SourceBuffer) -> ParserResult<ParsedModule> {
     let input = &source.as_str();
@@ -1459,13 +1719,6 @@ pub fn source_to_ast(source: &SourceBuffer) -> ParserResult<ParsedModule> {
             pt.next_back().expect("invalid parse tree"),
             source,
         )),
-        Err(err) => {
-            let loc = From::from(&err.location);
-            let ptr = source.pointer(loc);
-            Err(ParserError {
-                loc: ptr,
-                msg: err.variant.message().to_string(),
-            })
-        }
+        Err(err) => Err(recovery::collect_syntax_errors(source, err)),
     }
 }
@@ -0,0 +1,29 @@
+// SPDX-License-Identifier: Apache-2.0
+use crate::{
+    ast::{
+        AwaitExpression, SourceBuffer, UnaryOperation,
+        derive::Derive,
+        prettyprint::{PrettyPrintable, printout_accumulator::PrintoutAccumulator},
+    },
+    grammar::Rule,
+};
+
+impl Derive for AwaitExpression {
+    fn from_parse_tree(p: pest::iterators::Pair<'_, Rule>, source: &SourceBuffer) -> Self {
+        assert!(p.as_rule() == Rule::await_expr);
+        let loc = From::from(&p.as_span());
+        let mut inner = p.into_inner();
+        inner.next().expect("need await keyword");
+        let val = UnaryOperation::from_parse_tree(inner.next().expect("need expression"), source);
+        Self {
+            loc: source.pointer(loc),
+            val: Box::new(val),
+        }
+    }
+}
+
+impl PrettyPrintable for AwaitExpression {
+    fn prettyprint(&self, buffer: PrintoutAccumulator) -> PrintoutAccumulator {
+        buffer << "await " << self.val.as_ref()
+    }
+}
@@ -0,0 +1,19 @@
+// SPDX-License-Identifier: Apache-2.0
+use crate::{
+    ast::{
+        CatchPiece, CodeBlock, Identifier,
+        derive::Derive,
+        prettyprint::{PrettyPrintable, printout_accumulator::PrintoutAccumulator},
+    },
+    gen_from_components,
+};
+
+impl Derive for CatchPiece {
+    gen_from_components!(catch_piece; id: Identifier, then: CodeBlock);
+}
+
+impl PrettyPrintable for CatchPiece {
+    fn prettyprint(&self, buffer: PrintoutAccumulator) -> PrintoutAccumulator {
+        buffer << "catch (" << &self.id << ") " << &self.then
+    }
+}
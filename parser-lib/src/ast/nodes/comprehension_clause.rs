@@ -0,0 +1,43 @@
+// SPDX-License-Identifier: Apache-2.0
+use crate::{
+    ast::{
+        ComprehensionClause, Expression, SourceBuffer,
+        derive::Derive,
+        prettyprint::{PrettyPrintable, printout_accumulator::PrintoutAccumulator},
+    },
+    gen_from_options,
+    grammar::Rule,
+};
+
+use crate::ast::{ComprehensionFor, ComprehensionIf};
+
+impl Derive for ComprehensionClause {
+    gen_from_options!(
+        comprehension_clause;
+        (comprehension_for, ComprehensionFor),
+        (comprehension_if, ComprehensionIf)
+    );
+}
+
+impl PrettyPrintable for ComprehensionClause {
+    fn prettyprint(&self, buffer: PrintoutAccumulator) -> PrintoutAccumulator {
+        match self {
+            Self::ComprehensionFor(f) => f.prettyprint(buffer),
+            Self::ComprehensionIf(i) => i.prettyprint(buffer),
+        }
+    }
+}
+
+// the body of a list comprehension or generator expression: the produced
+// expression, the leading for clause and then any further clauses
+pub(super) fn comprehension_from_parse_tree(
+    mut inner: pest::iterators::Pairs<'_, Rule>,
+    source: &SourceBuffer,
+) -> (Box<Expression>, Vec<ComprehensionClause>) {
+    let expr = Expression::from_parse_tree(inner.next().expect("need expression"), source);
+    let first = ComprehensionFor::from_parse_tree(inner.next().expect("need for clause"), source);
+    let clauses = std::iter::once(ComprehensionClause::ComprehensionFor(first))
+        .chain(inner.map(|c| ComprehensionClause::from_parse_tree(c, source)))
+        .collect();
+    (Box::new(expr), clauses)
+}
@@ -0,0 +1,37 @@
+// SPDX-License-Identifier: Apache-2.0
+use crate::{
+    ast::{
+        ComprehensionFor, DeclarationId, Expression, Identifier, Pattern, SourceBuffer,
+        derive::Derive,
+        prettyprint::{PrettyPrintable, printout_accumulator::PrintoutAccumulator},
+    },
+    grammar::Rule,
+};
+
+impl Derive for ComprehensionFor {
+    fn from_parse_tree(p: pest::iterators::Pair<'_, Rule>, source: &SourceBuffer) -> Self {
+        assert!(p.as_rule() == Rule::comprehension_for);
+        let loc = From::from(&p.as_span());
+        let mut inner = p.into_inner();
+        let next = inner.next().expect("need identifier or pattern");
+        let pattern = if next.as_rule() == Rule::identifier {
+            Pattern::DeclarationId(DeclarationId::from(&Identifier::from_parse_tree(
+                next, source,
+            )))
+        } else {
+            Pattern::from_parse_tree(next, source)
+        };
+        let expr = Expression::from_parse_tree(inner.next().expect("need expression"), source);
+        Self {
+            loc: source.pointer(loc),
+            pattern,
+            expr,
+        }
+    }
+}
+
+impl PrettyPrintable for ComprehensionFor {
+    fn prettyprint(&self, buffer: PrintoutAccumulator) -> PrintoutAccumulator {
+        buffer << "for " << &self.pattern << " in " << &self.expr
+    }
+}
@@ -0,0 +1,28 @@
+// SPDX-License-Identifier: Apache-2.0
+use crate::{
+    ast::{
+        ComprehensionIf, Expression, SourceBuffer,
+        derive::Derive,
+        prettyprint::{PrettyPrintable, printout_accumulator::PrintoutAccumulator},
+    },
+    grammar::Rule,
+};
+
+impl Derive for ComprehensionIf {
+    fn from_parse_tree(p: pest::iterators::Pair<'_, Rule>, source: &SourceBuffer) -> Self {
+        assert!(p.as_rule() == Rule::comprehension_if);
+        let loc = From::from(&p.as_span());
+        let mut inner = p.into_inner();
+        let cond = Expression::from_parse_tree(inner.next().expect("need condition"), source);
+        Self {
+            loc: source.pointer(loc),
+            cond,
+        }
+    }
+}
+
+impl PrettyPrintable for ComprehensionIf {
+    fn prettyprint(&self, buffer: PrintoutAccumulator) -> PrintoutAccumulator {
+        buffer << "if " << &self.cond
+    }
+}
@@ -1,7 +1,7 @@
 // SPDX-License-Identifier: Apache-2.0
 use crate::{
     ast::{
-        ContinueStatement, SourceBuffer,
+        ContinueStatement, Identifier, SourceBuffer,
         derive::Derive,
         prettyprint::{PrettyPrintable, printout_accumulator::PrintoutAccumulator},
     },
@@ -12,14 +12,21 @@ impl Derive for ContinueStatement {
     fn from_parse_tree(p: pest::iterators::Pair<'_, Rule>, source: &SourceBuffer) -> Self {
         assert!(p.as_rule() == Rule::continue_stmt);
         let loc = From::from(&p.as_span());
+        let mut inner = p.into_inner();
+        inner.next().expect("need continue keyword");
+        let label = inner.next().map(|p| Identifier::from_parse_tree(p, source));
         Self {
             loc: source.pointer(loc),
+            label,
         }
     }
 }
 
 impl PrettyPrintable for ContinueStatement {
     fn prettyprint(&self, buffer: PrintoutAccumulator) -> PrintoutAccumulator {
-        buffer << "continue;"
+        match &self.label {
+            Some(label) => buffer << "continue " << label << ";",
+            None => buffer << "continue;",
+        }
     }
 }
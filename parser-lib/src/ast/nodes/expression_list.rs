@@ -10,7 +10,7 @@ use crate::{
 
 impl Derive for ExpressionList {
     fn from_parse_tree(p: pest::iterators::Pair<'_, Rule>, source: &SourceBuffer) -> Self {
-        assert!(p.as_rule() == Rule::expr_list);
+        assert!(p.as_rule() == Rule::expr_list || p.as_rule() == Rule::call_expr_list);
         let loc = From::from(&p.as_span());
         let inner = p.into_inner();
         let expressions = inner
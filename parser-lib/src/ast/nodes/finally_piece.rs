@@ -0,0 +1,19 @@
+// SPDX-License-Identifier: Apache-2.0
+use crate::{
+    ast::{
+        CodeBlock, FinallyPiece,
+        derive::Derive,
+        prettyprint::{PrettyPrintable, printout_accumulator::PrintoutAccumulator},
+    },
+    gen_from_components,
+};
+
+impl Derive for FinallyPiece {
+    gen_from_components!(finally_piece; then: CodeBlock);
+}
+
+impl PrettyPrintable for FinallyPiece {
+    fn prettyprint(&self, buffer: PrintoutAccumulator) -> PrintoutAccumulator {
+        buffer << "finally " << &self.then
+    }
+}
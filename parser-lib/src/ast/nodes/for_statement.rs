@@ -1,7 +1,7 @@
 // SPDX-License-Identifier: Apache-2.0
 use crate::{
     ast::{
-        CodeBlock, ElsePiece, Expression, ForStatement, Identifier,
+        CodeBlock, DeclarationId, ElsePiece, Expression, ForStatement, Identifier, Pattern,
         derive::Derive,
         prettyprint::{PrettyPrintable, printout_accumulator::PrintoutAccumulator},
     },
@@ -16,7 +16,25 @@ impl Derive for ForStatement {
         assert!(p.as_rule() == Rule::for_stmt);
         let loc = From::from(&p.as_span());
         let mut inner = p.into_inner();
-        let id = Identifier::from_parse_tree(inner.next().expect("need identifier"), source);
+        let label = if inner.peek().expect("need pattern").as_rule() == Rule::loop_label {
+            let label = inner
+                .next()
+                .unwrap()
+                .into_inner()
+                .next()
+                .expect("need label");
+            Some(Identifier::from_parse_tree(label, source))
+        } else {
+            None
+        };
+        let next = inner.next().expect("need identifier or pattern");
+        let pattern = if next.as_rule() == Rule::identifier {
+            Pattern::DeclarationId(DeclarationId::from(&Identifier::from_parse_tree(
+                next, source,
+            )))
+        } else {
+            Pattern::from_parse_tree(next, source)
+        };
         let expr = Expression::from_parse_tree(inner.next().expect("need expression"), source);
         let then = CodeBlock::from_parse_tree(inner.next().expect("need then block"), source);
         let els = inner
@@ -25,7 +43,8 @@ impl Derive for ForStatement {
 
         Self {
             loc: source.pointer(loc),
-            id,
+            label,
+            pattern,
             expr,
             then,
             els,
@@ -35,6 +54,10 @@ impl Derive for ForStatement {
 
 impl PrettyPrintable for ForStatement {
     fn prettyprint(&self, buffer: PrintoutAccumulator) -> PrintoutAccumulator {
-        buffer << "for( " << &self.id << " in " << &self.expr << ") " << &self.then
+        let buffer = match &self.label {
+            Some(label) => buffer << label << ": ",
+            None => buffer,
+        };
+        buffer << "for( " << &self.pattern << " in " << &self.expr << ") " << &self.then
     }
 }
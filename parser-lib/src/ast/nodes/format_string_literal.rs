@@ -0,0 +1,55 @@
+// SPDX-License-Identifier: Apache-2.0
+use crate::{
+    ast::{
+        Expression, FormatStringLiteral, FormatStringPart, SourceBuffer,
+        derive::Derive,
+        prettyprint::{PrettyPrintable, printout_accumulator::PrintoutAccumulator},
+    },
+    grammar::Rule,
+};
+
+use super::string_literal::process_string_escapes;
+
+impl Derive for FormatStringLiteral {
+    fn from_parse_tree(p: pest::iterators::Pair<'_, Rule>, source: &SourceBuffer) -> Self {
+        assert!(p.as_rule() == Rule::format_str_literal);
+        let loc = From::from(&p.as_span());
+        let parts = p
+            .into_inner()
+            .map(|part| match part.as_rule() {
+                Rule::format_str_expr => {
+                    let expr = part.into_inner().next().expect("need expression");
+                    FormatStringPart::Expression(Expression::from_parse_tree(expr, source))
+                }
+                Rule::format_str_text_dbl_qt | Rule::format_str_text_sgl_qt => {
+                    let text = part.as_str().replace("{{", "{").replace("}}", "}");
+                    FormatStringPart::Text(process_string_escapes(&text))
+                }
+                _ => panic!("unexpected format string part"),
+            })
+            .collect();
+        Self {
+            loc: source.pointer(loc),
+            parts,
+        }
+    }
+}
+
+impl PrettyPrintable for FormatStringPart {
+    fn prettyprint(&self, buffer: PrintoutAccumulator) -> PrintoutAccumulator {
+        match self {
+            Self::Text(text) => buffer << text.replace("{", "{{").replace("}", "}}"),
+            Self::Expression(expr) => buffer << "{" << expr << "}",
+        }
+    }
+}
+
+impl PrettyPrintable for FormatStringLiteral {
+    fn prettyprint(&self, buffer: PrintoutAccumulator) -> PrintoutAccumulator {
+        let mut buffer = buffer << "f\"";
+        for part in &self.parts {
+            buffer = part.prettyprint(buffer);
+        }
+        buffer << "\""
+    }
+}
@@ -13,6 +13,10 @@ impl Derive for FunctionDecl {
         assert!(p.as_rule() == Rule::function_decl);
         let loc = From::from(&p.as_span());
         let mut inner = p.into_inner();
+        let is_async = inner.peek().expect("expected next").as_rule() == Rule::async_kwd;
+        if is_async {
+            inner.next();
+        }
         let name = Identifier::from_parse_tree(inner.next().expect("need identifier"), source);
         let p = inner.peek().unwrap();
         let args = if p.as_rule() == Rule::arg_list {
@@ -24,6 +28,7 @@ impl Derive for FunctionDecl {
         let body = FunctionBody::from_parse_tree(inner.next().expect("need body"), source);
         Self {
             loc: source.pointer(loc),
+            is_async,
             name,
             args,
             body,
@@ -33,6 +38,11 @@ impl Derive for FunctionDecl {
 
 impl PrettyPrintable for FunctionDecl {
     fn prettyprint(&self, buffer: PrintoutAccumulator) -> PrintoutAccumulator {
+        let buffer = if self.is_async {
+            buffer << "async "
+        } else {
+            buffer
+        };
         buffer << "func " << &self.name << " (" << &self.args << ") " << &self.body
     }
 }
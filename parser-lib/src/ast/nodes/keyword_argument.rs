@@ -0,0 +1,30 @@
+// SPDX-License-Identifier: Apache-2.0
+use crate::{
+    ast::{
+        Expression, Identifier, KeywordArgument, SourceBuffer,
+        derive::Derive,
+        prettyprint::{PrettyPrintable, printout_accumulator::PrintoutAccumulator},
+    },
+    grammar::Rule,
+};
+
+impl Derive for KeywordArgument {
+    fn from_parse_tree(p: pest::iterators::Pair<'_, Rule>, source: &SourceBuffer) -> Self {
+        assert!(p.as_rule() == Rule::keyword_arg);
+        let loc = From::from(&p.as_span());
+        let mut inner = p.into_inner();
+        let name = Identifier::from_parse_tree(inner.next().expect("need name"), source);
+        let value = Expression::from_parse_tree(inner.next().expect("need value"), source);
+        Self {
+            loc: source.pointer(loc),
+            name,
+            value,
+        }
+    }
+}
+
+impl PrettyPrintable for KeywordArgument {
+    fn prettyprint(&self, buffer: PrintoutAccumulator) -> PrintoutAccumulator {
+        buffer << &self.name << ": " << &self.value
+    }
+}
@@ -0,0 +1,32 @@
+// SPDX-License-Identifier: Apache-2.0
+use crate::{
+    ast::{
+        ListComprehension, SourceBuffer,
+        derive::Derive,
+        prettyprint::{PrettyPrintable, printout_accumulator::PrintoutAccumulator},
+    },
+    grammar::Rule,
+};
+
+use super::comprehension_clause::comprehension_from_parse_tree;
+
+impl Derive for ListComprehension {
+    fn from_parse_tree(p: pest::iterators::Pair<'_, Rule>, source: &SourceBuffer) -> Self {
+        assert!(p.as_rule() == Rule::list_comprehension);
+        let loc = From::from(&p.as_span());
+        let (expr, clauses) = comprehension_from_parse_tree(p.into_inner(), source);
+        Self {
+            loc: source.pointer(loc),
+            expr,
+            clauses,
+        }
+    }
+}
+
+impl PrettyPrintable for ListComprehension {
+    fn prettyprint(&self, buffer: PrintoutAccumulator) -> PrintoutAccumulator {
+        (buffer << "[" << &self.expr << " ")
+            .write_separated_list(&self.clauses, " ")
+            .write("]")
+    }
+}
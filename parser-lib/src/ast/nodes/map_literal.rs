@@ -0,0 +1,33 @@
+// SPDX-License-Identifier: Apache-2.0
+use crate::{
+    ast::{
+        MapLiteral, MapLiteralEntry, SourceBuffer,
+        derive::Derive,
+        prettyprint::{PrettyPrintable, printout_accumulator::PrintoutAccumulator},
+    },
+    grammar::Rule,
+};
+
+impl Derive for MapLiteral {
+    fn from_parse_tree(p: pest::iterators::Pair<'_, Rule>, source: &SourceBuffer) -> Self {
+        assert!(p.as_rule() == Rule::map_literal);
+        let loc = From::from(&p.as_span());
+        let entries = p
+            .into_inner()
+            .map(|e| MapLiteralEntry::from_parse_tree(e, source))
+            .collect::<Vec<_>>();
+        Self {
+            loc: source.pointer(loc),
+            entries,
+        }
+    }
+}
+
+impl PrettyPrintable for MapLiteral {
+    fn prettyprint(&self, buffer: PrintoutAccumulator) -> PrintoutAccumulator {
+        buffer
+            .write("{")
+            .write_separated_list(&self.entries, ", ")
+            .write("}")
+    }
+}
@@ -1,7 +1,7 @@
 // SPDX-License-Identifier: Apache-2.0
 use crate::{
     ast::{
-        MatchPattern, MatchPatternComp, MatchPatternEnumCase, MatchPatternRel,
+        MatchPattern, MatchPatternComp, MatchPatternRel, Pattern,
         derive::Derive,
         prettyprint::{PrettyPrintable, printout_accumulator::PrintoutAccumulator},
     },
@@ -12,8 +12,8 @@ impl Derive for MatchPattern {
     gen_from_options!(
         match_pattern;
         (match_pattern_comp, MatchPatternComp),
-        (match_pattern_enum_case, MatchPatternEnumCase),
         (match_pattern_rel, MatchPatternRel),
+        (pattern, Pattern),
     );
 }
 
@@ -22,7 +22,7 @@ impl PrettyPrintable for MatchPattern {
         match self {
             Self::MatchPatternComp(e) => e.prettyprint(buffer),
             Self::MatchPatternRel(e) => e.prettyprint(buffer),
-            Self::MatchPatternEnumCase(e) => e.prettyprint(buffer),
+            Self::Pattern(e) => e.prettyprint(buffer),
         }
     }
 }
@@ -19,6 +19,10 @@ impl Derive for MethodDecl {
         } else {
             MethodAccess::Instance
         };
+        let is_async = inner.peek().expect("expected next").as_rule() == Rule::async_kwd;
+        if is_async {
+            inner.next();
+        }
         let name = Identifier::from_parse_tree(inner.next().expect("need identifier"), source);
         let p = inner.peek().unwrap();
         let args = if p.as_rule() == Rule::arg_list {
@@ -31,6 +35,7 @@ impl Derive for MethodDecl {
         Self {
             loc: source.pointer(loc),
             access,
+            is_async,
             name,
             args,
             body,
@@ -40,6 +45,12 @@ impl Derive for MethodDecl {
 
 impl PrettyPrintable for MethodDecl {
     fn prettyprint(&self, buffer: PrintoutAccumulator) -> PrintoutAccumulator {
-        buffer << &self.access << " func " << &self.name << " (" << &self.args << ") " << &self.body
+        let buffer = buffer << &self.access;
+        let buffer = if self.is_async {
+            buffer << " async"
+        } else {
+            buffer
+        };
+        buffer << " func " << &self.name << " (" << &self.args << ") " << &self.body
     }
 }
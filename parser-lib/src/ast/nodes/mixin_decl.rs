@@ -1,7 +1,7 @@
 // SPDX-License-Identifier: Apache-2.0
 use crate::{
     ast::{
-        Identifier, MixinDecl, SourceBuffer, StructEntry,
+        Identifier, MixinDecl, RequireDecl, SourceBuffer, StructEntry,
         derive::Derive,
         prettyprint::{PrettyPrintable, printout_accumulator::PrintoutAccumulator},
     },
@@ -14,14 +14,19 @@ impl Derive for MixinDecl {
         let loc = From::from(&p.as_span());
         let mut inner = p.into_inner();
         let name = Identifier::from_parse_tree(inner.next().expect("need identifier"), source);
+        let mut requirements = vec![];
         let mut body = vec![];
         for next in inner {
-            let next = StructEntry::from_parse_tree(next, source);
-            body.push(next);
+            if next.as_rule() == Rule::require_decl {
+                requirements.push(RequireDecl::from_parse_tree(next, source));
+            } else {
+                body.push(StructEntry::from_parse_tree(next, source));
+            }
         }
         Self {
             loc: source.pointer(loc),
             name,
+            requirements,
             body,
         }
     }
@@ -29,6 +34,12 @@ impl Derive for MixinDecl {
 
 impl PrettyPrintable for MixinDecl {
     fn prettyprint(&self, buffer: PrintoutAccumulator) -> PrintoutAccumulator {
-        (buffer << "mixin " << &self.name).write_indented_list(&self.body, "{\n", "\n", "\n}")
+        let buffer = (buffer << "mixin " << &self.name).write_indented_list(
+            &self.requirements,
+            "{\n",
+            "\n",
+            "\n",
+        );
+        buffer.write_indented_list(&self.body, "", "\n", "\n}")
     }
 }
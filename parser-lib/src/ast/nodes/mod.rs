@@ -6,10 +6,15 @@ mod argument_decl;
 mod argument_list;
 mod assert_statement;
 mod assign_statement;
+mod await_expression;
 mod break_statement;
+mod catch_piece;
 mod code_block;
 mod comp_operation;
 mod comp_symbol;
+mod comprehension_clause;
+mod comprehension_for;
+mod comprehension_if;
 mod continue_statement;
 mod declaration_id;
 mod else_piece;
@@ -21,10 +26,13 @@ mod expression;
 mod expression_list;
 mod expression_statement;
 mod extension_decl;
+mod finally_piece;
 mod float_literal;
 mod for_statement;
+mod format_string_literal;
 mod function_body;
 mod function_decl;
+mod generator_expression;
 mod identifier;
 mod identifier_list;
 mod if_cond_piece;
@@ -35,14 +43,17 @@ mod import_path;
 mod import_statement;
 mod import_target;
 mod int_literal;
+mod keyword_argument;
 mod lambda_body;
 mod lambda_function;
+mod list_comprehension;
 mod list_literal;
 mod log_operation;
 mod log_symbol;
+mod map_literal;
+mod map_literal_entry;
 mod match_pattern;
 mod match_pattern_comp;
-mod match_pattern_enum_case;
 mod match_pattern_rel;
 mod match_rule;
 mod match_statement;
@@ -58,6 +69,14 @@ mod operator_decl;
 mod operator_symbol;
 mod paren_expression;
 mod parsed_module;
+mod pattern;
+mod pattern_enum_case;
+mod pattern_list;
+mod pattern_literal;
+mod pattern_rest;
+mod pattern_struct;
+mod pattern_struct_field;
+mod pattern_wildcard;
 mod postfix_expression;
 mod postfix_rvalue;
 mod postfix_term;
@@ -68,12 +87,16 @@ mod postfix_term_field_write;
 mod postfix_term_index;
 mod postfix_term_index_write;
 mod postfix_term_object_write;
+mod postfix_term_safe_nav;
+mod postfix_term_slice;
 mod postfix_term_try_protocol;
 mod postfix_term_write;
 mod postfix_term_write_list;
 mod primary;
+mod property_decl;
 mod rel_operation;
 mod rel_symbol;
+mod require_decl;
 mod return_statement;
 mod shift_operation;
 mod shift_symbol;
@@ -90,5 +113,7 @@ mod unary_operation;
 mod unary_symbol;
 mod val_decl_entry;
 mod val_decl_statement;
+mod val_destructure_statement;
 mod while_statement;
 mod write_op_eq_statement;
+mod yield_statement;
@@ -19,6 +19,7 @@ impl Derive for ModuleFlag {
                 let path = StringLiteral::from_parse_tree(inner.next().expect("need path"), source);
                 Self::UsesDylib(path.value)
             }
+            "exhaustive_match" => Self::ExhaustiveMatch,
             _ => panic!("unknown module flag"),
         }
     }
@@ -29,6 +30,7 @@ impl PrettyPrintable for ModuleFlag {
         match self {
             ModuleFlag::NoStandardLibrary => buffer << "flag: no_std;",
             ModuleFlag::UsesDylib(dylib) => buffer << "flag: uses_dylib(" << dylib.as_str() << ");",
+            ModuleFlag::ExhaustiveMatch => buffer << "flag: exhaustive_match;",
         }
     }
 }
@@ -31,6 +31,7 @@ impl Derive for OperatorSymbol {
             "()" => Self::Call,
             "[]" => Self::GetSquareBrackets,
             "[]=" => Self::SetSquareBrackets,
+            "[:]" => Self::GetSlice,
             _ => panic!("valid operator symbol expected, found: {}", p.as_str()),
         }
     }
@@ -58,6 +59,7 @@ impl PrettyPrintable for OperatorSymbol {
             Self::Call => "()",
             Self::GetSquareBrackets => "[]",
             Self::SetSquareBrackets => "[]=",
+            Self::GetSlice => "[:]",
         })
     }
 }
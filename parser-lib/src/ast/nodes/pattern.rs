@@ -0,0 +1,47 @@
+// SPDX-License-Identifier: Apache-2.0
+use crate::{
+    ast::{
+        DeclarationId, Pattern, PatternEnumCase, PatternList, PatternLiteral, PatternStruct,
+        PatternWildcard, SourceBuffer,
+        derive::Derive,
+        prettyprint::{PrettyPrintable, printout_accumulator::PrintoutAccumulator},
+    },
+    grammar::Rule,
+};
+
+impl Derive for Pattern {
+    fn from_parse_tree(p: pest::iterators::Pair<'_, Rule>, source: &SourceBuffer) -> Self {
+        assert!(p.as_rule() == Rule::pattern || p.as_rule() == Rule::destructure_pattern);
+        let next = p.into_inner().next().expect("need content");
+        match next.as_rule() {
+            Rule::pattern_wildcard => {
+                Self::PatternWildcard(PatternWildcard::from_parse_tree(next, source))
+            }
+            Rule::pattern_literal => {
+                Self::PatternLiteral(PatternLiteral::from_parse_tree(next, source))
+            }
+            Rule::pattern_list => Self::PatternList(PatternList::from_parse_tree(next, source)),
+            Rule::pattern_enum_case => {
+                Self::PatternEnumCase(PatternEnumCase::from_parse_tree(next, source))
+            }
+            Rule::pattern_struct => {
+                Self::PatternStruct(PatternStruct::from_parse_tree(next, source))
+            }
+            Rule::decl_id => Self::DeclarationId(DeclarationId::from_parse_tree(next, source)),
+            _ => panic!("invalid node"),
+        }
+    }
+}
+
+impl PrettyPrintable for Pattern {
+    fn prettyprint(&self, buffer: PrintoutAccumulator) -> PrintoutAccumulator {
+        match self {
+            Self::PatternWildcard(w) => w.prettyprint(buffer),
+            Self::PatternLiteral(l) => l.prettyprint(buffer),
+            Self::PatternList(l) => l.prettyprint(buffer),
+            Self::PatternEnumCase(c) => c.prettyprint(buffer),
+            Self::PatternStruct(s) => s.prettyprint(buffer),
+            Self::DeclarationId(d) => d.prettyprint(buffer),
+        }
+    }
+}
@@ -1,22 +1,22 @@
 // SPDX-License-Identifier: Apache-2.0
 use crate::{
     ast::{
-        DeclarationId, Identifier, MatchPatternEnumCase, SourceBuffer,
+        Identifier, Pattern, PatternEnumCase, SourceBuffer,
         derive::Derive,
         prettyprint::{PrettyPrintable, printout_accumulator::PrintoutAccumulator},
     },
     grammar::Rule,
 };
 
-impl Derive for MatchPatternEnumCase {
+impl Derive for PatternEnumCase {
     fn from_parse_tree(p: pest::iterators::Pair<'_, Rule>, source: &SourceBuffer) -> Self {
-        assert!(p.as_rule() == Rule::match_pattern_enum_case);
+        assert!(p.as_rule() == Rule::pattern_enum_case);
         let loc = From::from(&p.as_span());
         let mut inner = p.into_inner();
-        let case = Identifier::from_parse_tree(inner.next().expect("need expression"), source);
+        let case = Identifier::from_parse_tree(inner.next().expect("need identifier"), source);
         let payload = inner
             .next()
-            .map(|next| DeclarationId::from_parse_tree(next, source));
+            .map(|next| Box::new(Pattern::from_parse_tree(next, source)));
         Self {
             loc: source.pointer(loc),
             case,
@@ -25,11 +25,11 @@ impl Derive for MatchPatternEnumCase {
     }
 }
 
-impl PrettyPrintable for MatchPatternEnumCase {
+impl PrettyPrintable for PatternEnumCase {
     fn prettyprint(&self, buffer: PrintoutAccumulator) -> PrintoutAccumulator {
-        let buffer = buffer << " case " << &self.case;
+        let buffer = buffer << "case " << &self.case;
         if let Some(p) = &self.payload {
-            buffer << "(" << p << ")"
+            buffer << "(" << p.as_ref() << ")"
         } else {
             buffer
         }
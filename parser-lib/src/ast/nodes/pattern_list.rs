@@ -0,0 +1,42 @@
+// SPDX-License-Identifier: Apache-2.0
+use crate::{
+    ast::{
+        Pattern, PatternList, PatternRest, SourceBuffer,
+        derive::Derive,
+        prettyprint::{PrettyPrintable, printout_accumulator::PrintoutAccumulator},
+    },
+    grammar::Rule,
+};
+
+impl Derive for PatternList {
+    fn from_parse_tree(p: pest::iterators::Pair<'_, Rule>, source: &SourceBuffer) -> Self {
+        assert!(p.as_rule() == Rule::pattern_list);
+        let loc = From::from(&p.as_span());
+        let mut items = vec![];
+        let mut rest = None;
+        for next in p.into_inner() {
+            match next.as_rule() {
+                Rule::pattern => items.push(Pattern::from_parse_tree(next, source)),
+                Rule::pattern_rest => rest = Some(PatternRest::from_parse_tree(next, source)),
+                _ => panic!("invalid list pattern entry"),
+            }
+        }
+        Self {
+            loc: source.pointer(loc),
+            items,
+            rest,
+        }
+    }
+}
+
+impl PrettyPrintable for PatternList {
+    fn prettyprint(&self, buffer: PrintoutAccumulator) -> PrintoutAccumulator {
+        let buffer = (buffer << "[").write_separated_list(&self.items, ", ");
+        let buffer = match &self.rest {
+            Some(rest) if self.items.is_empty() => buffer << rest,
+            Some(rest) => buffer << ", " << rest,
+            None => buffer,
+        };
+        buffer << "]"
+    }
+}
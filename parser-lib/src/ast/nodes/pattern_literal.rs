@@ -0,0 +1,35 @@
+// SPDX-License-Identifier: Apache-2.0
+use crate::{
+    ast::{
+        FloatLiteral, IntLiteral, PatternLiteral, Primary, SourceBuffer, StringLiteral,
+        derive::Derive,
+        prettyprint::{PrettyPrintable, printout_accumulator::PrintoutAccumulator},
+    },
+    grammar::Rule,
+};
+
+impl Derive for PatternLiteral {
+    fn from_parse_tree(p: pest::iterators::Pair<'_, Rule>, source: &SourceBuffer) -> Self {
+        assert!(p.as_rule() == Rule::pattern_literal);
+        let loc = From::from(&p.as_span());
+        let next = p.into_inner().next().expect("need literal");
+        let value = match next.as_rule() {
+            Rule::int_literal => Primary::IntLiteral(IntLiteral::from_parse_tree(next, source)),
+            Rule::fp_literal => Primary::FloatLiteral(FloatLiteral::from_parse_tree(next, source)),
+            Rule::str_literal => {
+                Primary::StringLiteral(StringLiteral::from_parse_tree(next, source))
+            }
+            _ => panic!("invalid literal"),
+        };
+        Self {
+            loc: source.pointer(loc),
+            value,
+        }
+    }
+}
+
+impl PrettyPrintable for PatternLiteral {
+    fn prettyprint(&self, buffer: PrintoutAccumulator) -> PrintoutAccumulator {
+        buffer << &self.value
+    }
+}
@@ -0,0 +1,35 @@
+// SPDX-License-Identifier: Apache-2.0
+use crate::{
+    ast::{
+        Identifier, PatternRest, SourceBuffer,
+        derive::Derive,
+        prettyprint::{PrettyPrintable, printout_accumulator::PrintoutAccumulator},
+    },
+    grammar::Rule,
+};
+
+impl Derive for PatternRest {
+    fn from_parse_tree(p: pest::iterators::Pair<'_, Rule>, source: &SourceBuffer) -> Self {
+        assert!(p.as_rule() == Rule::pattern_rest);
+        let loc = From::from(&p.as_span());
+        let name = p
+            .into_inner()
+            .next()
+            .map(|next| Identifier::from_parse_tree(next, source));
+        Self {
+            loc: source.pointer(loc),
+            name,
+        }
+    }
+}
+
+impl PrettyPrintable for PatternRest {
+    fn prettyprint(&self, buffer: PrintoutAccumulator) -> PrintoutAccumulator {
+        let buffer = buffer << "...";
+        if let Some(name) = &self.name {
+            buffer << name
+        } else {
+            buffer
+        }
+    }
+}
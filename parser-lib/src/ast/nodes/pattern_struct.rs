@@ -0,0 +1,64 @@
+// SPDX-License-Identifier: Apache-2.0
+use crate::{
+    ast::{
+        Expression, Identifier, PatternStruct, PatternStructField, PostfixExpression, PostfixTerm,
+        PostfixTermAttribute, Primary, SourceBuffer,
+        derive::Derive,
+        prettyprint::{PrettyPrintable, printout_accumulator::PrintoutAccumulator},
+    },
+    grammar::Rule,
+};
+
+// Foo.Bar.Baz is parsed as a plain list of names, but it means the same thing
+// as the equivalent postfix expression, so that is what gets stored
+fn type_from_parse_tree(p: pest::iterators::Pair<'_, Rule>, source: &SourceBuffer) -> Expression {
+    assert!(p.as_rule() == Rule::pattern_struct_type);
+    let loc = source.pointer(From::from(&p.as_span()));
+    let mut names = p
+        .into_inner()
+        .map(|next| Identifier::from_parse_tree(next, source));
+    let base = Primary::Identifier(names.next().expect("need identifier"));
+    let terms = names
+        .map(|id| {
+            PostfixTerm::PostfixTermAttribute(PostfixTermAttribute {
+                loc: id.loc.clone(),
+                id,
+            })
+        })
+        .collect();
+    Expression::from(&PostfixExpression { loc, base, terms })
+}
+
+impl Derive for PatternStruct {
+    fn from_parse_tree(p: pest::iterators::Pair<'_, Rule>, source: &SourceBuffer) -> Self {
+        assert!(p.as_rule() == Rule::pattern_struct);
+        let loc = From::from(&p.as_span());
+        let mut ty = None;
+        let mut fields = vec![];
+        for next in p.into_inner() {
+            match next.as_rule() {
+                Rule::pattern_struct_type => ty = Some(type_from_parse_tree(next, source)),
+                Rule::pattern_struct_field => {
+                    fields.push(PatternStructField::from_parse_tree(next, source))
+                }
+                _ => panic!("invalid struct pattern entry"),
+            }
+        }
+        Self {
+            loc: source.pointer(loc),
+            ty,
+            fields,
+        }
+    }
+}
+
+impl PrettyPrintable for PatternStruct {
+    fn prettyprint(&self, buffer: PrintoutAccumulator) -> PrintoutAccumulator {
+        let buffer = if let Some(ty) = &self.ty {
+            buffer << ty << " "
+        } else {
+            buffer
+        };
+        (buffer << "{").write_separated_list(&self.fields, ", ") << "}"
+    }
+}
@@ -0,0 +1,37 @@
+// SPDX-License-Identifier: Apache-2.0
+use crate::{
+    ast::{
+        Identifier, Pattern, PatternStructField, SourceBuffer,
+        derive::Derive,
+        prettyprint::{PrettyPrintable, printout_accumulator::PrintoutAccumulator},
+    },
+    grammar::Rule,
+};
+
+impl Derive for PatternStructField {
+    fn from_parse_tree(p: pest::iterators::Pair<'_, Rule>, source: &SourceBuffer) -> Self {
+        assert!(p.as_rule() == Rule::pattern_struct_field);
+        let loc = From::from(&p.as_span());
+        let mut inner = p.into_inner();
+        let name = Identifier::from_parse_tree(inner.next().expect("need identifier"), source);
+        let pattern = inner
+            .next()
+            .map(|next| Pattern::from_parse_tree(next, source));
+        Self {
+            loc: source.pointer(loc),
+            name,
+            pattern,
+        }
+    }
+}
+
+impl PrettyPrintable for PatternStructField {
+    fn prettyprint(&self, buffer: PrintoutAccumulator) -> PrintoutAccumulator {
+        let buffer = buffer << &self.name;
+        if let Some(pattern) = &self.pattern {
+            buffer << ": " << pattern
+        } else {
+            buffer
+        }
+    }
+}
@@ -0,0 +1,25 @@
+// SPDX-License-Identifier: Apache-2.0
+use crate::{
+    ast::{
+        PatternWildcard, SourceBuffer,
+        derive::Derive,
+        prettyprint::{PrettyPrintable, printout_accumulator::PrintoutAccumulator},
+    },
+    grammar::Rule,
+};
+
+impl Derive for PatternWildcard {
+    fn from_parse_tree(p: pest::iterators::Pair<'_, Rule>, source: &SourceBuffer) -> Self {
+        assert!(p.as_rule() == Rule::pattern_wildcard);
+        let loc = From::from(&p.as_span());
+        Self {
+            loc: source.pointer(loc),
+        }
+    }
+}
+
+impl PrettyPrintable for PatternWildcard {
+    fn prettyprint(&self, buffer: PrintoutAccumulator) -> PrintoutAccumulator {
+        buffer << "_"
+    }
+}
@@ -10,7 +10,7 @@ use crate::{
 
 use crate::ast::{
     PostfixTermAttribute, PostfixTermCall, PostfixTermEnumCase, PostfixTermIndex,
-    PostfixTermObjectWrite, PostfixTermTryProtocol,
+    PostfixTermObjectWrite, PostfixTermSafeNav, PostfixTermSlice, PostfixTermTryProtocol,
 };
 
 impl Derive for PostfixTerm {
@@ -18,9 +18,11 @@ impl Derive for PostfixTerm {
         postfix_term;
         (postfix_term_attrib, PostfixTermAttribute),
         (postfix_term_index, PostfixTermIndex),
+        (postfix_term_slice, PostfixTermSlice),
         (postfix_term_call, PostfixTermCall),
         (postfix_term_enum_case, PostfixTermEnumCase),
         (postfix_term_object_write, PostfixTermObjectWrite),
+        (postfix_term_safe_nav, PostfixTermSafeNav),
         (postfix_term_try_protocol, PostfixTermTryProtocol)
     );
 }
@@ -30,9 +32,11 @@ impl PrettyPrintable for PostfixTerm {
         match self {
             Self::PostfixTermAttribute(a) => a.prettyprint(buffer),
             Self::PostfixTermIndex(i) => i.prettyprint(buffer),
+            Self::PostfixTermSlice(s) => s.prettyprint(buffer),
             Self::PostfixTermCall(c) => c.prettyprint(buffer),
             Self::PostfixTermEnumCase(c) => c.prettyprint(buffer),
             Self::PostfixTermObjectWrite(w) => w.prettyprint(buffer),
+            Self::PostfixTermSafeNav(s) => s.prettyprint(buffer),
             Self::PostfixTermTryProtocol(t) => t.prettyprint(buffer),
         }
     }
@@ -1,7 +1,7 @@
 // SPDX-License-Identifier: Apache-2.0
 use crate::{
     ast::{
-        ExpressionList, PostfixTermCall, SourceBuffer,
+        ExpressionList, KeywordArgument, PostfixTermCall, SourceBuffer,
         derive::Derive,
         prettyprint::{PrettyPrintable, printout_accumulator::PrintoutAccumulator},
     },
@@ -12,22 +12,36 @@ impl Derive for PostfixTermCall {
     fn from_parse_tree(p: pest::iterators::Pair<'_, Rule>, source: &SourceBuffer) -> Self {
         assert!(p.as_rule() == Rule::postfix_term_call);
         let loc = From::from(&p.as_span());
-        let mut inner = p.into_inner();
-        let args = inner.find(|val| val.as_rule() == Rule::expr_list);
-        let args = if let Some(el) = args {
-            ExpressionList::from_parse_tree(el, source)
-        } else {
-            ExpressionList::empty(source.pointer(loc))
-        };
+        let mut args = None;
+        let mut keyword_args = vec![];
+        for item in p.into_inner() {
+            match item.as_rule() {
+                Rule::call_expr_list => args = Some(ExpressionList::from_parse_tree(item, source)),
+                Rule::keyword_arg_list => {
+                    keyword_args = item
+                        .into_inner()
+                        .map(|kw| KeywordArgument::from_parse_tree(kw, source))
+                        .collect();
+                }
+                _ => panic!("unexpected call argument"),
+            }
+        }
         Self {
             loc: source.pointer(loc),
-            args,
+            args: args.unwrap_or_else(|| ExpressionList::empty(source.pointer(loc))),
+            keyword_args,
         }
     }
 }
 
 impl PrettyPrintable for PostfixTermCall {
     fn prettyprint(&self, buffer: PrintoutAccumulator) -> PrintoutAccumulator {
-        buffer << "(" << &self.args << ")"
+        let buffer = buffer << "(" << &self.args;
+        let buffer = if !self.args.expressions.is_empty() && !self.keyword_args.is_empty() {
+            buffer << ","
+        } else {
+            buffer
+        };
+        buffer.write_separated_list(&self.keyword_args, ",") << ")"
     }
 }
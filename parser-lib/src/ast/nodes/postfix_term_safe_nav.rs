@@ -0,0 +1,47 @@
+// SPDX-License-Identifier: Apache-2.0
+use crate::{
+    ast::{
+        PostfixTerm, PostfixTermAttribute, PostfixTermCall, PostfixTermIndex, PostfixTermSafeNav,
+        PostfixTermSlice, SourceBuffer,
+        derive::Derive,
+        prettyprint::{PrettyPrintable, printout_accumulator::PrintoutAccumulator},
+    },
+    grammar::Rule,
+};
+
+impl Derive for PostfixTermSafeNav {
+    fn from_parse_tree(p: pest::iterators::Pair<'_, Rule>, source: &SourceBuffer) -> Self {
+        assert!(p.as_rule() == Rule::postfix_term_safe_nav);
+        let loc = From::from(&p.as_span());
+        let inner = p.into_inner().next().expect("need postfix term");
+        let term = match inner.as_rule() {
+            Rule::postfix_term_attrib => PostfixTerm::PostfixTermAttribute(
+                PostfixTermAttribute::from_parse_tree(inner, source),
+            ),
+            Rule::postfix_term_index => {
+                PostfixTerm::PostfixTermIndex(PostfixTermIndex::from_parse_tree(inner, source))
+            }
+            Rule::postfix_term_slice => {
+                PostfixTerm::PostfixTermSlice(PostfixTermSlice::from_parse_tree(inner, source))
+            }
+            Rule::postfix_term_call => {
+                PostfixTerm::PostfixTermCall(PostfixTermCall::from_parse_tree(inner, source))
+            }
+            _ => panic!("attribute, index or call expected"),
+        };
+
+        Self {
+            loc: source.pointer(loc),
+            term: Box::new(term),
+        }
+    }
+}
+
+impl PrettyPrintable for PostfixTermSafeNav {
+    fn prettyprint(&self, buffer: PrintoutAccumulator) -> PrintoutAccumulator {
+        match self.term.as_ref() {
+            PostfixTerm::PostfixTermAttribute(_) => buffer << "?" << self.term.as_ref(),
+            _ => buffer << "?." << self.term.as_ref(),
+        }
+    }
+}
@@ -0,0 +1,55 @@
+// SPDX-License-Identifier: Apache-2.0
+use crate::{
+    ast::{
+        Expression, PostfixTermSlice, SourceBuffer,
+        derive::Derive,
+        prettyprint::{PrettyPrintable, printout_accumulator::PrintoutAccumulator},
+    },
+    grammar::Rule,
+};
+
+impl Derive for PostfixTermSlice {
+    fn from_parse_tree(p: pest::iterators::Pair<'_, Rule>, source: &SourceBuffer) -> Self {
+        assert!(p.as_rule() == Rule::postfix_term_slice);
+        let loc = From::from(&p.as_span());
+        let mut start = None;
+        let mut stop = None;
+        let mut step = None;
+        for bound in p.into_inner() {
+            let rule = bound.as_rule();
+            let expr = Expression::from_parse_tree(
+                bound.into_inner().next().expect("need expression"),
+                source,
+            );
+            match rule {
+                Rule::slice_start => start = Some(expr),
+                Rule::slice_stop => stop = Some(expr),
+                Rule::slice_step => step = Some(expr),
+                _ => panic!("unexpected slice bound"),
+            }
+        }
+        Self {
+            loc: source.pointer(loc),
+            start,
+            stop,
+            step,
+        }
+    }
+}
+
+impl PrettyPrintable for PostfixTermSlice {
+    fn prettyprint(&self, buffer: PrintoutAccumulator) -> PrintoutAccumulator {
+        let mut buffer = buffer << "[";
+        if let Some(start) = &self.start {
+            buffer = buffer << start;
+        }
+        buffer = buffer << ":";
+        if let Some(stop) = &self.stop {
+            buffer = buffer << stop;
+        }
+        if let Some(step) = &self.step {
+            buffer = buffer << ":" << step;
+        }
+        buffer << "]"
+    }
+}
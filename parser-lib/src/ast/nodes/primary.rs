@@ -8,34 +8,49 @@ use crate::{
     gen_from_options,
 };
 
+use crate::ast::AwaitExpression;
 use crate::ast::FloatLiteral;
+use crate::ast::FormatStringLiteral;
+use crate::ast::GeneratorExpression;
 use crate::ast::Identifier;
 use crate::ast::IntLiteral;
+use crate::ast::ListComprehension;
 use crate::ast::ListLiteral;
+use crate::ast::MapLiteral;
 use crate::ast::ParenExpression;
 use crate::ast::StringLiteral;
 
 impl Derive for Primary {
     gen_from_options!(
         primary;
+        (await_expr, AwaitExpression),
         (int_literal, IntLiteral),
         (fp_literal, FloatLiteral),
         (identifier, Identifier),
         (list_literal, ListLiteral),
+        (list_comprehension, ListComprehension),
+        (map_literal, MapLiteral),
         (str_literal, StringLiteral),
-        (paren_expr, ParenExpression)
+        (format_str_literal, FormatStringLiteral),
+        (paren_expr, ParenExpression),
+        (generator_expr, GeneratorExpression)
     );
 }
 
 impl PrettyPrintable for Primary {
     fn prettyprint(&self, buffer: PrintoutAccumulator) -> PrintoutAccumulator {
         match self {
+            Self::AwaitExpression(ae) => ae.prettyprint(buffer),
             Self::IntLiteral(il) => il.prettyprint(buffer),
             Self::FloatLiteral(fp) => fp.prettyprint(buffer),
             Self::Identifier(id) => id.prettyprint(buffer),
             Self::ListLiteral(ll) => ll.prettyprint(buffer),
+            Self::ListComprehension(lc) => lc.prettyprint(buffer),
+            Self::MapLiteral(ml) => ml.prettyprint(buffer),
             Self::StringLiteral(sl) => sl.prettyprint(buffer),
+            Self::FormatStringLiteral(fl) => fl.prettyprint(buffer),
             Self::ParenExpression(pe) => pe.prettyprint(buffer),
+            Self::GeneratorExpression(ge) => ge.prettyprint(buffer),
         }
     }
 }
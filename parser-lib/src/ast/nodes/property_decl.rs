@@ -0,0 +1,122 @@
+// SPDX-License-Identifier: Apache-2.0
+use crate::{
+    ast::{
+        ArgumentDecl, ArgumentList, CodeBlock, Expression, FunctionBody, Identifier, MethodAccess,
+        MethodDecl, PropertyDecl, ReturnStatement, SourceBuffer, Statement,
+        derive::Derive,
+        prettyprint::{PrettyPrintable, printout_accumulator::PrintoutAccumulator},
+    },
+    grammar::Rule,
+};
+
+fn accessor(
+    name: &Identifier,
+    loc: pest::Span<'_>,
+    args: ArgumentList,
+    code: CodeBlock,
+    source: &SourceBuffer,
+) -> MethodDecl {
+    MethodDecl {
+        loc: source.pointer(From::from(&loc)),
+        access: MethodAccess::Instance,
+        is_async: false,
+        name: name.clone(),
+        args,
+        body: FunctionBody { code },
+    }
+}
+
+fn getter_from_parse_tree(
+    p: pest::iterators::Pair<'_, Rule>,
+    name: &Identifier,
+    source: &SourceBuffer,
+) -> MethodDecl {
+    assert!(p.as_rule() == Rule::prop_get);
+    let span = p.as_span();
+    let body = p.into_inner().next().expect("need getter body");
+    let code = match body.as_rule() {
+        Rule::code_block => CodeBlock::from_parse_tree(body, source),
+        Rule::expression => {
+            let expr = Expression::from_parse_tree(body, source);
+            CodeBlock {
+                loc: expr.loc().clone(),
+                entries: vec![Statement::ReturnStatement(ReturnStatement {
+                    loc: expr.loc().clone(),
+                    val: Some(expr),
+                })],
+            }
+        }
+        _ => panic!("Unexpected rule for getter body: {body:?}"),
+    };
+    let args = ArgumentList::empty(source.pointer(From::from(&span)));
+    accessor(name, span, args, code, source)
+}
+
+fn setter_from_parse_tree(
+    p: pest::iterators::Pair<'_, Rule>,
+    name: &Identifier,
+    source: &SourceBuffer,
+) -> MethodDecl {
+    assert!(p.as_rule() == Rule::prop_set);
+    let span = p.as_span();
+    let mut inner = p.into_inner();
+    let arg = inner.next().expect("need setter argument");
+    let args = ArgumentList {
+        loc: source.pointer(From::from(&arg.as_span())),
+        names: vec![ArgumentDecl::from_parse_tree(arg, source)],
+        vararg: false,
+    };
+    let body = inner.next().expect("need setter body");
+    let code = match body.as_rule() {
+        Rule::code_block => CodeBlock::from_parse_tree(body, source),
+        Rule::statement => match Statement::from_parse_tree(body, source) {
+            Statement::CodeBlock(cb) => cb,
+            stmt => CodeBlock {
+                loc: stmt.loc().clone(),
+                entries: vec![stmt],
+            },
+        },
+        _ => panic!("Unexpected rule for setter body: {body:?}"),
+    };
+    accessor(name, span, args, code, source)
+}
+
+impl Derive for PropertyDecl {
+    fn from_parse_tree(p: pest::iterators::Pair<'_, Rule>, source: &SourceBuffer) -> Self {
+        assert!(p.as_rule() == Rule::prop_decl);
+        let loc = From::from(&p.as_span());
+        let mut inner = p.into_inner();
+        inner.next().expect("need prop keyword");
+        let name = Identifier::from_parse_tree(inner.next().expect("need identifier"), source);
+        let mut getter = None;
+        let mut setter = None;
+        for accessor in inner {
+            match accessor.as_rule() {
+                Rule::prop_get => getter = Some(getter_from_parse_tree(accessor, &name, source)),
+                Rule::prop_set => setter = Some(setter_from_parse_tree(accessor, &name, source)),
+                _ => panic!("Unexpected rule for property accessor: {accessor:?}"),
+            }
+        }
+        Self {
+            loc: source.pointer(loc),
+            name,
+            getter,
+            setter,
+        }
+    }
+}
+
+impl PrettyPrintable for PropertyDecl {
+    fn prettyprint(&self, buffer: PrintoutAccumulator) -> PrintoutAccumulator {
+        let buffer = buffer << "prop " << &self.name << " {";
+        let buffer = match &self.getter {
+            Some(getter) => buffer << " get => " << &getter.body,
+            None => buffer,
+        };
+        let buffer = match &self.setter {
+            Some(setter) => buffer << " set(" << &setter.args << ") => " << &setter.body,
+            None => buffer,
+        };
+        buffer << " }"
+    }
+}
@@ -0,0 +1,34 @@
+// SPDX-License-Identifier: Apache-2.0
+use crate::{
+    ast::{
+        ArgumentList, Identifier, RequireDecl, SourceBuffer,
+        derive::Derive,
+        prettyprint::{PrettyPrintable, printout_accumulator::PrintoutAccumulator},
+    },
+    grammar::Rule,
+};
+
+impl Derive for RequireDecl {
+    fn from_parse_tree(p: pest::iterators::Pair<'_, Rule>, source: &SourceBuffer) -> Self {
+        assert!(p.as_rule() == Rule::require_decl);
+        let loc = From::from(&p.as_span());
+        let mut inner = p.into_inner();
+        inner.next().expect("need require keyword");
+        let name = Identifier::from_parse_tree(inner.next().expect("need identifier"), source);
+        let args = match inner.next() {
+            Some(p) => ArgumentList::from_parse_tree(p, source),
+            None => ArgumentList::empty(source.pointer(loc)),
+        };
+        Self {
+            loc: source.pointer(loc),
+            name,
+            args,
+        }
+    }
+}
+
+impl PrettyPrintable for RequireDecl {
+    fn prettyprint(&self, buffer: PrintoutAccumulator) -> PrintoutAccumulator {
+        buffer << "require func " << &self.name << "(" << &self.args << ");"
+    }
+}
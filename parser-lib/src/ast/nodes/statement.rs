@@ -4,7 +4,7 @@ use crate::{
         AssertStatement, AssignStatement, BreakStatement, CodeBlock, ContinueStatement, EnumDecl,
         ExpressionStatement, ForStatement, FunctionDecl, IfStatement, MatchStatement,
         ReturnStatement, Statement, StructDecl, ThrowStatement, TryBlock, ValDeclStatement,
-        WhileStatement, WriteOpEqStatement,
+        ValDestructureStatement, WhileStatement, WriteOpEqStatement, YieldStatement,
         derive::Derive,
         prettyprint::{PrettyPrintable, printout_accumulator::PrintoutAccumulator},
     },
@@ -27,8 +27,10 @@ impl Derive for Statement {
         (try_block, TryBlock),
         (val_add_eq_write, WriteOpEqStatement),
         (val_decl_stmt, ValDeclStatement),
+        (val_destructure_stmt, ValDestructureStatement),
         (val_write_stmt, AssignStatement),
         (while_stmt, WhileStatement),
+        (yield_stmt, YieldStatement),
         (struct_decl, StructDecl),
         (enum_decl, EnumDecl),
         (function_decl, FunctionDecl),
@@ -39,6 +41,7 @@ impl PrettyPrintable for Statement {
     fn prettyprint(&self, buffer: PrintoutAccumulator) -> PrintoutAccumulator {
         match self {
             Self::ValDeclStatement(v) => v.prettyprint(buffer),
+            Self::ValDestructureStatement(v) => v.prettyprint(buffer),
             Self::AssignStatement(a) => a.prettyprint(buffer),
             Self::WriteOpEqStatement(w) => w.prettyprint(buffer),
             Self::IfStatement(i) => i.prettyprint(buffer),
@@ -48,6 +51,7 @@ impl PrettyPrintable for Statement {
             Self::CodeBlock(c) => c.prettyprint(buffer),
             Self::ReturnStatement(r) => r.prettyprint(buffer),
             Self::ThrowStatement(t) => t.prettyprint(buffer),
+            Self::YieldStatement(y) => y.prettyprint(buffer),
             Self::TryBlock(t) => t.prettyprint(buffer),
             Self::AssertStatement(a) => a.prettyprint(buffer),
             Self::ExpressionStatement(e) => e.prettyprint(buffer),
@@ -1,8 +1,8 @@
 // SPDX-License-Identifier: Apache-2.0
 use crate::{
     ast::{
-        EnumDecl, MethodDecl, MixinIncludeDecl, OperatorDecl, SourceBuffer, StructDecl,
-        StructEntry, ValDeclStatement,
+        EnumDecl, MethodDecl, MixinIncludeDecl, OperatorDecl, PropertyDecl, SourceBuffer,
+        StructDecl, StructEntry, ValDeclStatement,
         derive::Derive,
         prettyprint::{PrettyPrintable, printout_accumulator::PrintoutAccumulator},
     },
@@ -30,6 +30,9 @@ impl Derive for StructEntry {
             Rule::mixin_include_decl => {
                 Self::MixinInclude(Box::new(MixinIncludeDecl::from_parse_tree(content, source)))
             }
+            Rule::prop_decl => {
+                Self::Property(Box::new(PropertyDecl::from_parse_tree(content, source)))
+            }
             _ => panic!("invalid struct entry kind"),
         }
     }
@@ -44,6 +47,7 @@ impl PrettyPrintable for StructEntry {
             Self::Struct(s) => s.prettyprint(buffer),
             Self::Enum(e) => e.prettyprint(buffer),
             Self::MixinInclude(m) => m.prettyprint(buffer),
+            Self::Property(p) => p.prettyprint(buffer),
         }
     }
 }
@@ -1,19 +1,49 @@
 // SPDX-License-Identifier: Apache-2.0
 use crate::{
     ast::{
-        CodeBlock, Identifier, TryBlock,
+        CatchPiece, CodeBlock, FinallyPiece, SourceBuffer, TryBlock,
         derive::Derive,
         prettyprint::{PrettyPrintable, printout_accumulator::PrintoutAccumulator},
     },
-    gen_from_components,
+    grammar::Rule,
 };
 
 impl Derive for TryBlock {
-    gen_from_components!(try_block; body: CodeBlock, id: Identifier, catch: CodeBlock);
+    fn from_parse_tree(p: pest::iterators::Pair<'_, Rule>, source: &SourceBuffer) -> Self {
+        assert!(p.as_rule() == Rule::try_block);
+        let loc = From::from(&p.as_span());
+        let mut inner = p.into_inner();
+        let body = CodeBlock::from_parse_tree(inner.next().expect("need body"), source);
+        let mut catch = None;
+        let mut finally = None;
+        for next in inner {
+            match next.as_rule() {
+                Rule::catch_piece => catch = Some(CatchPiece::from_parse_tree(next, source)),
+                Rule::finally_piece => finally = Some(FinallyPiece::from_parse_tree(next, source)),
+                _ => panic!("invalid try block"),
+            }
+        }
+        Self {
+            loc: source.pointer(loc),
+            body,
+            catch,
+            finally,
+        }
+    }
 }
 
 impl PrettyPrintable for TryBlock {
     fn prettyprint(&self, buffer: PrintoutAccumulator) -> PrintoutAccumulator {
-        buffer << "try " << &self.body << " catch (" << &self.id << ") " << &self.catch
+        let buffer = buffer << "try " << &self.body;
+        let buffer = if let Some(catch) = &self.catch {
+            buffer << " " << catch
+        } else {
+            buffer
+        };
+        if let Some(finally) = &self.finally {
+            buffer << " " << finally
+        } else {
+            buffer
+        }
     }
 }
@@ -1,7 +1,7 @@
 // SPDX-License-Identifier: Apache-2.0
 use crate::{
     ast::{
-        CodeBlock, ElsePiece, Expression, WhileStatement,
+        CodeBlock, ElsePiece, Expression, Identifier, WhileStatement,
         derive::Derive,
         prettyprint::{PrettyPrintable, printout_accumulator::PrintoutAccumulator},
     },
@@ -16,6 +16,17 @@ impl Derive for WhileStatement {
         assert!(p.as_rule() == Rule::while_stmt);
         let loc = From::from(&p.as_span());
         let mut inner = p.into_inner();
+        let label = if inner.peek().expect("need condition").as_rule() == Rule::loop_label {
+            let label = inner
+                .next()
+                .unwrap()
+                .into_inner()
+                .next()
+                .expect("need label");
+            Some(Identifier::from_parse_tree(label, source))
+        } else {
+            None
+        };
         let cond = Expression::from_parse_tree(inner.next().expect("need condition"), source);
         let then = CodeBlock::from_parse_tree(inner.next().expect("need then block"), source);
         let els = inner
@@ -24,6 +35,7 @@ impl Derive for WhileStatement {
 
         Self {
             loc: source.pointer(loc),
+            label,
             cond,
             then,
             els,
@@ -33,6 +45,10 @@ impl Derive for WhileStatement {
 
 impl PrettyPrintable for WhileStatement {
     fn prettyprint(&self, buffer: PrintoutAccumulator) -> PrintoutAccumulator {
+        let buffer = match &self.label {
+            Some(label) => buffer << label << ": ",
+            None => buffer,
+        };
         buffer << "while " << &self.cond << &self.then
     }
 }
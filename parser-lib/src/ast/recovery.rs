@@ -0,0 +1,167 @@
+// SPDX-License-Identifier: Apache-2.0
+use std::ops::Range;
+
+use pest::Parser;
+
+use crate::{
+    ast::{Location, ParserError, SourceBuffer},
+    grammar::{HaxbyParser, Rule},
+};
+
+// pest stops at the first syntax error; to find the ones after it, the
+// statement or declaration that failed is blanked out with whitespace (which
+// keeps every offset in the module intact) and the module is parsed again,
+// until it parses or there is nothing left to blank out
+
+// the offsets of the ; { and } that delimit statements and declarations,
+// skipping over those in strings and comments
+fn delimiters(src: &str) -> Vec<(usize, u8)> {
+    let bytes = src.as_bytes();
+    let mut ret = vec![];
+    let mut i = 0;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'#' => {
+                while i < bytes.len() && bytes[i] != b'\n' {
+                    i += 1;
+                }
+            }
+            quote @ (b'"' | b'\'') => {
+                i += 1;
+                while i < bytes.len() && bytes[i] != quote {
+                    i += 1;
+                }
+            }
+            c @ (b';' | b'{' | b'}') => ret.push((i, c)),
+            _ => {}
+        }
+        i += 1;
+    }
+    ret
+}
+
+// whether the code after a closing brace still belongs to the same statement,
+// as in `} else {` or `}, 3);`, rather than starting the next one
+fn continues_after_brace(rest: &str) -> bool {
+    let rest = rest.trim_start();
+    match rest.chars().next() {
+        None | Some('{' | '}' | '#') => false,
+        Some(c) if c.is_alphabetic() || c == '_' => {
+            let word = rest
+                .split(|c: char| !(c.is_alphanumeric() || c == '_'))
+                .next();
+            matches!(word, Some("else" | "elsif" | "catch" | "finally"))
+        }
+        Some(_) => true,
+    }
+}
+
+// from the delimiter that precedes the statement (skipping over any braces it
+// contains before the error, as in a lambda passed as an argument) up to the ;
+// or the closing brace that ends it; a closing brace that belongs to the
+// enclosing block is left alone
+fn failed_statement_range(src: &str, pos: usize) -> Range<usize> {
+    let delims = delimiters(src);
+
+    let mut depth = 0usize;
+    let mut start = 0;
+    for &(i, c) in delims.iter().rev().filter(|(i, _)| *i < pos) {
+        match c {
+            b'}' => depth += 1,
+            b'{' if depth > 0 => depth -= 1,
+            b';' if depth > 0 => {}
+            _ => {
+                start = i + 1;
+                break;
+            }
+        }
+    }
+
+    let mut depth = 0usize;
+    for &(i, c) in delims.iter().filter(|(i, _)| *i >= pos) {
+        match c {
+            b';' if depth == 0 => return start..i + 1,
+            b'{' => depth += 1,
+            b'}' if depth == 0 => return start..i,
+            b'}' => {
+                depth -= 1;
+                if depth == 0 && !continues_after_brace(&src[i + 1..]) {
+                    return start..i + 1;
+                }
+            }
+            _ => {}
+        }
+    }
+
+    start..src.len()
+}
+
+// replaces everything but line breaks with spaces of the same byte length;
+// returns false if there was no code to remove
+fn blank_out(src: &mut String, range: Range<usize>) -> bool {
+    let code = &src[range.clone()];
+    if code.chars().all(char::is_whitespace) {
+        return false;
+    }
+
+    let blank = code
+        .chars()
+        .map(|c| match c {
+            '\n' => "\n".to_owned(),
+            _ => " ".repeat(c.len_utf8()),
+        })
+        .collect::<String>();
+    src.replace_range(range, &blank);
+    true
+}
+
+// blanks out the statement in which the parser failed at pos or, if that is
+// only whitespace (as for a stray closing brace), the character at pos itself
+fn blank_out_failed_statement(src: &mut String, pos: usize) -> bool {
+    let range = failed_statement_range(src, pos);
+    if blank_out(src, range) {
+        return true;
+    }
+
+    match src[pos..].chars().next() {
+        Some(c) => blank_out(src, pos..pos + c.len_utf8()),
+        None => false,
+    }
+}
+
+fn to_parser_error(source: &SourceBuffer, err: &pest::error::Error<Rule>) -> ParserError {
+    ParserError {
+        loc: source.pointer(Location::from(&err.location)),
+        msg: err.variant.message().to_string(),
+    }
+}
+
+// each error costs a parse of the whole module, so a file that is mostly not
+// Aria would take quadratic time to go through; past this many errors, the
+// rest of the module is not looked at
+const MAX_SYNTAX_ERRORS: usize = 100;
+
+// the syntax errors in the module (up to MAX_SYNTAX_ERRORS of them), in
+// source order, given the first one
+pub(super) fn collect_syntax_errors(
+    source: &SourceBuffer,
+    first: pest::error::Error<Rule>,
+) -> Vec<ParserError> {
+    let mut src = source.as_str();
+    let mut errors = vec![to_parser_error(source, &first)];
+
+    let mut pos = Location::from(&first.location).start;
+    while errors.len() < MAX_SYNTAX_ERRORS && blank_out_failed_statement(&mut src, pos) {
+        match HaxbyParser::parse(Rule::module, &src) {
+            Ok(_) => break,
+            Err(err) => {
+                pos = Location::from(&err.location).start;
+                errors.push(to_parser_error(source, &err));
+            }
+        }
+    }
+
+    errors.sort_by_key(|e| e.loc.location.start);
+    errors.dedup_by_key(|e| e.loc.location.start);
+    errors
+}
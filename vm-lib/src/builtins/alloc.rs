@@ -28,6 +28,7 @@ impl BuiltinFunctionImpl for Alloc {
                     BVK::Integer => RuntimeValue::Integer(0.into()),
                     BVK::Float => RuntimeValue::Float(0.0.into()),
                     BVK::List => RuntimeValue::List(crate::runtime_value::list::List::from(&[])),
+                    BVK::Map => RuntimeValue::Map(crate::runtime_value::map::Map::default()),
                     BVK::String => RuntimeValue::String("".into()),
                     BVK::Type => return Err(VmErrorReason::UnexpectedType.into()),
                 };
@@ -0,0 +1,276 @@
+// SPDX-License-Identifier: Apache-2.0
+use std::{cell::RefCell, rc::Rc};
+
+use haxby_opcodes::function_attribs::FUNC_IS_METHOD;
+
+use crate::{
+    error::{exception::VmException, vm_error::VmErrorReason},
+    frame::Frame,
+    runtime_value::{
+        RuntimeValue,
+        function::{BuiltinFunctionImpl, Function},
+        kind::RuntimeValueType,
+        object::Object,
+        opaque::OpaqueValue,
+        structure::Struct,
+    },
+    symbol::INTERNED_ATTR_IMPL,
+    vm::{ExecutionResult, RunloopExit, VirtualMachine},
+};
+
+use super::VmGlobals;
+
+enum CoroutineState {
+    // not run yet, so there is no await to hand a value to
+    Created(Frame),
+    Suspended(Frame),
+    Running,
+    Returned(RuntimeValue),
+    Failed,
+}
+
+struct CoroutineImpl {
+    func: Function,
+    state: CoroutineState,
+}
+
+pub(crate) fn create_coroutine(
+    func: Function,
+    frame: Frame,
+    vm: &mut VirtualMachine,
+) -> ExecutionResult<RuntimeValue> {
+    let coroutine_struct = vm
+        .globals
+        .load_named_value("Coroutine")
+        .ok_or(VmErrorReason::UnexpectedVmState)?;
+    let coroutine_struct = coroutine_struct
+        .as_struct()
+        .ok_or(VmErrorReason::UnexpectedVmState)?;
+
+    let obj = RuntimeValue::Object(Object::new(coroutine_struct));
+    let imp = CoroutineImpl {
+        func,
+        state: CoroutineState::Created(frame),
+    };
+    obj.write_attribute(
+        INTERNED_ATTR_IMPL,
+        RuntimeValue::Opaque(OpaqueValue::new(RefCell::new(imp))),
+        &mut vm.globals,
+    )
+    .map_err(|_| VmErrorReason::UnexpectedVmState)?;
+    Ok(obj)
+}
+
+fn extract_impl(
+    frame: &mut Frame,
+    vm: &mut VirtualMachine,
+) -> ExecutionResult<Rc<RefCell<CoroutineImpl>>> {
+    let aria_this = VmGlobals::extract_arg(frame, |x: RuntimeValue| x.as_object().cloned())?;
+    let coroutine_impl = aria_this
+        .read(&vm.globals, INTERNED_ATTR_IMPL)
+        .ok_or(VmErrorReason::UnexpectedVmState)?;
+    coroutine_impl
+        .as_opaque_concrete::<RefCell<CoroutineImpl>>()
+        .ok_or(VmErrorReason::UnexpectedVmState.into())
+}
+
+// what the coroutine is resumed with: a value for its pending await to
+// evaluate to, or an error to throw from there
+enum Resumption {
+    Value(RuntimeValue),
+    Error(RuntimeValue),
+}
+
+// runs the coroutine up to its next await, and pushes Maybe::Some(awaited
+// value) if it suspends there, or Maybe::None once it has returned
+fn resume(
+    coroutine_impl: &RefCell<CoroutineImpl>,
+    resumption: Resumption,
+    frame: &mut Frame,
+    vm: &mut VirtualMachine,
+) -> ExecutionResult<RunloopExit> {
+    // the frame is moved out while the coroutine runs, like for generators
+    let (func, mut co_frame) = {
+        let mut imp = coroutine_impl.borrow_mut();
+        match std::mem::replace(&mut imp.state, CoroutineState::Running) {
+            CoroutineState::Created(co_frame) => match resumption {
+                Resumption::Value(_) => (imp.func.clone(), co_frame),
+                Resumption::Error(e) => {
+                    imp.state = CoroutineState::Failed;
+                    vm.release_frame(co_frame);
+                    return Ok(RunloopExit::Exception(VmException::from_value(e)));
+                }
+            },
+            CoroutineState::Suspended(mut co_frame) => match resumption {
+                Resumption::Value(v) => {
+                    co_frame.stack.push(v);
+                    (imp.func.clone(), co_frame)
+                }
+                Resumption::Error(e) => match co_frame.drop_to_first_try(vm) {
+                    Some(catch_offset) => {
+                        co_frame.resume_at = Some(catch_offset as usize);
+                        co_frame.stack.push(e);
+                        (imp.func.clone(), co_frame)
+                    }
+                    None => {
+                        imp.state = CoroutineState::Failed;
+                        vm.release_frame(co_frame);
+                        return Ok(RunloopExit::Exception(VmException::from_value(e)));
+                    }
+                },
+            },
+            CoroutineState::Running => {
+                return Err(VmErrorReason::OperationFailed(
+                    "coroutine is already running".to_owned(),
+                )
+                .into());
+            }
+            state @ (CoroutineState::Returned(_) | CoroutineState::Failed) => {
+                imp.state = state;
+                return Err(VmErrorReason::OperationFailed(
+                    "coroutine has already finished".to_owned(),
+                )
+                .into());
+            }
+        }
+    };
+
+    let result = func.resume_in_frame(&mut co_frame, vm);
+    match result {
+        Ok(RunloopExit::Ok(_)) if co_frame.is_suspended() => {
+            let val = co_frame.stack.try_pop().ok_or(VmErrorReason::EmptyStack)?;
+            coroutine_impl.borrow_mut().state = CoroutineState::Suspended(co_frame);
+            frame.stack.push(vm.globals.create_maybe_some(val)?);
+            Ok(RunloopExit::Ok(()))
+        }
+        Ok(RunloopExit::Ok(_)) => {
+            let val = co_frame.stack.try_pop().ok_or(VmErrorReason::EmptyStack)?;
+            coroutine_impl.borrow_mut().state = CoroutineState::Returned(val);
+            vm.release_frame(co_frame);
+            frame.stack.push(vm.globals.create_maybe_none()?);
+            Ok(RunloopExit::Ok(()))
+        }
+        Ok(RunloopExit::Exception(e)) => {
+            coroutine_impl.borrow_mut().state = CoroutineState::Failed;
+            vm.release_frame(co_frame);
+            Ok(RunloopExit::Exception(e))
+        }
+        Err(e) => {
+            coroutine_impl.borrow_mut().state = CoroutineState::Failed;
+            vm.release_frame(co_frame);
+            Err(e)
+        }
+    }
+}
+
+#[derive(Default)]
+struct SendValue {}
+impl BuiltinFunctionImpl for SendValue {
+    fn eval(&self, frame: &mut Frame, vm: &mut VirtualMachine) -> ExecutionResult<RunloopExit> {
+        let coroutine_impl = extract_impl(frame, vm)?;
+        let value = VmGlobals::extract_arg(frame, Some)?;
+        resume(&coroutine_impl, Resumption::Value(value), frame, vm)
+    }
+
+    fn attrib_byte(&self) -> u8 {
+        FUNC_IS_METHOD
+    }
+
+    fn arity(&self) -> crate::arity::Arity {
+        crate::arity::Arity::required(2)
+    }
+
+    fn name(&self) -> &str {
+        "send"
+    }
+}
+
+#[derive(Default)]
+struct SendError {}
+impl BuiltinFunctionImpl for SendError {
+    fn eval(&self, frame: &mut Frame, vm: &mut VirtualMachine) -> ExecutionResult<RunloopExit> {
+        let coroutine_impl = extract_impl(frame, vm)?;
+        let error = VmGlobals::extract_arg(frame, Some)?;
+        resume(&coroutine_impl, Resumption::Error(error), frame, vm)
+    }
+
+    fn attrib_byte(&self) -> u8 {
+        FUNC_IS_METHOD
+    }
+
+    fn arity(&self) -> crate::arity::Arity {
+        crate::arity::Arity::required(2)
+    }
+
+    fn name(&self) -> &str {
+        "send_error"
+    }
+}
+
+#[derive(Default)]
+struct IsDone {}
+impl BuiltinFunctionImpl for IsDone {
+    fn eval(&self, frame: &mut Frame, vm: &mut VirtualMachine) -> ExecutionResult<RunloopExit> {
+        let coroutine_impl = extract_impl(frame, vm)?;
+        let is_done = matches!(
+            coroutine_impl.borrow().state,
+            CoroutineState::Returned(_) | CoroutineState::Failed
+        );
+        frame.stack.push(RuntimeValue::Boolean(is_done.into()));
+        Ok(RunloopExit::Ok(()))
+    }
+
+    fn attrib_byte(&self) -> u8 {
+        FUNC_IS_METHOD
+    }
+
+    fn arity(&self) -> crate::arity::Arity {
+        crate::arity::Arity::required(1)
+    }
+
+    fn name(&self) -> &str {
+        "is_done"
+    }
+}
+
+#[derive(Default)]
+struct ReturnValue {}
+impl BuiltinFunctionImpl for ReturnValue {
+    fn eval(&self, frame: &mut Frame, vm: &mut VirtualMachine) -> ExecutionResult<RunloopExit> {
+        let coroutine_impl = extract_impl(frame, vm)?;
+        match &coroutine_impl.borrow().state {
+            CoroutineState::Returned(val) => {
+                frame.stack.push(val.clone());
+                Ok(RunloopExit::Ok(()))
+            }
+            _ => Err(VmErrorReason::OperationFailed(
+                "coroutine has not returned a value".to_owned(),
+            )
+            .into()),
+        }
+    }
+
+    fn attrib_byte(&self) -> u8 {
+        FUNC_IS_METHOD
+    }
+
+    fn arity(&self) -> crate::arity::Arity {
+        crate::arity::Arity::required(1)
+    }
+
+    fn name(&self) -> &str {
+        "return_value"
+    }
+}
+
+pub(super) fn insert_coroutine_builtins(builtins: &mut VmGlobals) {
+    let coroutine_struct = Struct::new("Coroutine");
+    coroutine_struct.insert_builtin::<SendValue>(builtins);
+    coroutine_struct.insert_builtin::<SendError>(builtins);
+    coroutine_struct.insert_builtin::<IsDone>(builtins);
+    coroutine_struct.insert_builtin::<ReturnValue>(builtins);
+    builtins.insert(
+        "Coroutine",
+        RuntimeValue::Type(RuntimeValueType::Struct(coroutine_struct)),
+    );
+}
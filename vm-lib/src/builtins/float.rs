@@ -1,12 +1,13 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use haxby_opcodes::function_attribs::FUNC_IS_METHOD;
+use num_traits::FromPrimitive;
 
 use crate::{
     frame::Frame,
     runtime_value::{
-        RuntimeValue, function::BuiltinFunctionImpl, kind::RuntimeValueType,
-        rust_native_type::RustNativeType,
+        RuntimeValue, function::BuiltinFunctionImpl, integer::int_from_bigint,
+        kind::RuntimeValueType, rust_native_type::RustNativeType,
     },
     vm::RunloopExit,
 };
@@ -103,8 +104,11 @@ impl BuiltinFunctionImpl for FpInt {
         _: &mut crate::vm::VirtualMachine,
     ) -> crate::vm::ExecutionResult<RunloopExit> {
         let this = VmGlobals::extract_arg(frame, |x| x.as_float().cloned())?;
-        let iv = *this.raw_value() as i64;
-        frame.stack.push(RuntimeValue::Integer(iv.into()));
+        let fv = *this.raw_value();
+        match num_bigint::BigInt::from_f64(fv) {
+            Some(n) => frame.stack.push(int_from_bigint(n)),
+            None => frame.stack.push(RuntimeValue::Integer((fv as i64).into())),
+        }
         Ok(RunloopExit::Ok(()))
     }
 
@@ -171,6 +175,10 @@ impl BuiltinFunctionImpl for FpPrettyprint {
     fn name(&self) -> &str {
         "prettyprint"
     }
+
+    fn argument_names(&self) -> &[&str] {
+        &["format"]
+    }
 }
 
 pub(super) fn insert_float_builtins(builtins: &mut VmGlobals) {
@@ -0,0 +1,102 @@
+// SPDX-License-Identifier: Apache-2.0
+use crate::{
+    builtins::VmGlobals,
+    error::vm_error::VmErrorReason,
+    frame::Frame,
+    runtime_value::{RuntimeValue, function::BuiltinFunctionImpl, map::Map},
+    vm::RunloopExit,
+};
+
+#[derive(Default)]
+struct Gc {}
+impl BuiltinFunctionImpl for Gc {
+    fn eval(
+        &self,
+        frame: &mut Frame,
+        _: &mut crate::vm::VirtualMachine,
+    ) -> crate::vm::ExecutionResult<RunloopExit> {
+        let freed = crate::gc::collect() as i64;
+        frame.stack.push(RuntimeValue::Integer(freed.into()));
+        Ok(RunloopExit::Ok(()))
+    }
+
+    fn arity(&self) -> crate::arity::Arity {
+        crate::arity::Arity::zero()
+    }
+
+    fn name(&self) -> &str {
+        "gc"
+    }
+}
+
+#[derive(Default)]
+struct GcStats {}
+impl BuiltinFunctionImpl for GcStats {
+    fn eval(
+        &self,
+        frame: &mut Frame,
+        vm: &mut crate::vm::VirtualMachine,
+    ) -> crate::vm::ExecutionResult<RunloopExit> {
+        let stats = crate::gc::stats();
+        let values = [
+            ("collections", stats.collections),
+            ("freed", stats.freed),
+            ("last_freed", stats.last_freed),
+            ("tracked", stats.tracked),
+            ("allocations", stats.allocations),
+            ("threshold", vm.options.gc_threshold),
+        ];
+
+        let map = Map::new_with_capacity(values.len());
+        for (key, val) in values {
+            let key = RuntimeValue::String(key.to_owned().into());
+            let val = RuntimeValue::Integer((val as i64).into());
+            if let RunloopExit::Exception(e) = map.insert(key, val, frame, vm)? {
+                return Ok(RunloopExit::Exception(e));
+            }
+        }
+
+        frame.stack.push(RuntimeValue::Map(map));
+        Ok(RunloopExit::Ok(()))
+    }
+
+    fn arity(&self) -> crate::arity::Arity {
+        crate::arity::Arity::zero()
+    }
+
+    fn name(&self) -> &str {
+        "gc_stats"
+    }
+}
+
+#[derive(Default)]
+struct GcSetThreshold {}
+impl BuiltinFunctionImpl for GcSetThreshold {
+    fn eval(
+        &self,
+        frame: &mut Frame,
+        vm: &mut crate::vm::VirtualMachine,
+    ) -> crate::vm::ExecutionResult<RunloopExit> {
+        let threshold = VmGlobals::extract_arg(frame, |x| x.as_integer().cloned())?;
+        let threshold = usize::try_from(*threshold.raw_value()).map_err(|_| {
+            VmErrorReason::OperationFailed("gc threshold cannot be negative".to_owned())
+        })?;
+        vm.options.gc_threshold = threshold;
+        frame.stack.push(vm.globals.create_unit_object()?);
+        Ok(RunloopExit::Ok(()))
+    }
+
+    fn arity(&self) -> crate::arity::Arity {
+        crate::arity::Arity::required(1)
+    }
+
+    fn name(&self) -> &str {
+        "gc_set_threshold"
+    }
+}
+
+pub(super) fn insert_builtins(builtins: &mut VmGlobals) {
+    builtins.insert_builtin::<Gc>();
+    builtins.insert_builtin::<GcStats>();
+    builtins.insert_builtin::<GcSetThreshold>();
+}
@@ -0,0 +1,143 @@
+// SPDX-License-Identifier: Apache-2.0
+use std::cell::RefCell;
+
+use haxby_opcodes::function_attribs::FUNC_IS_METHOD;
+
+use crate::{
+    error::vm_error::VmErrorReason,
+    frame::Frame,
+    runtime_value::{
+        RuntimeValue,
+        function::{BuiltinFunctionImpl, Function},
+        kind::RuntimeValueType,
+        object::Object,
+        opaque::OpaqueValue,
+        structure::Struct,
+    },
+    symbol::INTERNED_ATTR_IMPL,
+    vm::{ExecutionResult, RunloopExit, VirtualMachine},
+};
+
+use super::VmGlobals;
+
+enum GeneratorState {
+    Suspended(Frame),
+    Running,
+    Done,
+}
+
+struct GeneratorImpl {
+    func: Function,
+    state: GeneratorState,
+}
+
+pub(crate) fn create_generator(
+    func: Function,
+    frame: Frame,
+    vm: &mut VirtualMachine,
+) -> ExecutionResult<RuntimeValue> {
+    let generator_struct = vm
+        .globals
+        .load_named_value("Generator")
+        .ok_or(VmErrorReason::UnexpectedVmState)?;
+    let generator_struct = generator_struct
+        .as_struct()
+        .ok_or(VmErrorReason::UnexpectedVmState)?;
+
+    let obj = RuntimeValue::Object(Object::new(generator_struct));
+    let imp = GeneratorImpl {
+        func,
+        state: GeneratorState::Suspended(frame),
+    };
+    obj.write_attribute(
+        INTERNED_ATTR_IMPL,
+        RuntimeValue::Opaque(OpaqueValue::new(RefCell::new(imp))),
+        &mut vm.globals,
+    )
+    .map_err(|_| VmErrorReason::UnexpectedVmState)?;
+    Ok(obj)
+}
+
+#[derive(Default)]
+struct Next {}
+impl BuiltinFunctionImpl for Next {
+    fn eval(&self, frame: &mut Frame, vm: &mut VirtualMachine) -> ExecutionResult<RunloopExit> {
+        let aria_this = VmGlobals::extract_arg(frame, |x: RuntimeValue| x.as_object().cloned())?;
+
+        let generator_impl = aria_this
+            .read(&vm.globals, INTERNED_ATTR_IMPL)
+            .ok_or(VmErrorReason::UnexpectedVmState)?;
+        let generator_impl = generator_impl
+            .as_opaque_concrete::<RefCell<GeneratorImpl>>()
+            .ok_or(VmErrorReason::UnexpectedVmState)?;
+
+        // the frame is moved out while the generator runs, so that the body
+        // is free to touch the generator object (and a recursive next() is
+        // caught instead of aliasing the frame)
+        let (func, mut gen_frame) = {
+            let mut imp = generator_impl.borrow_mut();
+            match std::mem::replace(&mut imp.state, GeneratorState::Running) {
+                GeneratorState::Suspended(gen_frame) => (imp.func.clone(), gen_frame),
+                GeneratorState::Running => {
+                    return Err(VmErrorReason::OperationFailed(
+                        "generator is already running".to_owned(),
+                    )
+                    .into());
+                }
+                GeneratorState::Done => {
+                    imp.state = GeneratorState::Done;
+                    frame.stack.push(vm.globals.create_maybe_none()?);
+                    return Ok(RunloopExit::Ok(()));
+                }
+            }
+        };
+
+        let result = func.resume_in_frame(&mut gen_frame, vm);
+        match result {
+            Ok(RunloopExit::Ok(_)) if gen_frame.is_suspended() => {
+                let val = gen_frame.stack.try_pop().ok_or(VmErrorReason::EmptyStack)?;
+                generator_impl.borrow_mut().state = GeneratorState::Suspended(gen_frame);
+                frame.stack.push(vm.globals.create_maybe_some(val)?);
+                Ok(RunloopExit::Ok(()))
+            }
+            // the value of a return statement is not part of the sequence
+            Ok(RunloopExit::Ok(_)) => {
+                generator_impl.borrow_mut().state = GeneratorState::Done;
+                vm.release_frame(gen_frame);
+                frame.stack.push(vm.globals.create_maybe_none()?);
+                Ok(RunloopExit::Ok(()))
+            }
+            Ok(RunloopExit::Exception(e)) => {
+                generator_impl.borrow_mut().state = GeneratorState::Done;
+                vm.release_frame(gen_frame);
+                Ok(RunloopExit::Exception(e))
+            }
+            Err(e) => {
+                generator_impl.borrow_mut().state = GeneratorState::Done;
+                vm.release_frame(gen_frame);
+                Err(e)
+            }
+        }
+    }
+
+    fn attrib_byte(&self) -> u8 {
+        FUNC_IS_METHOD
+    }
+
+    fn arity(&self) -> crate::arity::Arity {
+        crate::arity::Arity::required(1)
+    }
+
+    fn name(&self) -> &str {
+        "next"
+    }
+}
+
+pub(super) fn insert_generator_builtins(builtins: &mut VmGlobals) {
+    let generator_struct = Struct::new("Generator");
+    generator_struct.insert_builtin::<Next>(builtins);
+    builtins.insert(
+        "Generator",
+        RuntimeValue::Type(RuntimeValueType::Struct(generator_struct)),
+    );
+}
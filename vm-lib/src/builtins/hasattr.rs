@@ -17,7 +17,11 @@ impl BuiltinFunctionImpl for HasAttr {
         let the_value = frame.stack.pop();
         let the_string = VmGlobals::extract_arg(frame, |x| x.as_string().cloned())?;
         if let Some(symbol) = vm.globals.lookup_symbol(the_string.raw_value()) {
-            let has_attr = the_value.read_attribute(symbol, &vm.globals).is_ok();
+            let has_attr = the_value.read_attribute(symbol, &vm.globals).is_ok()
+                || the_value
+                    .as_object()
+                    .and_then(|obj| obj.get_struct().property(symbol))
+                    .is_some_and(|property| property.getter.is_some());
             frame.stack.push(RuntimeValue::Boolean(has_attr.into()));
         } else {
             frame.stack.push(RuntimeValue::Boolean(false.into()));
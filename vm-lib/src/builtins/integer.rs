@@ -13,7 +13,10 @@ use crate::{
 
 use super::VmGlobals;
 
-fn int_format(n: i64, fmt: &str) -> String {
+fn int_format<T>(n: &T, fmt: &str) -> String
+where
+    T: std::fmt::Display + std::fmt::LowerHex + std::fmt::UpperHex,
+{
     // Determine if format ends with 'x' or 'X' for hexadecimal formatting
     let (base, digits_spec) = if let Some(stripped) = fmt.strip_suffix('x') {
         (16, (stripped, false)) // lowercase hex
@@ -48,9 +51,16 @@ impl BuiltinFunctionImpl for Prettyprint {
         frame: &mut Frame,
         _: &mut crate::vm::VirtualMachine,
     ) -> crate::vm::ExecutionResult<RunloopExit> {
-        let this = VmGlobals::extract_arg(frame, |x| x.as_integer().cloned())?;
+        let this = VmGlobals::extract_arg(frame, |x| match x {
+            RuntimeValue::Integer(_) | RuntimeValue::BigInteger(_) => Some(x),
+            _ => None,
+        })?;
         let format_style = VmGlobals::extract_arg(frame, |x| x.as_string().cloned())?;
-        let output_string = int_format(*this.raw_value(), format_style.raw_value());
+        let output_string = match this {
+            RuntimeValue::BigInteger(n) => int_format(n.raw_value(), format_style.raw_value()),
+            RuntimeValue::Integer(n) => int_format(n.raw_value(), format_style.raw_value()),
+            _ => unreachable!(),
+        };
         frame.stack.push(RuntimeValue::String(output_string.into()));
         Ok(RunloopExit::Ok(()))
     }
@@ -68,11 +78,64 @@ impl BuiltinFunctionImpl for Prettyprint {
     }
 }
 
+fn wrapped_i64(x: RuntimeValue) -> Option<i64> {
+    match x {
+        RuntimeValue::Integer(n) => Some(*n.raw_value()),
+        RuntimeValue::BigInteger(n) => Some(n.truncate_to_i64()),
+        _ => None,
+    }
+}
+
+// Int promotes to arbitrary precision on overflow; these keep the 64-bit
+// two's complement semantics that hashing and random number generation rely on
+macro_rules! wrapping_op {
+    ($struct_name: ident, $aria_name: literal, $op: expr) => {
+        #[derive(Default)]
+        struct $struct_name {}
+        impl BuiltinFunctionImpl for $struct_name {
+            fn eval(
+                &self,
+                frame: &mut Frame,
+                _: &mut crate::vm::VirtualMachine,
+            ) -> crate::vm::ExecutionResult<RunloopExit> {
+                let this = VmGlobals::extract_arg(frame, wrapped_i64)?;
+                let other = VmGlobals::extract_arg(frame, wrapped_i64)?;
+                let op: fn(i64, i64) -> i64 = $op;
+                let result = op(this, other);
+                frame.stack.push(RuntimeValue::Integer(result.into()));
+                Ok(RunloopExit::Ok(()))
+            }
+
+            fn attrib_byte(&self) -> u8 {
+                FUNC_IS_METHOD
+            }
+
+            fn arity(&self) -> crate::arity::Arity {
+                crate::arity::Arity::required(2)
+            }
+
+            fn name(&self) -> &str {
+                $aria_name
+            }
+        }
+    };
+}
+
+wrapping_op!(WrappingAdd, "wrapping_add", i64::wrapping_add);
+wrapping_op!(WrappingSub, "wrapping_sub", i64::wrapping_sub);
+wrapping_op!(WrappingMul, "wrapping_mul", i64::wrapping_mul);
+wrapping_op!(WrappingShl, "wrapping_shl", |n, by| n
+    .wrapping_shl(by as u32));
+
 pub(super) fn insert_integer_builtins(builtins: &mut VmGlobals) {
     let int_builtin =
         RustNativeType::new(crate::runtime_value::rust_native_type::RustNativeValueKind::Integer);
 
     int_builtin.insert_builtin::<Prettyprint>(builtins);
+    int_builtin.insert_builtin::<WrappingAdd>(builtins);
+    int_builtin.insert_builtin::<WrappingSub>(builtins);
+    int_builtin.insert_builtin::<WrappingMul>(builtins);
+    int_builtin.insert_builtin::<WrappingShl>(builtins);
 
     builtins.register_builtin_type(
         haxby_opcodes::BuiltinTypeId::Int,
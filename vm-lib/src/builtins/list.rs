@@ -170,6 +170,40 @@ impl BuiltinFunctionImpl for OpReadIndex {
     }
 }
 
+#[derive(Default)]
+struct OpReadSlice {}
+impl BuiltinFunctionImpl for OpReadSlice {
+    fn eval(
+        &self,
+        frame: &mut Frame,
+        _: &mut crate::vm::VirtualMachine,
+    ) -> crate::vm::ExecutionResult<RunloopExit> {
+        let this = VmGlobals::extract_arg(frame, |x| x.as_list().cloned())?;
+        let indices = super::slice::extract_slice_indices(frame, this.len())?;
+        let ret = List::new_with_capacity(indices.len());
+        for index in indices {
+            match this.get_at(index) {
+                Some(v) => ret.append(v),
+                None => return Err(VmErrorReason::IndexOutOfBounds(index).into()),
+            }
+        }
+        frame.stack.push(RuntimeValue::List(ret));
+        Ok(RunloopExit::Ok(()))
+    }
+
+    fn attrib_byte(&self) -> u8 {
+        FUNC_IS_METHOD
+    }
+
+    fn arity(&self) -> crate::arity::Arity {
+        crate::arity::Arity::required(4)
+    }
+
+    fn name(&self) -> &str {
+        "_op_impl_read_slice"
+    }
+}
+
 #[derive(Default)]
 struct SetAt {}
 impl BuiltinFunctionImpl for SetAt {
@@ -280,6 +314,7 @@ pub(super) fn insert_list_builtins(builtins: &mut VmGlobals) {
     list_builtin.insert_builtin::<Drop>(builtins);
     list_builtin.insert_builtin::<GetAt>(builtins);
     list_builtin.insert_builtin::<OpReadIndex>(builtins);
+    list_builtin.insert_builtin::<OpReadSlice>(builtins);
     list_builtin.insert_builtin::<SetAt>(builtins);
     list_builtin.insert_builtin::<OpWriteIndex>(builtins);
     list_builtin.insert_builtin::<NewWithCapacity>(builtins);
@@ -0,0 +1,389 @@
+// SPDX-License-Identifier: Apache-2.0
+use haxby_opcodes::function_attribs::{FUNC_IS_METHOD, METHOD_ATTRIBUTE_TYPE};
+
+use crate::{
+    error::vm_error::VmErrorReason,
+    frame::Frame,
+    runtime_value::{
+        RuntimeValue, function::BuiltinFunctionImpl, kind::RuntimeValueType, list::List, map::Map,
+        rust_native_type::RustNativeType,
+    },
+    vm::RunloopExit,
+};
+
+use super::VmGlobals;
+
+#[derive(Default)]
+struct MapLen {}
+impl BuiltinFunctionImpl for MapLen {
+    fn eval(
+        &self,
+        frame: &mut Frame,
+        _: &mut crate::vm::VirtualMachine,
+    ) -> crate::vm::ExecutionResult<RunloopExit> {
+        let this = VmGlobals::extract_arg(frame, |x| x.as_map().cloned())?;
+        let len = this.len() as i64;
+        frame.stack.push(RuntimeValue::Integer(len.into()));
+        Ok(RunloopExit::Ok(()))
+    }
+
+    fn attrib_byte(&self) -> u8 {
+        FUNC_IS_METHOD
+    }
+
+    fn arity(&self) -> crate::arity::Arity {
+        crate::arity::Arity::required(1)
+    }
+
+    fn name(&self) -> &str {
+        "len"
+    }
+}
+
+#[derive(Default)]
+struct MapSet {}
+impl BuiltinFunctionImpl for MapSet {
+    fn eval(
+        &self,
+        frame: &mut Frame,
+        vm: &mut crate::vm::VirtualMachine,
+    ) -> crate::vm::ExecutionResult<RunloopExit> {
+        let this = VmGlobals::extract_arg(frame, |x| x.as_map().cloned())?;
+        let key = frame.stack.pop();
+        let value = frame.stack.pop();
+        match this.insert(key, value, frame, vm)? {
+            RunloopExit::Ok(is_new) => {
+                frame.stack.push(RuntimeValue::Boolean(is_new.into()));
+                Ok(RunloopExit::Ok(()))
+            }
+            RunloopExit::Exception(e) => Ok(RunloopExit::Exception(e)),
+        }
+    }
+
+    fn attrib_byte(&self) -> u8 {
+        FUNC_IS_METHOD
+    }
+
+    fn arity(&self) -> crate::arity::Arity {
+        crate::arity::Arity::required(3)
+    }
+
+    fn name(&self) -> &str {
+        "set"
+    }
+}
+
+#[derive(Default)]
+struct MapGet {}
+impl BuiltinFunctionImpl for MapGet {
+    fn eval(
+        &self,
+        frame: &mut Frame,
+        vm: &mut crate::vm::VirtualMachine,
+    ) -> crate::vm::ExecutionResult<RunloopExit> {
+        let this = VmGlobals::extract_arg(frame, |x| x.as_map().cloned())?;
+        let key = frame.stack.pop();
+        match this.get(&key, frame, vm)? {
+            RunloopExit::Ok(Some(v)) => {
+                frame.stack.push(vm.globals.create_maybe_some(v)?);
+                Ok(RunloopExit::Ok(()))
+            }
+            RunloopExit::Ok(None) => {
+                frame.stack.push(vm.globals.create_maybe_none()?);
+                Ok(RunloopExit::Ok(()))
+            }
+            RunloopExit::Exception(e) => Ok(RunloopExit::Exception(e)),
+        }
+    }
+
+    fn attrib_byte(&self) -> u8 {
+        FUNC_IS_METHOD
+    }
+
+    fn arity(&self) -> crate::arity::Arity {
+        crate::arity::Arity::required(2)
+    }
+
+    fn name(&self) -> &str {
+        "get"
+    }
+}
+
+#[derive(Default)]
+struct MapContains {}
+impl BuiltinFunctionImpl for MapContains {
+    fn eval(
+        &self,
+        frame: &mut Frame,
+        vm: &mut crate::vm::VirtualMachine,
+    ) -> crate::vm::ExecutionResult<RunloopExit> {
+        let this = VmGlobals::extract_arg(frame, |x| x.as_map().cloned())?;
+        let key = frame.stack.pop();
+        match this.get(&key, frame, vm)? {
+            RunloopExit::Ok(v) => {
+                frame.stack.push(RuntimeValue::Boolean(v.is_some().into()));
+                Ok(RunloopExit::Ok(()))
+            }
+            RunloopExit::Exception(e) => Ok(RunloopExit::Exception(e)),
+        }
+    }
+
+    fn attrib_byte(&self) -> u8 {
+        FUNC_IS_METHOD
+    }
+
+    fn arity(&self) -> crate::arity::Arity {
+        crate::arity::Arity::required(2)
+    }
+
+    fn name(&self) -> &str {
+        "contains"
+    }
+}
+
+#[derive(Default)]
+struct MapRemove {}
+impl BuiltinFunctionImpl for MapRemove {
+    fn eval(
+        &self,
+        frame: &mut Frame,
+        vm: &mut crate::vm::VirtualMachine,
+    ) -> crate::vm::ExecutionResult<RunloopExit> {
+        let this = VmGlobals::extract_arg(frame, |x| x.as_map().cloned())?;
+        let key = frame.stack.pop();
+        match this.remove(&key, frame, vm)? {
+            RunloopExit::Ok(removed) => {
+                frame.stack.push(RuntimeValue::Boolean(removed.into()));
+                Ok(RunloopExit::Ok(()))
+            }
+            RunloopExit::Exception(e) => Ok(RunloopExit::Exception(e)),
+        }
+    }
+
+    fn attrib_byte(&self) -> u8 {
+        FUNC_IS_METHOD
+    }
+
+    fn arity(&self) -> crate::arity::Arity {
+        crate::arity::Arity::required(2)
+    }
+
+    fn name(&self) -> &str {
+        "remove"
+    }
+}
+
+#[derive(Default)]
+struct MapKeys {}
+impl BuiltinFunctionImpl for MapKeys {
+    fn eval(
+        &self,
+        frame: &mut Frame,
+        _: &mut crate::vm::VirtualMachine,
+    ) -> crate::vm::ExecutionResult<RunloopExit> {
+        let this = VmGlobals::extract_arg(frame, |x| x.as_map().cloned())?;
+        frame
+            .stack
+            .push(RuntimeValue::List(List::from(&this.keys())));
+        Ok(RunloopExit::Ok(()))
+    }
+
+    fn attrib_byte(&self) -> u8 {
+        FUNC_IS_METHOD
+    }
+
+    fn arity(&self) -> crate::arity::Arity {
+        crate::arity::Arity::required(1)
+    }
+
+    fn name(&self) -> &str {
+        "keys"
+    }
+}
+
+#[derive(Default)]
+struct OpReadIndex {}
+impl BuiltinFunctionImpl for OpReadIndex {
+    fn eval(
+        &self,
+        frame: &mut Frame,
+        vm: &mut crate::vm::VirtualMachine,
+    ) -> crate::vm::ExecutionResult<RunloopExit> {
+        let this = VmGlobals::extract_arg(frame, |x| x.as_map().cloned())?;
+        let key = frame.stack.pop();
+        match this.get(&key, frame, vm)? {
+            RunloopExit::Ok(Some(v)) => {
+                frame.stack.push(v);
+                Ok(RunloopExit::Ok(()))
+            }
+            RunloopExit::Ok(None) => {
+                Err(VmErrorReason::OperationFailed(format!("key {key:?} not found in map")).into())
+            }
+            RunloopExit::Exception(e) => Ok(RunloopExit::Exception(e)),
+        }
+    }
+
+    fn attrib_byte(&self) -> u8 {
+        FUNC_IS_METHOD
+    }
+
+    fn arity(&self) -> crate::arity::Arity {
+        crate::arity::Arity::required(2)
+    }
+
+    fn name(&self) -> &str {
+        "_op_impl_read_index"
+    }
+}
+
+#[derive(Default)]
+struct OpWriteIndex {}
+impl BuiltinFunctionImpl for OpWriteIndex {
+    fn eval(
+        &self,
+        frame: &mut Frame,
+        vm: &mut crate::vm::VirtualMachine,
+    ) -> crate::vm::ExecutionResult<RunloopExit> {
+        let this = VmGlobals::extract_arg(frame, |x| x.as_map().cloned())?;
+        let key = frame.stack.pop();
+        let value = frame.stack.pop();
+        match this.insert(key, value, frame, vm)? {
+            RunloopExit::Ok(_) => {
+                frame.stack.push(vm.globals.create_unit_object()?);
+                Ok(RunloopExit::Ok(()))
+            }
+            RunloopExit::Exception(e) => Ok(RunloopExit::Exception(e)),
+        }
+    }
+
+    fn attrib_byte(&self) -> u8 {
+        FUNC_IS_METHOD
+    }
+
+    fn arity(&self) -> crate::arity::Arity {
+        crate::arity::Arity::required(3)
+    }
+
+    fn name(&self) -> &str {
+        "_op_impl_write_index"
+    }
+}
+
+// used by the Aria side of Map to iterate: returns Maybe::Some([position, key, value])
+// for the first entry at or after the given position, Maybe::None past the last one
+#[derive(Default)]
+struct NextEntry {}
+impl BuiltinFunctionImpl for NextEntry {
+    fn eval(
+        &self,
+        frame: &mut Frame,
+        vm: &mut crate::vm::VirtualMachine,
+    ) -> crate::vm::ExecutionResult<RunloopExit> {
+        let this = VmGlobals::extract_arg(frame, |x| x.as_map().cloned())?;
+        let position = *VmGlobals::extract_arg(frame, |x| x.as_integer().cloned())?.raw_value();
+        let position = position.max(0) as u64;
+        let ret = match this.next_entry(position) {
+            Some((position, key, value)) => {
+                vm.globals
+                    .create_maybe_some(RuntimeValue::List(List::from(&[
+                        RuntimeValue::Integer((position as i64).into()),
+                        key,
+                        value,
+                    ])))?
+            }
+            None => vm.globals.create_maybe_none()?,
+        };
+        frame.stack.push(ret);
+        Ok(RunloopExit::Ok(()))
+    }
+
+    fn attrib_byte(&self) -> u8 {
+        FUNC_IS_METHOD
+    }
+
+    fn arity(&self) -> crate::arity::Arity {
+        crate::arity::Arity::required(2)
+    }
+
+    fn name(&self) -> &str {
+        "_next_entry"
+    }
+}
+
+#[derive(Default)]
+struct New {}
+impl BuiltinFunctionImpl for New {
+    fn eval(
+        &self,
+        frame: &mut Frame,
+        _: &mut crate::vm::VirtualMachine,
+    ) -> crate::vm::ExecutionResult<RunloopExit> {
+        let _ = frame.stack.pop(); // ignore Map type, we know who we are
+        frame.stack.push(RuntimeValue::Map(Map::default()));
+        Ok(RunloopExit::Ok(()))
+    }
+
+    fn attrib_byte(&self) -> u8 {
+        FUNC_IS_METHOD | METHOD_ATTRIBUTE_TYPE
+    }
+
+    fn arity(&self) -> crate::arity::Arity {
+        crate::arity::Arity::required(1)
+    }
+
+    fn name(&self) -> &str {
+        "new"
+    }
+}
+
+#[derive(Default)]
+struct NewWithCapacity {}
+impl BuiltinFunctionImpl for NewWithCapacity {
+    fn eval(
+        &self,
+        frame: &mut Frame,
+        _: &mut crate::vm::VirtualMachine,
+    ) -> crate::vm::ExecutionResult<RunloopExit> {
+        let _ = frame.stack.pop(); // ignore Map type, we know who we are
+        let capacity = *VmGlobals::extract_arg(frame, |x| x.as_integer().cloned())?.raw_value();
+        let capacity = if capacity < 0 { 0 } else { capacity } as usize;
+        frame
+            .stack
+            .push(RuntimeValue::Map(Map::new_with_capacity(capacity)));
+        Ok(RunloopExit::Ok(()))
+    }
+
+    fn attrib_byte(&self) -> u8 {
+        FUNC_IS_METHOD | METHOD_ATTRIBUTE_TYPE
+    }
+
+    fn arity(&self) -> crate::arity::Arity {
+        crate::arity::Arity::required(2)
+    }
+
+    fn name(&self) -> &str {
+        "new_with_capacity"
+    }
+}
+
+pub(super) fn insert_map_builtins(builtins: &mut VmGlobals) {
+    let map_builtin =
+        RustNativeType::new(crate::runtime_value::rust_native_type::RustNativeValueKind::Map);
+
+    map_builtin.insert_builtin::<MapLen>(builtins);
+    map_builtin.insert_builtin::<MapSet>(builtins);
+    map_builtin.insert_builtin::<MapGet>(builtins);
+    map_builtin.insert_builtin::<MapContains>(builtins);
+    map_builtin.insert_builtin::<MapRemove>(builtins);
+    map_builtin.insert_builtin::<MapKeys>(builtins);
+    map_builtin.insert_builtin::<OpReadIndex>(builtins);
+    map_builtin.insert_builtin::<OpWriteIndex>(builtins);
+    map_builtin.insert_builtin::<NextEntry>(builtins);
+    map_builtin.insert_builtin::<New>(builtins);
+    map_builtin.insert_builtin::<NewWithCapacity>(builtins);
+
+    builtins.register_builtin_type(
+        haxby_opcodes::BuiltinTypeId::Map,
+        RuntimeValueType::RustNative(map_builtin),
+    );
+}
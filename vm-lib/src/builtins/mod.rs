@@ -1,5 +1,5 @@
 // SPDX-License-Identifier: Apache-2.0
-use std::rc::Rc;
+use std::{collections::HashSet, rc::Rc};
 
 use haxby_opcodes::BuiltinTypeId;
 
@@ -20,13 +20,17 @@ mod alloc;
 mod arity;
 mod boolean;
 mod cmdline_args;
+pub(crate) mod coroutine;
 mod exit;
 mod float;
+mod gc;
+pub(crate) mod generator;
 mod getenv;
 mod hasattr;
 mod integer;
 mod list;
 mod listattrs;
+mod map;
 mod maybe;
 pub mod native_iterator;
 mod now;
@@ -39,6 +43,7 @@ mod result;
 pub(crate) mod runtime_error;
 mod setenv;
 mod sleep;
+mod slice;
 mod string;
 mod system;
 mod typ;
@@ -144,8 +149,11 @@ impl Default for VmGlobals {
         arity::insert_builtins(&mut this);
         boolean::insert_boolean_builtins(&mut this);
         cmdline_args::insert_builtins(&mut this);
+        coroutine::insert_coroutine_builtins(&mut this);
         exit::insert_builtins(&mut this);
         float::insert_float_builtins(&mut this);
+        gc::insert_builtins(&mut this);
+        generator::insert_generator_builtins(&mut this);
         getenv::insert_builtins(&mut this);
         hasattr::insert_builtins(&mut this);
         list::insert_list_builtins(&mut this);
@@ -160,6 +168,7 @@ impl Default for VmGlobals {
         sleep::insert_builtins(&mut this);
         system::insert_builtins(&mut this);
         typ::insert_type_builtins(&mut this);
+        map::insert_map_builtins(&mut this); // Map's type id comes right after Type
         typeof_builtin::insert_builtins(&mut this);
         writeattr::insert_builtins(&mut this);
 
@@ -187,6 +196,14 @@ impl VmGlobals {
         self.values.read(self, sym)
     }
 
+    pub fn list_named_values(&self) -> HashSet<String> {
+        self.values
+            .list_attributes(self)
+            .into_iter()
+            .filter_map(|sym| self.resolve_symbol(sym).map(str::to_owned))
+            .collect()
+    }
+
     pub fn insert(&mut self, name: &str, val: RuntimeValue) {
         let sym = self.intern_symbol(name).expect("too many symbols interned");
         let values = Rc::clone(&self.values);
@@ -1,7 +1,10 @@
 // SPDX-License-Identifier: Apache-2.0
 use crate::{
-    builtins::VmGlobals, error::vm_error::VmErrorReason, frame::Frame,
-    runtime_value::function::BuiltinFunctionImpl, vm::RunloopExit,
+    builtins::VmGlobals,
+    error::vm_error::VmErrorReason,
+    frame::Frame,
+    runtime_value::{CallResult, function::BuiltinFunctionImpl},
+    vm::RunloopExit,
 };
 
 #[derive(Default)]
@@ -14,14 +17,26 @@ impl BuiltinFunctionImpl for ReadAttr {
     ) -> crate::vm::ExecutionResult<RunloopExit> {
         let the_value = frame.stack.pop();
         let the_string = VmGlobals::extract_arg(frame, |x| x.as_string().cloned())?;
-        if let Some(symbol) = vm.globals.lookup_symbol(the_string.raw_value())
-            && let Ok(the_attr) = the_value.read_attribute(symbol, &vm.globals)
-        {
-            frame.stack.push(the_attr);
-            Ok(RunloopExit::Ok(()))
-        } else {
-            Err(VmErrorReason::NoSuchIdentifier(the_string.raw_value().to_owned()).into())
+        if let Some(symbol) = vm.globals.lookup_symbol(the_string.raw_value()) {
+            if let Ok(the_attr) = the_value.read_attribute(symbol, &vm.globals) {
+                frame.stack.push(the_attr);
+                return Ok(RunloopExit::Ok(()));
+            }
+            if let Some(getter) = the_value
+                .as_object()
+                .and_then(|obj| obj.get_struct().property(symbol))
+                .and_then(|property| property.getter)
+            {
+                return match the_value.bind(getter).eval(0, frame, vm, true)? {
+                    CallResult::Ok(the_attr) => {
+                        frame.stack.push(the_attr);
+                        Ok(RunloopExit::Ok(()))
+                    }
+                    CallResult::Exception(e) => Ok(RunloopExit::Exception(e)),
+                };
+            }
         }
+        Err(VmErrorReason::NoSuchIdentifier(the_string.raw_value().to_owned()).into())
     }
 
     fn arity(&self) -> crate::arity::Arity {
@@ -15,13 +15,15 @@ use crate::{
 use super::VmGlobals;
 
 pub const RUNTIME_ERR_CASE_DIVISION_BY_ZERO_IDX: usize = 0;
-pub const RUNTIME_ERR_CASE_ENUM_WITHOUT_PAYLOAD_IDX: usize = 1;
-pub const RUNTIME_ERR_CASE_INDEX_OUT_OF_BOUNDS_IDX: usize = 2;
-pub const RUNTIME_ERR_CASE_MISMATCHED_ARGC_IDX: usize = 3;
-pub const RUNTIME_ERR_CASE_NO_SUCH_CASE_IDX: usize = 4;
-pub const RUNTIME_ERR_CASE_NO_SUCH_IDENTIFIER_IDX: usize = 5;
-pub const RUNTIME_ERR_CASE_OPERATION_FAILED_IDX: usize = 6;
-pub const RUNTIME_ERR_CASE_UNEXPECTED_TYPE_IDX: usize = 7;
+pub const RUNTIME_ERR_CASE_DUPLICATE_ARGUMENT_IDX: usize = 1;
+pub const RUNTIME_ERR_CASE_ENUM_WITHOUT_PAYLOAD_IDX: usize = 2;
+pub const RUNTIME_ERR_CASE_INDEX_OUT_OF_BOUNDS_IDX: usize = 3;
+pub const RUNTIME_ERR_CASE_MISMATCHED_ARGC_IDX: usize = 4;
+pub const RUNTIME_ERR_CASE_NO_SUCH_ARGUMENT_IDX: usize = 5;
+pub const RUNTIME_ERR_CASE_NO_SUCH_CASE_IDX: usize = 6;
+pub const RUNTIME_ERR_CASE_NO_SUCH_IDENTIFIER_IDX: usize = 7;
+pub const RUNTIME_ERR_CASE_OPERATION_FAILED_IDX: usize = 9;
+pub const RUNTIME_ERR_CASE_UNEXPECTED_TYPE_IDX: usize = 10;
 
 pub(super) fn insert_runtime_error_builtins(builtins: &mut VmGlobals) {
     let argc_mismatch = Struct::new("ArgcMismatch");
@@ -30,6 +32,9 @@ pub(super) fn insert_runtime_error_builtins(builtins: &mut VmGlobals) {
     let division_by_zero_sym = builtins
         .intern_symbol("DivisionByZero")
         .expect("too many symbols interned");
+    let duplicate_argument_sym = builtins
+        .intern_symbol("DuplicateArgument")
+        .expect("too many symbols interned");
     let enum_without_payload_sym = builtins
         .intern_symbol("EnumWithoutPayload")
         .expect("too many symbols interned");
@@ -39,12 +44,18 @@ pub(super) fn insert_runtime_error_builtins(builtins: &mut VmGlobals) {
     let mismatched_argc_sym = builtins
         .intern_symbol("MismatchedArgumentCount")
         .expect("too many symbols interned");
+    let no_such_argument_sym = builtins
+        .intern_symbol("NoSuchArgument")
+        .expect("too many symbols interned");
     let no_such_case_sym = builtins
         .intern_symbol("NoSuchCase")
         .expect("too many symbols interned");
     let no_such_identifier_sym = builtins
         .intern_symbol("NoSuchIdentifier")
         .expect("too many symbols interned");
+    let non_exhaustive_match_sym = builtins
+        .intern_symbol("NonExhaustiveMatch")
+        .expect("too many symbols interned");
     let operation_failed_sym = builtins
         .intern_symbol("OperationFailed")
         .expect("too many symbols interned");
@@ -59,6 +70,10 @@ pub(super) fn insert_runtime_error_builtins(builtins: &mut VmGlobals) {
                 name: division_by_zero_sym,
                 payload_type: None,
             },
+            EnumCase {
+                name: duplicate_argument_sym,
+                payload_type: Some(IsaCheckable::Type(str.clone())),
+            },
             EnumCase {
                 name: enum_without_payload_sym,
                 payload_type: None,
@@ -73,6 +88,10 @@ pub(super) fn insert_runtime_error_builtins(builtins: &mut VmGlobals) {
                     argc_mismatch.clone(),
                 ))),
             },
+            EnumCase {
+                name: no_such_argument_sym,
+                payload_type: Some(IsaCheckable::Type(str.clone())),
+            },
             EnumCase {
                 name: no_such_case_sym,
                 payload_type: Some(IsaCheckable::Type(str.clone())),
@@ -81,6 +100,10 @@ pub(super) fn insert_runtime_error_builtins(builtins: &mut VmGlobals) {
                 name: no_such_identifier_sym,
                 payload_type: Some(IsaCheckable::Type(str.clone())),
             },
+            EnumCase {
+                name: non_exhaustive_match_sym,
+                payload_type: None,
+            },
             EnumCase {
                 name: operation_failed_sym,
                 payload_type: Some(IsaCheckable::Type(str.clone())),
@@ -0,0 +1,53 @@
+// SPDX-License-Identifier: Apache-2.0
+use crate::{error::vm_error::VmErrorReason, frame::Frame, runtime_value::RuntimeValue};
+
+use super::VmGlobals;
+
+// a slice bound is passed as Maybe[Int]; Maybe::None means the bound was left open
+fn extract_bound(frame: &mut Frame) -> crate::vm::ExecutionResult<Option<i64>> {
+    let bound = VmGlobals::extract_arg(frame, |x| x.as_enum_value().cloned())?;
+    match (bound.get_case_index(), bound.get_payload()) {
+        (0, Some(RuntimeValue::Integer(n))) => Ok(Some(*n.raw_value())),
+        (1, None) => Ok(None),
+        _ => Err(VmErrorReason::UnexpectedType.into()),
+    }
+}
+
+// pops the start, stop and step bounds of a slice and resolves them against a
+// sequence of the given length, returning the indices that the slice selects;
+// negative bounds count from the end, and out of range bounds are clamped
+pub(super) fn extract_slice_indices(
+    frame: &mut Frame,
+    len: usize,
+) -> crate::vm::ExecutionResult<Vec<usize>> {
+    let start = extract_bound(frame)?;
+    let stop = extract_bound(frame)?;
+    let step = extract_bound(frame)?.unwrap_or(1);
+    if step == 0 {
+        return Err(VmErrorReason::OperationFailed("slice step cannot be zero".to_owned()).into());
+    }
+
+    let len = len as i64;
+    let (lower, upper) = if step > 0 { (0, len) } else { (-1, len - 1) };
+    let resolve = |bound: Option<i64>, default: i64| match bound {
+        None => default,
+        Some(n) if n < 0 => (n + len).clamp(lower, upper),
+        Some(n) => n.clamp(lower, upper),
+    };
+
+    let mut indices = vec![];
+    if step > 0 {
+        let (mut i, stop) = (resolve(start, lower), resolve(stop, upper));
+        while i < stop {
+            indices.push(i as usize);
+            i = i.saturating_add(step);
+        }
+    } else {
+        let (mut i, stop) = (resolve(start, upper), resolve(stop, lower));
+        while i > stop {
+            indices.push(i as usize);
+            i = i.saturating_add(step);
+        }
+    }
+    Ok(indices)
+}
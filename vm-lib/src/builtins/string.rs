@@ -592,6 +592,35 @@ impl BuiltinFunctionImpl for GetAt {
     }
 }
 
+#[derive(Default)]
+struct OpReadSlice {}
+impl BuiltinFunctionImpl for OpReadSlice {
+    fn eval(
+        &self,
+        frame: &mut Frame,
+        _: &mut crate::vm::VirtualMachine,
+    ) -> crate::vm::ExecutionResult<RunloopExit> {
+        let this = VmGlobals::extract_arg(frame, |x| x.as_string().cloned())?;
+        let chars = this.raw_value().chars().collect::<Vec<_>>();
+        let indices = super::slice::extract_slice_indices(frame, chars.len())?;
+        let ret = indices.into_iter().map(|i| chars[i]).collect::<String>();
+        frame.stack.push(RuntimeValue::String(ret.into()));
+        Ok(RunloopExit::Ok(()))
+    }
+
+    fn attrib_byte(&self) -> u8 {
+        FUNC_IS_METHOD
+    }
+
+    fn arity(&self) -> crate::arity::Arity {
+        crate::arity::Arity::required(4)
+    }
+
+    fn name(&self) -> &str {
+        "_op_impl_read_slice"
+    }
+}
+
 pub(super) fn insert_string_builtins(builtins: &mut VmGlobals) {
     let string_builtin =
         RustNativeType::new(crate::runtime_value::rust_native_type::RustNativeValueKind::String);
@@ -611,6 +640,7 @@ pub(super) fn insert_string_builtins(builtins: &mut VmGlobals) {
     string_builtin.insert_builtin::<Lowercase>(builtins);
     string_builtin.insert_builtin::<Contains>(builtins);
     string_builtin.insert_builtin::<GetAt>(builtins);
+    string_builtin.insert_builtin::<OpReadSlice>(builtins);
 
     builtins.register_builtin_type(
         haxby_opcodes::BuiltinTypeId::String,
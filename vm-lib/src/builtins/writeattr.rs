@@ -1,6 +1,9 @@
 // SPDX-License-Identifier: Apache-2.0
 use crate::{
-    builtins::VmGlobals, frame::Frame, runtime_value::function::BuiltinFunctionImpl,
+    builtins::VmGlobals,
+    error::vm_error::VmErrorReason,
+    frame::Frame,
+    runtime_value::{CallResult, function::BuiltinFunctionImpl},
     vm::RunloopExit,
 };
 
@@ -16,6 +19,24 @@ impl BuiltinFunctionImpl for WriteAttr {
         let the_string = VmGlobals::extract_arg(frame, |x| x.as_string().cloned())?;
         let the_symbol = vm.globals.intern_symbol(the_string.raw_value())?;
         let the_value = frame.stack.pop();
+        if let Some(property) = the_object
+            .as_object()
+            .and_then(|obj| obj.get_struct().property(the_symbol))
+        {
+            let Some(setter) = property.setter else {
+                return Err(VmErrorReason::OperationFailed(format!(
+                    "property '{}' has no setter",
+                    the_string.raw_value()
+                ))
+                .into());
+            };
+            frame.stack.push(the_value);
+            if let CallResult::Exception(e) = the_object.bind(setter).eval(1, frame, vm, true)? {
+                return Ok(RunloopExit::Exception(e));
+            }
+            frame.stack.push(vm.globals.create_unit_object()?);
+            return Ok(RunloopExit::Ok(()));
+        }
         the_object
             .write_attribute(the_symbol, the_value, &mut vm.globals)
             .map_err(|e| e.to_vm_error_reason(the_string.raw_value()))?;
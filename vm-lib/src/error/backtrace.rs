@@ -5,6 +5,8 @@ use aria_parser::ast::SourcePointer;
 #[derive(Clone, Debug, Default)]
 pub struct Backtrace {
     entries: Vec<SourcePointer>,
+    // callers that tail calls replaced, and so are missing from entries
+    elided_frames: usize,
 }
 
 impl Backtrace {
@@ -20,6 +22,14 @@ impl Backtrace {
         self.entries.push(loc);
     }
 
+    pub fn note_elided_frames(&mut self, count: usize) {
+        self.elided_frames += count;
+    }
+
+    pub fn elided_frames(&self) -> usize {
+        self.elided_frames
+    }
+
     pub fn len(&self) -> usize {
         self.entries.len()
     }
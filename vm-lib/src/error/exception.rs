@@ -10,7 +10,10 @@ use crate::{
         vm_error::{VmError, VmErrorReason},
     },
     runtime_value::{RuntimeValue, list::List, object::Object},
-    symbol::{INTERNED_ATTR_ACTUAL, INTERNED_ATTR_BACKTRACE, INTERNED_ATTR_EXPECTED, Symbol},
+    symbol::{
+        INTERNED_ATTR_ACTUAL, INTERNED_ATTR_BACKTRACE, INTERNED_ATTR_ELIDED_FRAMES,
+        INTERNED_ATTR_EXPECTED, Symbol,
+    },
     vm::VirtualMachine,
 };
 
@@ -71,6 +74,14 @@ impl VmException {
             RuntimeValue::List(bt_list),
             builtins,
         );
+        // frames replaced by tail calls are not in the backtrace, so they
+        // are counted separately
+        let elided_frames = self.backtrace.elided_frames() as i64;
+        let _ = self.value.write_attribute(
+            INTERNED_ATTR_ELIDED_FRAMES,
+            RuntimeValue::Integer(elided_frames.into()),
+            builtins,
+        );
     }
 }
 
@@ -86,8 +97,9 @@ impl VmException {
         }
 
         use crate::builtins::runtime_error::{
-            RUNTIME_ERR_CASE_DIVISION_BY_ZERO_IDX, RUNTIME_ERR_CASE_ENUM_WITHOUT_PAYLOAD_IDX,
-            RUNTIME_ERR_CASE_INDEX_OUT_OF_BOUNDS_IDX, RUNTIME_ERR_CASE_MISMATCHED_ARGC_IDX,
+            RUNTIME_ERR_CASE_DIVISION_BY_ZERO_IDX, RUNTIME_ERR_CASE_DUPLICATE_ARGUMENT_IDX,
+            RUNTIME_ERR_CASE_ENUM_WITHOUT_PAYLOAD_IDX, RUNTIME_ERR_CASE_INDEX_OUT_OF_BOUNDS_IDX,
+            RUNTIME_ERR_CASE_MISMATCHED_ARGC_IDX, RUNTIME_ERR_CASE_NO_SUCH_ARGUMENT_IDX,
             RUNTIME_ERR_CASE_NO_SUCH_CASE_IDX, RUNTIME_ERR_CASE_NO_SUCH_IDENTIFIER_IDX,
             RUNTIME_ERR_CASE_OPERATION_FAILED_IDX, RUNTIME_ERR_CASE_UNEXPECTED_TYPE_IDX,
         };
@@ -106,6 +118,10 @@ impl VmException {
                 case: RUNTIME_ERR_CASE_DIVISION_BY_ZERO_IDX,
                 payload: None,
             },
+            VmErrorReason::DuplicateArgument(s) => ExceptionData {
+                case: RUNTIME_ERR_CASE_DUPLICATE_ARGUMENT_IDX,
+                payload: Some(RuntimeValue::String(s.clone().into())),
+            },
             VmErrorReason::EnumWithoutPayload => ExceptionData {
                 case: RUNTIME_ERR_CASE_ENUM_WITHOUT_PAYLOAD_IDX,
                 payload: None,
@@ -137,6 +153,10 @@ impl VmException {
                     payload: Some(argc_mismatch_obj),
                 }
             }
+            VmErrorReason::NoSuchArgument(s) => ExceptionData {
+                case: RUNTIME_ERR_CASE_NO_SUCH_ARGUMENT_IDX,
+                payload: Some(RuntimeValue::String(s.clone().into())),
+            },
             VmErrorReason::NoSuchCase(s) => ExceptionData {
                 case: RUNTIME_ERR_CASE_NO_SUCH_CASE_IDX,
                 payload: Some(RuntimeValue::String(s.clone().into())),
@@ -179,6 +199,9 @@ impl VmException {
             rt_err.make_value(e_data.case, e_data.payload),
             err
         ));
-        Ok(VmException::from_value_and_loc(exception_value, err.loc))
+        Ok(VmException::from_value_and_loc(
+            exception_value,
+            err.loc.map(|loc| *loc),
+        ))
     }
 }
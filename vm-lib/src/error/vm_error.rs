@@ -48,6 +48,12 @@ pub enum VmErrorReason {
     #[error("cannot import module at path '{0}': {1}")]
     ImportNotAvailable(String, String),
 
+    #[error("module '{0}' is already provided by {1}")]
+    ConflictingBundledModule(String, String),
+
+    #[error("{0} does not provide '{1}', which is required by a mixin it includes")]
+    MixinRequirementNotMet(String, String),
+
     #[error("instruction cannot be fully decoded")]
     IncompleteInstruction,
 
@@ -63,6 +69,12 @@ pub enum VmErrorReason {
     #[error("mismatched argument count, expected {0} actual {1}")]
     MismatchedArgumentCount(usize, usize),
 
+    #[error("argument '{0}' supplied more than once")]
+    DuplicateArgument(String),
+
+    #[error("no argument named '{0}'")]
+    NoSuchArgument(String),
+
     #[error("unknown named identifier: '{0}'")]
     NoSuchIdentifier(String),
 
@@ -122,7 +134,7 @@ impl From<DecodeError> for VmErrorReason {
 pub struct VmError {
     pub reason: VmErrorReason,
     pub opcode: Option<Opcode>,
-    pub loc: Option<SourcePointer>,
+    pub loc: Option<Box<SourcePointer>>,
     pub backtrace: Box<Backtrace>,
 }
 
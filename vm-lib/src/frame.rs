@@ -35,6 +35,17 @@ pub struct Frame {
     pub(crate) locals: Vec<LocalVariable>,
     pub(crate) func: Option<Function>,
     pub argc: u8,
+    // set when a generator suspends, so that the next run continues after the yield
+    pub(crate) resume_at: Option<usize>,
+    // optional arguments left out by a call with keyword arguments; they
+    // are below argc, but get their default value anyway
+    pub(crate) skipped_args: Vec<u8>,
+    // set when `return f(...)` has set f up to run in this frame, in place of
+    // the function that returned
+    pub(crate) pending_tail_call: bool,
+    // how many callers were replaced by tail calls in this frame, which
+    // backtraces cannot show
+    pub(crate) elided_frames: usize,
 }
 
 impl Frame {
@@ -52,6 +63,10 @@ impl Frame {
 }
 
 impl Frame {
+    pub(crate) fn is_suspended(&self) -> bool {
+        self.resume_at.is_some()
+    }
+
     pub fn new_with_function(f: Function) -> Self {
         let mut this = Self::new_with_n_locals(f.frame_size());
         this.set_line_table(f.line_table());
@@ -67,6 +82,10 @@ impl Frame {
             locals: Vec::with_capacity(n as usize),
             func: None,
             argc: 0,
+            resume_at: None,
+            skipped_args: Vec::new(),
+            pending_tail_call: false,
+            elided_frames: 0,
         };
         for _ in 0..n {
             this.locals.push(LocalVariable::default())
@@ -79,6 +98,11 @@ impl Frame {
         self
     }
 
+    // whether the argument at idx was passed by the caller
+    pub(crate) fn is_arg_supplied(&self, idx: u8) -> bool {
+        self.argc > idx && !self.skipped_args.contains(&idx)
+    }
+
     pub(crate) fn set_line_table(&mut self, lt: Option<&LineTable>) -> &mut Self {
         self.line_table = lt.cloned();
         self
@@ -101,6 +125,10 @@ impl Frame {
         self.ctrl_blocks.clear();
         self.func = Some(f.clone());
         self.argc = 0;
+        self.resume_at = None;
+        self.skipped_args.clear();
+        self.pending_tail_call = false;
+        self.elided_frames = 0;
         self.set_line_table(f.line_table());
         let locals = f.frame_size() as usize;
         self.locals.clear();
@@ -113,6 +141,10 @@ impl Frame {
         self.locals.clear();
         self.func = None;
         self.argc = 0;
+        self.resume_at = None;
+        self.skipped_args.clear();
+        self.pending_tail_call = false;
+        self.elided_frames = 0;
         self.line_table = None;
         self
     }
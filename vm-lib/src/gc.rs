@@ -0,0 +1,177 @@
+// SPDX-License-Identifier: Apache-2.0
+
+// runtime values are reference counted, which cannot reclaim values that
+// refer to each other; this is a trial deletion cycle collector for them
+//
+// every value that can hold other values (objects, lists, maps, closures,
+// bound functions and enum values) is registered here when it is created.
+// a collection subtracts the references that tracked values hold to each
+// other from their reference counts: whatever is left over comes from
+// outside the tracked heap (modules, frames, the Rust stack, ...), and
+// anything that cannot be reached from such a value is garbage
+//
+// values held by something the collector cannot see into (e.g. an opaque
+// native value) are always assumed to be alive
+
+use std::{
+    cell::RefCell,
+    rc::{Rc, Weak},
+};
+
+use rustc_data_structures::fx::FxHashMap;
+
+use crate::runtime_value::RuntimeValue;
+
+pub const DEFAULT_GC_THRESHOLD: usize = 10_000;
+
+// the registry drops the entries of values that have been freed once it
+// grows past this, even if no collection ever runs
+const MIN_PRUNE_SIZE: usize = 1024;
+
+pub(crate) trait Traced: 'static {
+    // calls visit with the address of each value this value refers to
+    fn visit_children(&self, visit: &mut dyn FnMut(usize));
+    // drops all the references this value holds, to break a cycle
+    fn clear(&self);
+}
+
+pub(crate) fn address_of<T: ?Sized>(rc: &Rc<T>) -> usize {
+    Rc::as_ptr(rc) as *const () as usize
+}
+
+pub(crate) fn visit_value(val: &RuntimeValue, visit: &mut dyn FnMut(usize)) {
+    if let Some(addr) = val.gc_address() {
+        visit(addr);
+    }
+}
+
+#[derive(Clone, Copy, Default, Debug)]
+pub struct GcStats {
+    // number of collections run so far
+    pub collections: usize,
+    // number of values freed by all collections
+    pub freed: usize,
+    // number of values freed by the latest collection
+    pub last_freed: usize,
+    // number of values currently registered with the collector
+    pub tracked: usize,
+    // number of values created since the latest collection
+    pub allocations: usize,
+}
+
+struct GcHeap {
+    tracked: Vec<Weak<dyn Traced>>,
+    prune_at: usize,
+    survivors: usize,
+    stats: GcStats,
+}
+
+impl Default for GcHeap {
+    fn default() -> Self {
+        Self {
+            tracked: Vec::new(),
+            prune_at: MIN_PRUNE_SIZE,
+            survivors: 0,
+            stats: GcStats::default(),
+        }
+    }
+}
+
+impl GcHeap {
+    fn prune(&mut self) {
+        self.tracked.retain(|w| w.strong_count() > 0);
+        self.prune_at = MIN_PRUNE_SIZE.max(self.tracked.len() * 2);
+    }
+}
+
+thread_local! {
+    static HEAP: RefCell<GcHeap> = RefCell::new(GcHeap::default());
+}
+
+pub(crate) fn track<T: Traced>(rc: &Rc<T>) {
+    let weak: Weak<dyn Traced> = Rc::downgrade(rc) as Weak<dyn Traced>;
+    HEAP.with_borrow_mut(|heap| {
+        heap.tracked.push(weak);
+        heap.stats.allocations += 1;
+        if heap.tracked.len() >= heap.prune_at {
+            heap.prune();
+        }
+    });
+}
+
+// a collection runs once the number of values created since the last one
+// reaches the threshold, or the number of values that survived the last one,
+// whichever is larger, so that large heaps are not scanned over and over;
+// a threshold of 0 disables automatic collection
+pub(crate) fn should_collect(threshold: usize) -> bool {
+    threshold != 0
+        && HEAP.with_borrow(|heap| heap.stats.allocations >= threshold.max(heap.survivors))
+}
+
+pub fn stats() -> GcStats {
+    HEAP.with_borrow(|heap| GcStats {
+        tracked: heap.tracked.len(),
+        ..heap.stats
+    })
+}
+
+// runs a full collection, returning the number of values freed
+pub fn collect() -> usize {
+    let live: Vec<Rc<dyn Traced>> = HEAP.with_borrow_mut(|heap| {
+        let live: Vec<_> = heap.tracked.iter().filter_map(|w| w.upgrade()).collect();
+        heap.tracked = live.iter().map(Rc::downgrade).collect();
+        live
+    });
+
+    let index: FxHashMap<usize, usize> = live
+        .iter()
+        .enumerate()
+        .map(|(i, v)| (address_of(v), i))
+        .collect();
+
+    // references from outside of the tracked heap; the one held by `live` is not counted
+    let mut external: Vec<usize> = live.iter().map(|v| Rc::strong_count(v) - 1).collect();
+    for v in &live {
+        v.visit_children(&mut |addr| {
+            if let Some(&i) = index.get(&addr) {
+                external[i] = external[i].saturating_sub(1);
+            }
+        });
+    }
+
+    let mut reachable = vec![false; live.len()];
+    let mut pending: Vec<usize> = (0..live.len()).filter(|&i| external[i] > 0).collect();
+    for &i in &pending {
+        reachable[i] = true;
+    }
+    while let Some(i) = pending.pop() {
+        live[i].visit_children(&mut |addr| {
+            if let Some(&j) = index.get(&addr)
+                && !reachable[j]
+            {
+                reachable[j] = true;
+                pending.push(j);
+            }
+        });
+    }
+
+    let mut freed = 0;
+    for (v, reachable) in live.iter().zip(&reachable) {
+        if !*reachable {
+            v.clear();
+            freed += 1;
+        }
+    }
+    drop(live);
+
+    HEAP.with_borrow_mut(|heap| {
+        heap.prune();
+        heap.survivors = heap.tracked.len();
+        heap.stats.collections += 1;
+        heap.stats.freed += freed;
+        heap.stats.last_freed = freed;
+        heap.stats.allocations = 0;
+    });
+
+    freed
+}
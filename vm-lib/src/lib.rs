@@ -7,6 +7,7 @@ pub mod builtins;
 pub mod console;
 pub mod error;
 pub mod frame;
+pub mod gc;
 pub mod mixin_includer;
 pub mod opcodes;
 pub mod runtime_module;
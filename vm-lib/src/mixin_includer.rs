@@ -2,6 +2,8 @@
 
 use std::collections::HashSet;
 
+use rustc_data_structures::fx::FxHashSet;
+
 use crate::{
     builtins::VmGlobals,
     runtime_value::{RuntimeValue, mixin::Mixin},
@@ -34,6 +36,12 @@ impl MixinIncluder {
         false
     }
 
+    pub fn unmet_requirement(&self, provided: &FxHashSet<Symbol>) -> Option<(String, Symbol)> {
+        self.mixins
+            .iter()
+            .find_map(|mixin| mixin.unmet_requirement(provided))
+    }
+
     pub fn list_attributes(&self, builtins: &VmGlobals) -> HashSet<Symbol> {
         let mut attrs = HashSet::new();
         for mixin in &self.mixins {
@@ -1,7 +1,7 @@
 // SPDX-License-Identifier: Apache-2.0
 use std::{cell::RefCell, collections::HashSet, rc::Rc};
 
-use aria_compiler::module::CompiledModule;
+use aria_compiler::{do_compile::CompilationWarning, module::CompiledModule};
 use haxby_opcodes::Opcode;
 use rustc_data_structures::fx::FxHashMap;
 
@@ -24,6 +24,9 @@ pub struct NamedValue {
 
 struct RuntimeModuleImpl {
     compiled_module: CompiledModule,
+    // taken from the compiled module, so that the VM can add to them once it
+    // has loaded the module
+    warnings: RefCell<Vec<CompilationWarning>>,
     indexed_constants: Vec<RuntimeValue>,
     values: RefCell<FxHashMap<String, NamedValue>>,
     entry_co: crate::runtime_value::runtime_code_object::CodeObject,
@@ -81,7 +84,12 @@ fn replace_attribute_access_with_interned(
             Opcode::WriteAttribute(n) => {
                 replace_const_with_symbol!(vm, cm, *n, opcode, WriteAttributeSymbol)
             }
-            Opcode::ReadAttributeSymbol(_) | Opcode::WriteAttributeSymbol(_) => {
+            Opcode::TryReadAttribute(n) => {
+                replace_const_with_symbol!(vm, cm, *n, opcode, TryReadAttributeSymbol)
+            }
+            Opcode::ReadAttributeSymbol(_)
+            | Opcode::WriteAttributeSymbol(_)
+            | Opcode::TryReadAttributeSymbol(_) => {
                 // the compiler cannot generate these instructions because it does not know
                 // what the VM will intern at runtime in what order - so if we see them in
                 // the compiled module's byte stream, it's clearly bad and we should reject
@@ -123,6 +131,7 @@ fn compiled_code_object_to_runtime_code_object(
         body,
         required_argc: cco.required_argc,
         default_argc: cco.default_argc,
+        arg_names: cco.arg_names.into(),
         frame_size: cco.frame_size,
         loc: cco.loc.clone(),
         line_table: Rc::from(cco.line_table.clone()),
@@ -148,11 +157,12 @@ fn compiled_constant_to_runtime_value(
 }
 
 impl RuntimeModuleImpl {
-    fn new(vm: &mut VirtualMachine, cm: CompiledModule) -> Result<Self, VmErrorReason> {
+    fn new(vm: &mut VirtualMachine, mut cm: CompiledModule) -> Result<Self, VmErrorReason> {
         let entry_co =
             compiled_code_object_to_runtime_code_object(vm, &cm, cm.load_entry_code_object())?;
 
         let mut this = Self {
+            warnings: RefCell::new(std::mem::take(&mut cm.warnings)),
             compiled_module: cm,
             indexed_constants: Vec::new(),
             values: Default::default(),
@@ -271,6 +281,18 @@ impl RuntimeModule {
         &self.imp.compiled_module
     }
 
+    // the compiler's warnings about this module, in source order; once it has
+    // been loaded, these include the names it uses that are not defined
+    pub fn warnings(&self) -> Vec<CompilationWarning> {
+        self.imp.warnings.borrow().clone()
+    }
+
+    pub(crate) fn add_warnings(&self, warnings: Vec<CompilationWarning>) {
+        let mut all = self.imp.warnings.borrow_mut();
+        all.extend(warnings);
+        all.sort_by_key(|w| w.loc.location.start);
+    }
+
     pub fn load_named_value(&self, name: &str) -> Option<RuntimeValue> {
         self.imp.load_named_value(name)
     }
@@ -300,12 +322,18 @@ impl RuntimeModule {
         self.imp.load_indexed_const(idx)
     }
 
+    // names starting with an underscore are private to their module, and
+    // only copied if include_private is set
     pub fn lift_all_symbols_from_other(
         &self,
         prior_art: &Self,
+        include_private: bool,
         vm: &crate::VirtualMachine,
     ) -> Result<(), VmErrorReason> {
         for (name, val) in prior_art.named_values_of_this() {
+            if !include_private && name.starts_with('_') {
+                continue;
+            }
             self.typedef_named_value(&name, val.ty.clone());
             self.store_typechecked_named_value(&name, val.val.clone(), &vm.globals)?;
         }
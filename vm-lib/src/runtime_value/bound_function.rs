@@ -3,6 +3,7 @@ use std::rc::Rc;
 
 use crate::{
     frame::Frame,
+    gc::{self, Traced},
     runtime_value::function::PartialFunctionApplication,
     vm::{ExecutionResult, VirtualMachine},
 };
@@ -14,6 +15,17 @@ struct BoundFunctionImpl {
     func: Function,
 }
 
+// a bound function cannot be changed, so it cannot be cleared either; any
+// cycle through it also goes through a value that can
+impl Traced for BoundFunctionImpl {
+    fn visit_children(&self, visit: &mut dyn FnMut(usize)) {
+        gc::visit_value(&self.this, visit);
+        visit(self.func.gc_address());
+    }
+
+    fn clear(&self) {}
+}
+
 #[derive(Clone)]
 pub struct BoundFunction {
     imp: Rc<BoundFunctionImpl>,
@@ -21,9 +33,9 @@ pub struct BoundFunction {
 
 impl BoundFunction {
     pub(super) fn bind(this: RuntimeValue, func: Function) -> Self {
-        Self {
-            imp: Rc::new(BoundFunctionImpl { this, func }),
-        }
+        let imp = Rc::new(BoundFunctionImpl { this, func });
+        gc::track(&imp);
+        Self { imp }
     }
 
     pub fn this(&self) -> &RuntimeValue {
@@ -34,6 +46,10 @@ impl BoundFunction {
         &self.imp.func
     }
 
+    pub(crate) fn gc_address(&self) -> usize {
+        gc::address_of(&self.imp)
+    }
+
     pub fn eval(
         &self,
         argc: u8,
@@ -46,6 +62,38 @@ impl BoundFunction {
         self.func()
             .eval(argc, cur_frame, vm, &partial_application, discard_result)
     }
+
+    pub(crate) fn tail_call_in_frame(
+        &self,
+        argc: u8,
+        cur_frame: &mut Frame,
+        vm: &mut VirtualMachine,
+    ) -> bool {
+        let partial_application =
+            PartialFunctionApplication::default().with_suffix_arg(self.this().clone());
+        self.func()
+            .tail_call_in_frame(argc, cur_frame, vm, &partial_application)
+    }
+
+    pub fn eval_with_keywords(
+        &self,
+        argc: u8,
+        keywords: Vec<(String, RuntimeValue)>,
+        cur_frame: &mut Frame,
+        vm: &mut VirtualMachine,
+        discard_result: bool,
+    ) -> ExecutionResult<CallResult> {
+        let partial_application =
+            PartialFunctionApplication::default().with_suffix_arg(self.this().clone());
+        self.func().eval_with_keywords(
+            argc,
+            keywords,
+            cur_frame,
+            vm,
+            &partial_application,
+            discard_result,
+        )
+    }
 }
 
 impl PartialEq for BoundFunction {
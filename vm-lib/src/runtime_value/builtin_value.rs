@@ -44,6 +44,12 @@ impl GetBuiltinTypeId for i64 {
         BuiltinTypeId::Int
     }
 }
+impl GetBuiltinTypeId for num_bigint::BigInt {
+    #[inline]
+    fn get_builtin_type_id() -> BuiltinTypeId {
+        BuiltinTypeId::Int
+    }
+}
 impl GetBuiltinTypeId for bool {
     #[inline]
     fn get_builtin_type_id() -> BuiltinTypeId {
@@ -2,7 +2,12 @@
 
 use std::rc::Rc;
 
-use crate::{builtins::VmGlobals, frame::Frame, vm::VirtualMachine};
+use crate::{
+    builtins::VmGlobals,
+    frame::Frame,
+    gc::{self, Traced},
+    vm::VirtualMachine,
+};
 
 use crate::symbol::Symbol;
 
@@ -14,12 +19,29 @@ pub(super) struct EnumValueImpl {
     pub(super) payload: Option<RuntimeValue>,
 }
 
+// like bound functions, enum values cannot be changed once created
+impl Traced for EnumValueImpl {
+    fn visit_children(&self, visit: &mut dyn FnMut(usize)) {
+        if let Some(payload) = &self.payload {
+            gc::visit_value(payload, visit);
+        }
+    }
+
+    fn clear(&self) {}
+}
+
 #[derive(Clone)]
 pub struct EnumValue {
     pub(super) imp: Rc<EnumValueImpl>,
 }
 
 impl EnumValue {
+    pub(super) fn new(imp: EnumValueImpl) -> Self {
+        let imp = Rc::new(imp);
+        gc::track(&imp);
+        Self { imp }
+    }
+
     pub fn get_container_enum(&self) -> &Enum {
         &self.imp.enumm
     }
@@ -108,6 +108,12 @@ impl EnumImpl {
         attrs
     }
 
+    fn unmet_mixin_requirement(&self, builtins: &VmGlobals) -> Option<(String, Symbol)> {
+        self.mixins
+            .borrow()
+            .unmet_requirement(&self.list_attributes(builtins))
+    }
+
     fn case_shape_id(&self) -> ShapeId {
         self.case_shape.get()
     }
@@ -179,13 +185,11 @@ impl Enum {
         match self.get_case_by_idx(cidx) {
             Some(case) => {
                 if case.payload_type.is_some() == payload.is_some() {
-                    Some(EnumValue {
-                        imp: Rc::new(EnumValueImpl {
-                            enumm: self.clone(),
-                            case: cidx,
-                            payload,
-                        }),
-                    })
+                    Some(EnumValue::new(EnumValueImpl {
+                        enumm: self.clone(),
+                        case: cidx,
+                        payload,
+                    }))
                 } else {
                     None
                 }
@@ -198,6 +202,10 @@ impl Enum {
         self.imp.list_attributes(builtins)
     }
 
+    pub fn unmet_mixin_requirement(&self, builtins: &VmGlobals) -> Option<(String, Symbol)> {
+        self.imp.unmet_mixin_requirement(builtins)
+    }
+
     pub(crate) fn case_shape_id(&self) -> ShapeId {
         self.imp.case_shape_id()
     }
@@ -5,14 +5,19 @@ use aria_compiler::line_table::LineTable;
 use aria_parser::ast::SourcePointer;
 use haxby_opcodes::{
     Opcode,
-    function_attribs::{FUNC_ACCEPTS_VARARG, FUNC_IS_METHOD, METHOD_ATTRIBUTE_TYPE},
+    function_attribs::{
+        FUNC_ACCEPTS_VARARG, FUNC_IS_ASYNC, FUNC_IS_GENERATOR, FUNC_IS_METHOD,
+        METHOD_ATTRIBUTE_TYPE,
+    },
 };
 use rustc_data_structures::fx::FxHashSet;
 
 use crate::{
     arity::Arity,
-    builtins::VmGlobals,
+    builtins::{VmGlobals, coroutine::create_coroutine, generator::create_generator},
+    error::vm_error::VmErrorReason,
     frame::Frame,
+    gc::{self, Traced},
     runtime_module::RuntimeModule,
     symbol::Symbol,
     vm::{ExecutionResult, RunloopExit, VirtualMachine},
@@ -29,6 +34,11 @@ pub trait BuiltinFunctionImpl {
         0
     }
     fn name(&self) -> &str;
+    // the names that keyword arguments are matched against, in argument order;
+    // a builtin that returns none can only be called with positional arguments
+    fn argument_names(&self) -> &[&str] {
+        &[]
+    }
 }
 
 pub struct BuiltinFunction {
@@ -50,6 +60,7 @@ pub struct BytecodeFunction {
     pub body: Rc<[Opcode]>,
     pub sidecar: Rc<[std::cell::Cell<Option<crate::opcodes::sidecar::OpcodeSidecar>>]>,
     pub arity: Arity,
+    pub arg_names: Rc<[String]>,
     pub frame_size: u8,
     pub line_table: Rc<LineTable>,
     pub loc: SourcePointer,
@@ -75,6 +86,26 @@ pub(crate) enum FunctionImpl {
     BuiltinFunction(BuiltinFunction),
 }
 
+// only bytecode functions are tracked, as only closures capture values
+impl Traced for FunctionImpl {
+    fn visit_children(&self, visit: &mut dyn FnMut(usize)) {
+        if let Self::BytecodeFunction(bcf) = self {
+            for val in bcf.uplevels.borrow().values() {
+                gc::visit_value(val, visit);
+            }
+        }
+        self.get_attribute_store().visit_values(visit);
+    }
+
+    fn clear(&self) {
+        if let Self::BytecodeFunction(bcf) = self {
+            let uplevels = std::mem::take(&mut *bcf.uplevels.borrow_mut());
+            drop(uplevels);
+        }
+        self.get_attribute_store().clear();
+    }
+}
+
 #[derive(Clone)]
 pub struct Function {
     pub(crate) imp: Rc<FunctionImpl>,
@@ -102,6 +133,14 @@ impl FunctionImpl {
         }
     }
 
+    // the position of the argument with the given name, not counting `this`
+    pub(crate) fn argument_index(&self, name: &str) -> Option<usize> {
+        match self {
+            Self::BytecodeFunction(bc) => bc.arg_names.iter().position(|n| n == name),
+            Self::BuiltinFunction(bf) => bf.body.argument_names().iter().position(|n| *n == name),
+        }
+    }
+
     pub(crate) fn frame_size(&self) -> u8 {
         match self {
             Self::BytecodeFunction(bc) => bc.frame_size,
@@ -184,6 +223,14 @@ impl FunctionAttribute {
         self.val & FUNC_ACCEPTS_VARARG != 0
     }
 
+    pub fn is_generator(&self) -> bool {
+        self.val & FUNC_IS_GENERATOR != 0
+    }
+
+    pub fn is_async(&self) -> bool {
+        self.val & FUNC_IS_ASYNC != 0
+    }
+
     pub fn is_method(&self) -> bool {
         self.val & FUNC_IS_METHOD == FUNC_IS_METHOD
     }
@@ -226,6 +273,7 @@ impl FunctionImpl {
                 required: co.required_argc,
                 optional: co.default_argc,
             },
+            arg_names: co.arg_names.clone(),
             frame_size: co.frame_size,
             line_table: lt,
             loc: co.loc.clone(),
@@ -285,9 +333,13 @@ impl Function {
     }
 
     pub fn from_code_object(co: &CodeObject, m: &RuntimeModule) -> Self {
-        Self {
-            imp: Rc::new(FunctionImpl::from_code_object(co, m)),
-        }
+        let imp = Rc::new(FunctionImpl::from_code_object(co, m));
+        gc::track(&imp);
+        Self { imp }
+    }
+
+    pub(crate) fn gc_address(&self) -> usize {
+        gc::address_of(&self.imp)
     }
 
     pub fn read(&self, builtins: &VmGlobals, name: Symbol) -> Option<RuntimeValue> {
@@ -314,6 +366,21 @@ impl Function {
         }
     }
 
+    // runs a generator's or coroutine's frame up to its next yield or await
+    // (or its end); the frame keeps its arguments, locals and stack between runs
+    pub(crate) fn resume_in_frame(
+        &self,
+        target_frame: &mut Frame,
+        vm: &mut VirtualMachine,
+    ) -> ExecutionResult<RunloopExit> {
+        match self.imp.as_ref() {
+            FunctionImpl::BytecodeFunction(bcf) => {
+                vm.eval_bytecode_in_frame(&bcf.module, &bcf.body, &bcf.sidecar, target_frame)
+            }
+            FunctionImpl::BuiltinFunction(bnf) => bnf.body.eval(target_frame, vm),
+        }
+    }
+
     pub fn eval(
         &self,
         argc: u8,
@@ -321,9 +388,78 @@ impl Function {
         vm: &mut VirtualMachine,
         other_args: &PartialFunctionApplication,
         discard_result: bool,
+    ) -> ExecutionResult<CallResult> {
+        self.eval_skipping(argc, Vec::new(), cur_frame, vm, other_args, discard_result)
+    }
+
+    // the top argc values on the stack are the positional arguments, and
+    // keywords the (name, value) pairs to match against the argument names
+    pub fn eval_with_keywords(
+        &self,
+        argc: u8,
+        keywords: Vec<(String, RuntimeValue)>,
+        cur_frame: &mut Frame,
+        vm: &mut VirtualMachine,
+        other_args: &PartialFunctionApplication,
+        discard_result: bool,
     ) -> ExecutionResult<CallResult> {
         let other_argc = other_args.suffix_args.len() as u8;
-        let effective_argc = argc + other_argc;
+
+        let mut slots: Vec<Option<RuntimeValue>> = cur_frame
+            .stack
+            .pop_count(argc as usize)
+            .into_iter()
+            .map(Some)
+            .collect();
+        for (name, val) in keywords {
+            let idx = self
+                .imp
+                .argument_index(&name)
+                .ok_or_else(|| VmErrorReason::NoSuchArgument(name.clone()))?;
+            if slots.len() <= idx {
+                slots.resize(idx + 1, None);
+            }
+            if slots[idx].is_some() {
+                return Err(VmErrorReason::DuplicateArgument(name).into());
+            }
+            slots[idx] = Some(val);
+        }
+
+        // an argument that was not passed is only fine if it has a default,
+        // which the function body fills in when it sees the argument skipped
+        let user_required = self.arity().required.saturating_sub(other_argc) as usize;
+        let mut skipped = vec![];
+        for (idx, slot) in slots.iter().enumerate() {
+            if slot.is_some() {
+                continue;
+            }
+            if idx < user_required {
+                return Err(VmErrorReason::MismatchedArgumentCount(
+                    self.arity().required as usize,
+                    idx + other_argc as usize,
+                )
+                .into());
+            }
+            if self.imp.as_builtin_function().is_some() {
+                return Err(VmErrorReason::OperationFailed(format!(
+                    "{}() cannot skip optional arguments",
+                    self.name()
+                ))
+                .into());
+            }
+            skipped.push(idx as u8 + other_argc);
+        }
+
+        let mut supplied = 0;
+        for val in slots.into_iter().flatten().rev() {
+            cur_frame.stack.push(val);
+            supplied += 1;
+        }
+
+        self.eval_skipping(supplied, skipped, cur_frame, vm, other_args, discard_result)
+    }
+
+    fn check_argument_count(&self, effective_argc: u8) -> ExecutionResult<()> {
         let fixed_arity = self.arity().required + self.arity().optional;
 
         if self.attribute().is_vararg() {
@@ -357,10 +493,21 @@ impl Function {
             }
         }
 
-        let mut new_frame = vm.acquire_frame(self);
+        Ok(())
+    }
+
+    // pushes the arguments, as popped off the caller's stack, onto the stack
+    // of the callee's frame, collecting the extra ones of a vararg function
+    fn push_arguments(
+        &self,
+        mut popped_args: Vec<RuntimeValue>,
+        other_args: &PartialFunctionApplication,
+        new_frame: &mut Frame,
+    ) {
+        let other_argc = other_args.suffix_args.len() as u8;
+        let fixed_arity = self.arity().required + self.arity().optional;
 
         if self.attribute().is_vararg() {
-            let mut popped_args = cur_frame.stack.pop_count(argc as usize);
             let split_at = (fixed_arity - other_argc) as usize;
             let varargs = popped_args.split_off(split_at.min(popped_args.len()));
 
@@ -374,7 +521,7 @@ impl Function {
                 new_frame.stack.push(arg);
             }
         } else {
-            for item in cur_frame.stack.pop_count(argc as usize).into_iter().rev() {
+            for item in popped_args.into_iter().rev() {
                 new_frame.stack.push(item);
             }
         }
@@ -382,6 +529,91 @@ impl Function {
         for arg in &other_args.suffix_args {
             new_frame.stack.push(arg.clone());
         }
+    }
+
+    // sets up `return self(...)` to run in cur_frame, in place of the function
+    // that is returning, instead of nesting a new frame; the frame then runs
+    // the callee once its current code returns. This only works for a plain
+    // bytecode function, returned from one that is not a generator or async
+    // function and has no try block open; otherwise it returns false, and the
+    // call has to be made as usual
+    pub(crate) fn tail_call_in_frame(
+        &self,
+        argc: u8,
+        cur_frame: &mut Frame,
+        vm: &mut VirtualMachine,
+        other_args: &PartialFunctionApplication,
+    ) -> bool {
+        let runs_in_own_frame = |f: &Function| {
+            f.imp.as_bytecode_function().is_some()
+                && !f.attribute().is_generator()
+                && !f.attribute().is_async()
+        };
+        let Some(cur_func) = &cur_frame.func else {
+            return false;
+        };
+        if !runs_in_own_frame(self) || !runs_in_own_frame(cur_func) {
+            return false;
+        }
+        if !cur_frame.ctrl_blocks.is_empty() {
+            return false;
+        }
+
+        // a wrong argument count is reported by the regular call instead
+        let effective_argc = argc + other_args.suffix_args.len() as u8;
+        if self.check_argument_count(effective_argc).is_err() {
+            return false;
+        }
+
+        vm.gc_safe_point();
+        let popped_args = cur_frame.stack.pop_count(argc as usize);
+        let elided_frames = cur_frame.elided_frames + 1;
+        cur_frame.reset_for_function(self);
+        cur_frame.elided_frames = elided_frames;
+        self.push_arguments(popped_args, other_args, cur_frame);
+        cur_frame.set_argc(effective_argc);
+        cur_frame.pending_tail_call = true;
+        true
+    }
+
+    fn eval_skipping(
+        &self,
+        argc: u8,
+        skipped: Vec<u8>,
+        cur_frame: &mut Frame,
+        vm: &mut VirtualMachine,
+        other_args: &PartialFunctionApplication,
+        discard_result: bool,
+    ) -> ExecutionResult<CallResult> {
+        let other_argc = other_args.suffix_args.len() as u8;
+        let effective_argc = argc + skipped.len() as u8 + other_argc;
+        self.check_argument_count(effective_argc)?;
+
+        let mut new_frame = vm.acquire_frame(self);
+        new_frame.skipped_args = skipped;
+        let popped_args = cur_frame.stack.pop_count(argc as usize);
+        self.push_arguments(popped_args, other_args, &mut new_frame);
+
+        // calling a generator does not run any of its code, it just hands the
+        // prepared frame over to a Generator object
+        if self.attribute().is_generator() {
+            new_frame.set_argc(effective_argc);
+            let generator = create_generator(self.clone(), new_frame, vm)?;
+            if !discard_result {
+                cur_frame.stack.push(generator.clone());
+            }
+            return Ok(CallResult::Ok(generator));
+        }
+
+        // likewise, an async function only runs once its Coroutine is resumed
+        if self.attribute().is_async() {
+            new_frame.set_argc(effective_argc);
+            let coroutine = create_coroutine(self.clone(), new_frame, vm)?;
+            if !discard_result {
+                cur_frame.stack.push(coroutine.clone());
+            }
+            return Ok(CallResult::Ok(coroutine));
+        }
 
         let eval_result = self.eval_in_frame(effective_argc, &mut new_frame, vm);
         let result = match eval_result {
@@ -1,12 +1,64 @@
 // SPDX-License-Identifier: Apache-2.0
-use super::{builtin_value::BuiltinValue, float::FloatValue};
+use std::cmp::Ordering;
+
+use num_bigint::BigInt;
+use num_traits::{ToPrimitive, Zero};
+
+use crate::error::vm_error::VmErrorReason;
+
+use super::{OperatorEvalOutcome, RuntimeValue, builtin_value::BuiltinValue, float::FloatValue};
 
 pub type IntegerValue = BuiltinValue<i64>;
 
+// an Int that does not fit in 64 bits; arithmetic always demotes results that
+// fit back to IntegerValue, so the two representations never overlap
+pub type BigIntegerValue = BuiltinValue<BigInt>;
+
+pub fn int_from_bigint(n: BigInt) -> RuntimeValue {
+    match n.to_i64() {
+        Some(n) => RuntimeValue::Integer(n.into()),
+        None => RuntimeValue::BigInteger(n.into()),
+    }
+}
+
+fn as_bigint(val: &RuntimeValue) -> Option<BigInt> {
+    match val {
+        RuntimeValue::Integer(n) => Some(BigInt::from(*n.raw_value())),
+        RuntimeValue::BigInteger(n) => Some(n.raw_value().clone()),
+        _ => None,
+    }
+}
+
+fn as_fp(val: &RuntimeValue) -> Option<f64> {
+    match val {
+        RuntimeValue::Integer(n) => Some(*n.raw_value() as f64),
+        RuntimeValue::BigInteger(n) => Some(*n.to_fp().raw_value()),
+        RuntimeValue::Float(f) => Some(*f.raw_value()),
+        _ => None,
+    }
+}
+
 impl IntegerValue {
     pub fn to_fp(&self) -> FloatValue {
         (*self.raw_value() as f64).into()
     }
+
+    fn to_bigint(&self) -> BigInt {
+        BigInt::from(*self.raw_value())
+    }
+}
+
+impl BigIntegerValue {
+    pub fn to_fp(&self) -> FloatValue {
+        self.raw_value().to_f64().unwrap_or(f64::NAN).into()
+    }
+
+    // the low 64 bits of this value, in two's complement
+    pub fn truncate_to_i64(&self) -> i64 {
+        (self.raw_value() & BigInt::from(u64::MAX))
+            .to_u64()
+            .unwrap_or_default() as i64
+    }
 }
 
 impl PartialEq<FloatValue> for IntegerValue {
@@ -15,57 +67,95 @@ impl PartialEq<FloatValue> for IntegerValue {
     }
 }
 
+impl PartialEq<FloatValue> for BigIntegerValue {
+    fn eq(&self, other: &FloatValue) -> bool {
+        self.to_fp() == *other
+    }
+}
+
+impl PartialEq<BigIntegerValue> for BigIntegerValue {
+    fn eq(&self, other: &BigIntegerValue) -> bool {
+        self.raw_value() == other.raw_value()
+    }
+}
+
 impl std::ops::Add<&IntegerValue> for &IntegerValue {
-    type Output = IntegerValue;
+    type Output = RuntimeValue;
 
     #[inline]
     fn add(self, rhs: &IntegerValue) -> Self::Output {
-        From::from(self.raw_value().wrapping_add(*rhs.raw_value()))
+        match self.raw_value().checked_add(*rhs.raw_value()) {
+            Some(n) => RuntimeValue::Integer(n.into()),
+            None => int_from_bigint(self.to_bigint() + rhs.to_bigint()),
+        }
     }
 }
 
 impl std::ops::Sub<&IntegerValue> for &IntegerValue {
-    type Output = IntegerValue;
+    type Output = RuntimeValue;
 
     #[inline]
     fn sub(self, rhs: &IntegerValue) -> Self::Output {
-        From::from(self.raw_value().wrapping_sub(*rhs.raw_value()))
+        match self.raw_value().checked_sub(*rhs.raw_value()) {
+            Some(n) => RuntimeValue::Integer(n.into()),
+            None => int_from_bigint(self.to_bigint() - rhs.to_bigint()),
+        }
     }
 }
 
 impl std::ops::Mul<&IntegerValue> for &IntegerValue {
-    type Output = IntegerValue;
+    type Output = RuntimeValue;
 
     #[inline]
     fn mul(self, rhs: &IntegerValue) -> Self::Output {
-        From::from(self.raw_value().wrapping_mul(*rhs.raw_value()))
+        match self.raw_value().checked_mul(*rhs.raw_value()) {
+            Some(n) => RuntimeValue::Integer(n.into()),
+            None => int_from_bigint(self.to_bigint() * rhs.to_bigint()),
+        }
     }
 }
 
 impl std::ops::Div<&IntegerValue> for &IntegerValue {
-    type Output = IntegerValue;
+    type Output = RuntimeValue;
 
     #[inline]
     fn div(self, rhs: &IntegerValue) -> Self::Output {
-        From::from(self.raw_value().wrapping_div(*rhs.raw_value()))
+        match self.raw_value().checked_div(*rhs.raw_value()) {
+            Some(n) => RuntimeValue::Integer(n.into()),
+            None => int_from_bigint(self.to_bigint() / rhs.to_bigint()),
+        }
     }
 }
 
 impl std::ops::Rem<&IntegerValue> for &IntegerValue {
-    type Output = IntegerValue;
+    type Output = RuntimeValue;
 
     #[inline]
     fn rem(self, rhs: &IntegerValue) -> Self::Output {
-        From::from(self.raw_value().wrapping_rem(*rhs.raw_value()))
+        match self.raw_value().checked_rem(*rhs.raw_value()) {
+            Some(n) => RuntimeValue::Integer(n.into()),
+            None => int_from_bigint(self.to_bigint() % rhs.to_bigint()),
+        }
     }
 }
 
 impl std::ops::Neg for &IntegerValue {
-    type Output = IntegerValue;
+    type Output = RuntimeValue;
 
     #[inline]
     fn neg(self) -> Self::Output {
-        From::from(-self.raw_value())
+        match self.raw_value().checked_neg() {
+            Some(n) => RuntimeValue::Integer(n.into()),
+            None => int_from_bigint(-self.to_bigint()),
+        }
+    }
+}
+
+impl std::ops::Neg for &BigIntegerValue {
+    type Output = RuntimeValue;
+
+    fn neg(self) -> Self::Output {
+        int_from_bigint(-self.raw_value())
     }
 }
 
@@ -96,21 +186,40 @@ impl std::ops::BitXor<&IntegerValue> for &IntegerValue {
     }
 }
 
+// shifting by a negative amount shifts in the opposite direction
+fn shift_left(n: &BigInt, by: i64) -> RuntimeValue {
+    if by >= 0 {
+        int_from_bigint(n << by.unsigned_abs())
+    } else {
+        int_from_bigint(n >> by.unsigned_abs())
+    }
+}
+
 impl std::ops::Shl<&IntegerValue> for &IntegerValue {
-    type Output = IntegerValue;
+    type Output = RuntimeValue;
 
     #[inline]
     fn shl(self, rhs: &IntegerValue) -> Self::Output {
-        From::from(self.raw_value() << rhs.raw_value())
+        let (n, by) = (*self.raw_value(), *rhs.raw_value());
+        if (0..64).contains(&by) && (n << by) >> by == n {
+            RuntimeValue::Integer((n << by).into())
+        } else {
+            shift_left(&self.to_bigint(), by)
+        }
     }
 }
 
 impl std::ops::Shr<&IntegerValue> for &IntegerValue {
-    type Output = IntegerValue;
+    type Output = RuntimeValue;
 
     #[inline]
     fn shr(self, rhs: &IntegerValue) -> Self::Output {
-        From::from(self.raw_value() >> rhs.raw_value())
+        let (n, by) = (*self.raw_value(), *rhs.raw_value());
+        if by >= 0 {
+            RuntimeValue::Integer((n >> by.min(63)).into())
+        } else {
+            shift_left(&self.to_bigint(), by.saturating_neg())
+        }
     }
 }
 
@@ -141,3 +250,92 @@ impl PartialEq<i64> for IntegerValue {
         *self.raw_value() == *other
     }
 }
+
+#[derive(Clone, Copy)]
+pub(crate) enum IntegerOp {
+    Add,
+    Sub,
+    Mul,
+    Div,
+    Rem,
+    ShiftLeft,
+    ShiftRight,
+    BitwiseAnd,
+    BitwiseOr,
+    Xor,
+}
+
+// the slow path for arithmetic where at least one operand is a big integer;
+// returns None if the operands are not both numbers, so that the caller can
+// fall back to operator overloading
+pub(crate) fn big_integer_binop(
+    op: IntegerOp,
+    lhs: &RuntimeValue,
+    rhs: &RuntimeValue,
+) -> Option<OperatorEvalOutcome<RuntimeValue>> {
+    if !lhs.is_big_integer() && !rhs.is_big_integer() {
+        return None;
+    }
+
+    if let (Some(l), Some(r)) = (as_bigint(lhs), as_bigint(rhs)) {
+        let result = match op {
+            IntegerOp::Add => l + r,
+            IntegerOp::Sub => l - r,
+            IntegerOp::Mul => l * r,
+            IntegerOp::Div | IntegerOp::Rem if r.is_zero() => {
+                return Some(OperatorEvalOutcome::Error(
+                    VmErrorReason::DivisionByZero.into(),
+                ));
+            }
+            IntegerOp::Div => l / r,
+            IntegerOp::Rem => l % r,
+            IntegerOp::ShiftLeft | IntegerOp::ShiftRight => {
+                let by = r.to_i64()?;
+                let by = if matches!(op, IntegerOp::ShiftLeft) {
+                    by
+                } else {
+                    by.saturating_neg()
+                };
+                return Some(OperatorEvalOutcome::Ok(shift_left(&l, by)));
+            }
+            IntegerOp::BitwiseAnd => l & r,
+            IntegerOp::BitwiseOr => l | r,
+            IntegerOp::Xor => l ^ r,
+        };
+        return Some(OperatorEvalOutcome::Ok(int_from_bigint(result)));
+    }
+
+    let (l, r) = (as_fp(lhs)?, as_fp(rhs)?);
+    let result = match op {
+        IntegerOp::Add => l + r,
+        IntegerOp::Sub => l - r,
+        IntegerOp::Mul => l * r,
+        IntegerOp::Div | IntegerOp::Rem if r == 0.0 => {
+            return Some(OperatorEvalOutcome::Error(
+                VmErrorReason::DivisionByZero.into(),
+            ));
+        }
+        IntegerOp::Div => l / r,
+        IntegerOp::Rem => l % r,
+        _ => return None,
+    };
+    Some(OperatorEvalOutcome::Ok(RuntimeValue::Float(result.into())))
+}
+
+// the slow path for comparisons where at least one operand is a big integer;
+// returns None if the operands are not both numbers, and Some(None) if they
+// are unordered (i.e. one of them is NaN)
+pub(crate) fn big_integer_compare(
+    lhs: &RuntimeValue,
+    rhs: &RuntimeValue,
+) -> Option<Option<Ordering>> {
+    if !lhs.is_big_integer() && !rhs.is_big_integer() {
+        return None;
+    }
+
+    if let (Some(l), Some(r)) = (as_bigint(lhs), as_bigint(rhs)) {
+        Some(Some(l.cmp(&r)))
+    } else {
+        Some(as_fp(lhs)?.partial_cmp(&as_fp(rhs)?))
+    }
+}
@@ -91,9 +91,12 @@ impl RuntimeValueType {
             }),
             RuntimeValue::Type(_) => builtins.get_builtin_type_by_id(BuiltinTypeId::Type),
             RuntimeValue::Boolean(_) => builtins.get_builtin_type_by_id(BuiltinTypeId::Bool),
-            RuntimeValue::Integer(_) => builtins.get_builtin_type_by_id(BuiltinTypeId::Int),
+            RuntimeValue::Integer(_) | RuntimeValue::BigInteger(_) => {
+                builtins.get_builtin_type_by_id(BuiltinTypeId::Int)
+            }
             RuntimeValue::Float(_) => builtins.get_builtin_type_by_id(BuiltinTypeId::Float),
             RuntimeValue::List(_) => builtins.get_builtin_type_by_id(BuiltinTypeId::List),
+            RuntimeValue::Map(_) => builtins.get_builtin_type_by_id(BuiltinTypeId::Map),
             RuntimeValue::String(_) => builtins.get_builtin_type_by_id(BuiltinTypeId::String),
         }
     }
@@ -7,6 +7,7 @@ use crate::{
     builtins::VmGlobals,
     error::vm_error::{VmError, VmErrorReason},
     frame::Frame,
+    gc::{self, Traced},
     runtime_value::object::ObjectBox,
     symbol::Symbol,
     vm::{ExecutionResult, VirtualMachine},
@@ -83,6 +84,21 @@ impl ListImpl {
     }
 }
 
+impl Traced for ListImpl {
+    fn visit_children(&self, visit: &mut dyn FnMut(usize)) {
+        for val in self.get() {
+            gc::visit_value(val, visit);
+        }
+        self.boxx.visit_values(visit);
+    }
+
+    fn clear(&self) {
+        let values = std::mem::take(self.get_mut());
+        drop(values);
+        self.boxx.clear();
+    }
+}
+
 impl std::fmt::Debug for ListImpl {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         let li = self.get();
@@ -97,11 +113,17 @@ impl std::fmt::Debug for ListImpl {
     }
 }
 
-#[derive(Clone, Default)]
+#[derive(Clone)]
 pub struct List {
     pub(super) imp: Rc<ListImpl>,
 }
 
+impl Default for List {
+    fn default() -> Self {
+        Self::new_with_capacity(0)
+    }
+}
+
 impl List {
     pub fn from(values: &[RuntimeValue]) -> Self {
         let ret = Self::default();
@@ -110,9 +132,9 @@ impl List {
     }
 
     pub fn new_with_capacity(cap: usize) -> Self {
-        Self {
-            imp: Rc::new(ListImpl::new_with_capacity(cap)),
-        }
+        let imp = Rc::new(ListImpl::new_with_capacity(cap));
+        gc::track(&imp);
+        Self { imp }
     }
 
     pub fn len(&self) -> usize {
@@ -0,0 +1,366 @@
+// SPDX-License-Identifier: Apache-2.0
+use std::{
+    cell::RefCell,
+    hash::{Hash, Hasher},
+    rc::Rc,
+};
+
+use rustc_data_structures::fx::{FxHashMap, FxHashSet};
+
+use crate::{
+    builtins::VmGlobals,
+    error::vm_error::VmErrorReason,
+    frame::Frame,
+    gc::{self, Traced},
+    runtime_value::object::ObjectBox,
+    symbol::{INTERNED_ATTR_HASH, Symbol},
+    vm::{ExecutionResult, RunloopExit, VirtualMachine},
+};
+
+use super::{CallResult, RuntimeValue};
+
+struct MapEntry {
+    hash: u64,
+    key: RuntimeValue,
+    value: RuntimeValue,
+}
+
+#[derive(Default)]
+struct MapStorage {
+    // entries are kept in insertion order; removing an entry leaves a hole behind,
+    // until there are enough holes to compact them away
+    entries: Vec<Option<MapEntry>>,
+    // the position of each entry (or hole) in insertion order, which unlike its
+    // slot in entries survives compaction; iterators use it as their cursor
+    positions: Vec<u64>,
+    next_position: u64,
+    index: FxHashMap<u64, Vec<usize>>,
+    len: usize,
+}
+
+impl MapStorage {
+    fn with_capacity(cap: usize) -> Self {
+        Self {
+            entries: Vec::with_capacity(cap),
+            positions: Vec::with_capacity(cap),
+            next_position: 0,
+            index: FxHashMap::with_capacity_and_hasher(cap, Default::default()),
+            len: 0,
+        }
+    }
+
+    fn candidates(&self, hash: u64) -> Vec<(usize, RuntimeValue)> {
+        self.index
+            .get(&hash)
+            .map(|slots| {
+                slots
+                    .iter()
+                    .filter_map(|slot| match self.entries.get(*slot) {
+                        Some(Some(e)) => Some((*slot, e.key.clone())),
+                        _ => None,
+                    })
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    // drops the holes left behind by removed entries, once there are enough of them
+    fn compact_if_needed(&mut self) {
+        let holes = self.entries.len() - self.len;
+        if holes < 16 || holes < self.len {
+            return;
+        }
+
+        let mut live = self.entries.iter().map(Option::is_some);
+        self.positions.retain(|_| live.next().unwrap_or(false));
+        self.entries.retain(|e| e.is_some());
+        self.index.clear();
+        for (slot, e) in self.entries.iter().enumerate() {
+            if let Some(e) = e {
+                self.index.entry(e.hash).or_default().push(slot);
+            }
+        }
+    }
+
+    fn push(&mut self, hash: u64, key: RuntimeValue, value: RuntimeValue) {
+        self.compact_if_needed();
+        let slot = self.entries.len();
+        self.entries.push(Some(MapEntry { hash, key, value }));
+        self.positions.push(self.next_position);
+        self.next_position += 1;
+        self.index.entry(hash).or_default().push(slot);
+        self.len += 1;
+    }
+
+    fn remove_slot(&mut self, slot: usize) -> bool {
+        let Some(e) = self.entries.get_mut(slot).and_then(|e| e.take()) else {
+            return false;
+        };
+        if let Some(slots) = self.index.get_mut(&e.hash) {
+            slots.retain(|s| *s != slot);
+            if slots.is_empty() {
+                self.index.remove(&e.hash);
+            }
+        }
+        self.len -= 1;
+        true
+    }
+}
+
+pub(super) struct MapImpl {
+    storage: RefCell<MapStorage>,
+    pub(super) boxx: ObjectBox,
+}
+
+impl MapImpl {
+    fn new_with_capacity(cap: usize) -> Self {
+        Self {
+            storage: RefCell::new(MapStorage::with_capacity(cap)),
+            boxx: ObjectBox::default(),
+        }
+    }
+}
+
+// if the storage happens to be borrowed, its entries are neither visited nor
+// cleared, which keeps them alive for this collection
+impl Traced for MapImpl {
+    fn visit_children(&self, visit: &mut dyn FnMut(usize)) {
+        if let Ok(storage) = self.storage.try_borrow() {
+            for e in storage.entries.iter().flatten() {
+                gc::visit_value(&e.key, visit);
+                gc::visit_value(&e.value, visit);
+            }
+        }
+        self.boxx.visit_values(visit);
+    }
+
+    fn clear(&self) {
+        if let Ok(mut storage) = self.storage.try_borrow_mut() {
+            let entries = std::mem::take(&mut *storage);
+            drop(storage);
+            drop(entries);
+        }
+        self.boxx.clear();
+    }
+}
+
+#[derive(Clone)]
+pub struct Map {
+    pub(super) imp: Rc<MapImpl>,
+}
+
+impl Default for Map {
+    fn default() -> Self {
+        Self::new_with_capacity(0)
+    }
+}
+
+// hashing and comparing keys may call back into Aria code (which may in turn
+// access the map), so no borrow of the storage is ever held across those calls
+impl Map {
+    pub fn new_with_capacity(cap: usize) -> Self {
+        let imp = Rc::new(MapImpl::new_with_capacity(cap));
+        gc::track(&imp);
+        Self { imp }
+    }
+
+    pub fn len(&self) -> usize {
+        self.imp.storage.borrow().len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn hash_key(
+        key: &RuntimeValue,
+        cur_frame: &mut Frame,
+        vm: &mut VirtualMachine,
+    ) -> ExecutionResult<RunloopExit<u64>> {
+        let hash = match key {
+            RuntimeValue::Integer(i) => *i.raw_value() as u64,
+            RuntimeValue::BigInteger(i) => i.truncate_to_i64() as u64,
+            RuntimeValue::Boolean(b) => *b.raw_value() as u64,
+            RuntimeValue::Float(f) => {
+                // 0.0 and -0.0 compare equal, so they must hash equal too
+                let f = *f.raw_value();
+                if f == 0.0 { 0 } else { f.to_bits() }
+            }
+            RuntimeValue::String(s) => {
+                let mut hasher = std::hash::DefaultHasher::new();
+                s.raw_value().hash(&mut hasher);
+                hasher.finish()
+            }
+            _ => {
+                let hash_f = key
+                    .read_attribute(INTERNED_ATTR_HASH, &vm.globals)
+                    .map_err(|e| e.to_vm_error_reason("hash"))?;
+                match hash_f.eval(0, cur_frame, vm, true)? {
+                    CallResult::Ok(RuntimeValue::Integer(h)) => *h.raw_value() as u64,
+                    CallResult::Ok(RuntimeValue::BigInteger(h)) => h.truncate_to_i64() as u64,
+                    CallResult::Ok(_) => return Err(VmErrorReason::UnexpectedType.into()),
+                    CallResult::Exception(e) => return Ok(RunloopExit::Exception(e)),
+                }
+            }
+        };
+
+        Ok(RunloopExit::Ok(hash))
+    }
+
+    fn keys_equal(
+        lhs: &RuntimeValue,
+        rhs: &RuntimeValue,
+        cur_frame: &mut Frame,
+        vm: &mut VirtualMachine,
+    ) -> ExecutionResult<RunloopExit<bool>> {
+        let eq = match (lhs, rhs) {
+            (RuntimeValue::Integer(l), RuntimeValue::Integer(r)) => l == r,
+            (RuntimeValue::Boolean(l), RuntimeValue::Boolean(r)) => l == r,
+            (RuntimeValue::Float(l), RuntimeValue::Float(r)) => l == r,
+            (RuntimeValue::String(l), RuntimeValue::String(r)) => l == r,
+            _ => return RuntimeValue::equals_or_throw(lhs, rhs, cur_frame, vm),
+        };
+
+        Ok(RunloopExit::Ok(eq))
+    }
+
+    // returns the hash of key, and the slot it is stored at (if any)
+    fn find(
+        &self,
+        key: &RuntimeValue,
+        cur_frame: &mut Frame,
+        vm: &mut VirtualMachine,
+    ) -> ExecutionResult<RunloopExit<(u64, Option<usize>)>> {
+        let hash = match Self::hash_key(key, cur_frame, vm)? {
+            RunloopExit::Ok(h) => h,
+            RunloopExit::Exception(e) => return Ok(RunloopExit::Exception(e)),
+        };
+
+        let candidates = self.imp.storage.borrow().candidates(hash);
+        for (slot, candidate) in candidates {
+            match Self::keys_equal(&candidate, key, cur_frame, vm)? {
+                RunloopExit::Ok(true) => return Ok(RunloopExit::Ok((hash, Some(slot)))),
+                RunloopExit::Ok(false) => {}
+                RunloopExit::Exception(e) => return Ok(RunloopExit::Exception(e)),
+            }
+        }
+
+        Ok(RunloopExit::Ok((hash, None)))
+    }
+
+    pub fn get(
+        &self,
+        key: &RuntimeValue,
+        cur_frame: &mut Frame,
+        vm: &mut VirtualMachine,
+    ) -> ExecutionResult<RunloopExit<Option<RuntimeValue>>> {
+        match self.find(key, cur_frame, vm)? {
+            RunloopExit::Ok((_, Some(slot))) => Ok(RunloopExit::Ok(
+                self.imp
+                    .storage
+                    .borrow()
+                    .entries
+                    .get(slot)
+                    .and_then(|e| e.as_ref())
+                    .map(|e| e.value.clone()),
+            )),
+            RunloopExit::Ok((_, None)) => Ok(RunloopExit::Ok(None)),
+            RunloopExit::Exception(e) => Ok(RunloopExit::Exception(e)),
+        }
+    }
+
+    // returns whether key was not already in the map
+    pub fn insert(
+        &self,
+        key: RuntimeValue,
+        value: RuntimeValue,
+        cur_frame: &mut Frame,
+        vm: &mut VirtualMachine,
+    ) -> ExecutionResult<RunloopExit<bool>> {
+        let (hash, slot) = match self.find(&key, cur_frame, vm)? {
+            RunloopExit::Ok(found) => found,
+            RunloopExit::Exception(e) => return Ok(RunloopExit::Exception(e)),
+        };
+
+        let mut storage = self.imp.storage.borrow_mut();
+        if let Some(slot) = slot
+            && let Some(Some(e)) = storage.entries.get_mut(slot)
+        {
+            e.value = value;
+            Ok(RunloopExit::Ok(false))
+        } else {
+            storage.push(hash, key, value);
+            Ok(RunloopExit::Ok(true))
+        }
+    }
+
+    // returns whether key was in the map
+    pub fn remove(
+        &self,
+        key: &RuntimeValue,
+        cur_frame: &mut Frame,
+        vm: &mut VirtualMachine,
+    ) -> ExecutionResult<RunloopExit<bool>> {
+        match self.find(key, cur_frame, vm)? {
+            RunloopExit::Ok((_, Some(slot))) => Ok(RunloopExit::Ok(
+                self.imp.storage.borrow_mut().remove_slot(slot),
+            )),
+            RunloopExit::Ok((_, None)) => Ok(RunloopExit::Ok(false)),
+            RunloopExit::Exception(e) => Ok(RunloopExit::Exception(e)),
+        }
+    }
+
+    pub fn keys(&self) -> Vec<RuntimeValue> {
+        self.imp
+            .storage
+            .borrow()
+            .entries
+            .iter()
+            .flatten()
+            .map(|e| e.key.clone())
+            .collect()
+    }
+
+    // the first entry inserted at or after position, along with its own position;
+    // positions stay valid when entries are added or removed during iteration
+    pub fn next_entry(&self, position: u64) -> Option<(u64, RuntimeValue, RuntimeValue)> {
+        let storage = self.imp.storage.borrow();
+        let start = storage.positions.partition_point(|p| *p < position);
+        storage.entries[start..]
+            .iter()
+            .zip(&storage.positions[start..])
+            .find_map(|(e, p)| e.as_ref().map(|e| (*p, e.key.clone(), e.value.clone())))
+    }
+
+    pub fn read(&self, builtins: &VmGlobals, name: Symbol) -> Option<RuntimeValue> {
+        self.imp.boxx.read(builtins, name)
+    }
+
+    pub fn list_attributes(&self, builtins: &VmGlobals) -> FxHashSet<Symbol> {
+        self.imp.boxx.list_attributes(builtins)
+    }
+}
+
+impl std::fmt::Debug for Map {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let storage = self.imp.storage.borrow();
+        write!(
+            f,
+            "{{{}}}",
+            storage
+                .entries
+                .iter()
+                .flatten()
+                .map(|e| format!("{:?}: {:?}", e.key, e.value))
+                .collect::<Vec<String>>()
+                .join(",")
+        )
+    }
+}
+
+impl PartialEq for Map {
+    fn eq(&self, other: &Self) -> bool {
+        Rc::ptr_eq(&self.imp, &other.imp)
+    }
+}
+impl Eq for Map {}
@@ -11,6 +11,8 @@ pub(super) struct MixinImpl {
     name: String,
     pub(super) entries: ObjectBox,
     mixins: RefCell<crate::mixin_includer::MixinIncluder>,
+    // members that a type including this mixin must provide
+    requirements: RefCell<Vec<Symbol>>,
 }
 
 impl MixinImpl {
@@ -19,6 +21,7 @@ impl MixinImpl {
             name: name.to_owned(),
             entries: ObjectBox::default(),
             mixins: RefCell::new(crate::mixin_includer::MixinIncluder::default()),
+            requirements: RefCell::new(vec![]),
         }
     }
 
@@ -47,6 +50,25 @@ impl MixinImpl {
         attrs.extend(self.mixins.borrow().list_attributes(builtins));
         attrs
     }
+
+    fn add_requirement(&self, name: Symbol) {
+        let mut requirements = self.requirements.borrow_mut();
+        if !requirements.contains(&name) {
+            requirements.push(name);
+        }
+    }
+
+    fn unmet_requirement(&self, provided: &FxHashSet<Symbol>) -> Option<(String, Symbol)> {
+        if let Some(name) = self
+            .requirements
+            .borrow()
+            .iter()
+            .find(|name| !provided.contains(name))
+        {
+            return Some((self.name.clone(), *name));
+        }
+        self.mixins.borrow().unmet_requirement(provided)
+    }
 }
 
 #[derive(Clone)]
@@ -84,6 +106,16 @@ impl Mixin {
     pub fn list_attributes(&self, builtins: &VmGlobals) -> FxHashSet<Symbol> {
         self.imp.list_attributes(builtins)
     }
+
+    pub fn add_requirement(&self, name: Symbol) {
+        self.imp.add_requirement(name);
+    }
+
+    // returns the name of a mixin and of a member it requires that is not
+    // among the provided ones, looking into the mixins it includes as well
+    pub fn unmet_requirement(&self, provided: &FxHashSet<Symbol>) -> Option<(String, Symbol)> {
+        self.imp.unmet_requirement(provided)
+    }
 }
 
 impl PartialEq for Mixin {
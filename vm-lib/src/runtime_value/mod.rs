@@ -11,9 +11,10 @@ use enumeration::Enum;
 use float::FloatValue;
 use function::Function;
 use haxby_opcodes::BuiltinTypeId;
-use integer::IntegerValue;
+use integer::{BigIntegerValue, IntegerValue};
 use kind::RuntimeValueType;
 use list::List;
+use map::Map;
 use mixin::Mixin;
 use object::Object;
 use opaque::OpaqueValue;
@@ -30,9 +31,9 @@ use crate::{
     runtime_value::isa::IsaCheckable,
     symbol::{
         INTERNED_OP_IMPL_CALL, INTERNED_OP_IMPL_EQUALS, INTERNED_OP_IMPL_READ_INDEX,
-        INTERNED_OP_IMPL_WRITE_INDEX, INTERNED_OP_PRETTYPRINT, Symbol,
+        INTERNED_OP_IMPL_READ_SLICE, INTERNED_OP_IMPL_WRITE_INDEX, INTERNED_OP_PRETTYPRINT, Symbol,
     },
-    vm::{ExecutionResult, VirtualMachine},
+    vm::{ExecutionResult, RunloopExit, VirtualMachine},
 };
 
 pub mod boolean;
@@ -46,6 +47,7 @@ pub mod integer;
 pub mod isa;
 pub mod kind;
 pub mod list;
+pub mod map;
 pub mod mixin;
 pub mod object;
 pub mod opaque;
@@ -57,6 +59,7 @@ pub mod structure;
 #[derive(EnumAsInner, Clone)]
 pub enum RuntimeValue {
     Integer(IntegerValue),
+    BigInteger(BigIntegerValue),
     String(StringValue),
     Float(FloatValue),
     Boolean(BooleanValue),
@@ -66,6 +69,7 @@ pub enum RuntimeValue {
     Function(Function),
     BoundFunction(BoundFunction),
     List(List),
+    Map(Map),
     Mixin(Mixin),
     Type(RuntimeValueType),
     Module(RuntimeModule),
@@ -85,6 +89,9 @@ impl RuntimeValue {
             (Self::Float(l0), Self::Float(r0)) => l0 == r0,
             (Self::Float(l0), Self::Integer(r0)) => l0 == r0,
             (Self::Integer(l0), Self::Float(r0)) => l0 == r0,
+            (Self::BigInteger(l0), Self::BigInteger(r0)) => l0 == r0,
+            (Self::BigInteger(l0), Self::Float(r0)) => l0 == r0,
+            (Self::Float(l0), Self::BigInteger(r0)) => r0 == l0,
             (Self::Boolean(l0), Self::Boolean(r0)) => l0 == r0,
             (Self::String(l0), Self::String(r0)) => l0 == r0,
             (Self::Object(l0), Self::Object(r0)) => l0 == r0,
@@ -95,6 +102,7 @@ impl RuntimeValue {
             (Self::Function(l0), Self::Function(r0)) => l0 == r0,
             (Self::BoundFunction(l0), Self::BoundFunction(r0)) => l0 == r0,
             (Self::List(l0), Self::List(r0)) => l0 == r0,
+            (Self::Map(l0), Self::Map(r0)) => l0 == r0,
             (Self::Type(l0), Self::Type(r0)) => l0 == r0,
             (Self::TypeCheck(l0), Self::TypeCheck(r0)) => l0 == r0,
             _ => false,
@@ -238,6 +246,37 @@ impl RuntimeValue {
 
         lhs.builtin_equals(rhs, cur_frame, vm)
     }
+
+    // like equals, but an exception thrown by an _op_impl_equals is passed on
+    // rather than making the values compare unequal
+    pub(crate) fn equals_or_throw(
+        lhs: &RuntimeValue,
+        rhs: &RuntimeValue,
+        cur_frame: &mut Frame,
+        vm: &mut VirtualMachine,
+    ) -> ExecutionResult<RunloopExit<bool>> {
+        let same_type = RuntimeValueType::get_type(lhs, &vm.globals)
+            == RuntimeValueType::get_type(rhs, &vm.globals);
+        let operands = if same_type {
+            vec![(lhs, rhs)]
+        } else {
+            vec![(lhs, rhs), (rhs, lhs)]
+        };
+
+        for (this, other) in operands {
+            let Ok(op_equals) = this.read_attribute(INTERNED_OP_IMPL_EQUALS, &vm.globals) else {
+                continue;
+            };
+            match RuntimeValue::try_eval_rel_op(op_equals, other, cur_frame, vm) {
+                OperatorEvalAttemptOutcome::Ok(val) => return Ok(RunloopExit::Ok(val)),
+                OperatorEvalAttemptOutcome::Exception(e) => return Ok(RunloopExit::Exception(e)),
+                OperatorEvalAttemptOutcome::Error(err) => return Err(err),
+                OperatorEvalAttemptOutcome::NeedTryROperator => {}
+            }
+        }
+
+        Ok(RunloopExit::Ok(lhs.builtin_equals(rhs, cur_frame, vm)))
+    }
 }
 
 macro_rules! rel_op_impl {
@@ -446,6 +485,7 @@ impl std::fmt::Debug for RuntimeValue {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             Self::Integer(x) => write!(f, "{}", x.raw_value()),
+            Self::BigInteger(x) => write!(f, "{}", x.raw_value()),
             Self::Float(x) => write!(f, "{}", x.raw_value()),
             Self::Boolean(x) => write!(f, "{}", x.raw_value()),
             Self::String(s) => write!(f, "\"{}\"", s.raw_value()),
@@ -465,6 +505,7 @@ impl std::fmt::Debug for RuntimeValue {
             Self::Function(fnc) => write!(f, "{fnc:?}"),
             Self::BoundFunction(_) => write!(f, "<bound-function>"),
             Self::List(lt) => write!(f, "{lt:?}"),
+            Self::Map(m) => write!(f, "{m:?}"),
             Self::Type(t) => write!(f, "type<{t:?}>"),
             Self::TypeCheck(t) => write!(f, "type-check({t:?})"),
         }
@@ -533,6 +574,19 @@ impl RuntimeValue {
         RuntimeValue::BoundFunction(BoundFunction::bind(self.clone(), f))
     }
 
+    // identifies the values that the cycle collector may be tracking
+    pub(crate) fn gc_address(&self) -> Option<usize> {
+        match self {
+            Self::Object(o) => Some(crate::gc::address_of(&o.imp)),
+            Self::List(l) => Some(crate::gc::address_of(&l.imp)),
+            Self::Map(m) => Some(crate::gc::address_of(&m.imp)),
+            Self::Function(f) => Some(f.gc_address()),
+            Self::BoundFunction(b) => Some(b.gc_address()),
+            Self::EnumValue(e) => Some(crate::gc::address_of(&e.imp)),
+            _ => None,
+        }
+    }
+
     pub fn as_struct(&self) -> Option<&Struct> {
         self.as_type().and_then(|rt| rt.as_struct())
     }
@@ -559,13 +613,18 @@ impl RuntimeValue {
 
     pub fn is_builtin_type(&self) -> bool {
         match self {
-            Self::Integer(_) | Self::String(_) | Self::Float(_) | Self::Boolean(_) => true,
+            Self::Integer(_)
+            | Self::BigInteger(_)
+            | Self::String(_)
+            | Self::Float(_)
+            | Self::Boolean(_) => true,
             Self::Object(_)
             | Self::EnumValue(_)
             | Self::CodeObject(_)
             | Self::Function(_)
             | Self::BoundFunction(_)
             | Self::List(_)
+            | Self::Map(_)
             | Self::Mixin(_)
             | Self::Type(_)
             | Self::Module(_)
@@ -577,6 +636,7 @@ impl RuntimeValue {
     pub fn get_builtin_type_id(&self) -> Option<BuiltinTypeId> {
         match self {
             Self::Integer(x) => Some(x.builtin_type_id()),
+            Self::BigInteger(x) => Some(x.builtin_type_id()),
             Self::String(x) => Some(x.builtin_type_id()),
             Self::Float(x) => Some(x.builtin_type_id()),
             Self::Boolean(x) => Some(x.builtin_type_id()),
@@ -586,6 +646,7 @@ impl RuntimeValue {
             | Self::Function(_)
             | Self::BoundFunction(_)
             | Self::List(_)
+            | Self::Map(_)
             | Self::Mixin(_)
             | Self::Type(_)
             | Self::Module(_)
@@ -613,6 +674,52 @@ impl RuntimeValue {
         }
     }
 
+    // sets up `return self(...)` to reuse cur_frame, if self is a function
+    // that allows it; see Function::tail_call_in_frame
+    pub(crate) fn tail_call_in_frame(
+        &self,
+        argc: u8,
+        cur_frame: &mut Frame,
+        vm: &mut VirtualMachine,
+    ) -> bool {
+        if let Some(f) = self.as_function() {
+            f.tail_call_in_frame(argc, cur_frame, vm, &Default::default())
+        } else if let Some(bf) = self.as_bound_function() {
+            bf.tail_call_in_frame(argc, cur_frame, vm)
+        } else {
+            false
+        }
+    }
+
+    pub fn eval_with_keywords(
+        &self,
+        argc: u8,
+        keywords: Vec<(String, RuntimeValue)>,
+        cur_frame: &mut Frame,
+        vm: &mut VirtualMachine,
+        discard_result: bool,
+    ) -> ExecutionResult<CallResult> {
+        if let Some(f) = self.as_function() {
+            f.eval_with_keywords(
+                argc,
+                keywords,
+                cur_frame,
+                vm,
+                &Default::default(),
+                discard_result,
+            )
+        } else if let Some(bf) = self.as_bound_function() {
+            bf.eval_with_keywords(argc, keywords, cur_frame, vm, discard_result)
+        } else {
+            match self.read_attribute(INTERNED_OP_IMPL_CALL, &vm.globals) {
+                Ok(op_call) => {
+                    op_call.eval_with_keywords(argc, keywords, cur_frame, vm, discard_result)
+                }
+                _ => Err(crate::error::vm_error::VmErrorReason::UnexpectedType.into()),
+            }
+        }
+    }
+
     pub fn prettyprint(&self, cur_frame: &mut Frame, vm: &mut VirtualMachine) -> String {
         if let Ok(ppf) = self.read_attribute(INTERNED_OP_PRETTYPRINT, &vm.globals)
             && ppf.eval(0, cur_frame, vm, false).is_ok()
@@ -631,6 +738,7 @@ impl RuntimeValue {
     fn get_attribute_store(&self) -> Option<&object::ObjectBox> {
         match self {
             RuntimeValue::Integer(bv) => Some(&bv.imp.as_ref().boxx),
+            RuntimeValue::BigInteger(bv) => Some(&bv.imp.as_ref().boxx),
             RuntimeValue::String(bv) => Some(&bv.imp.as_ref().boxx),
             RuntimeValue::Float(bv) => Some(&bv.imp.as_ref().boxx),
             RuntimeValue::Boolean(bv) => Some(&bv.imp.as_ref().boxx),
@@ -640,6 +748,7 @@ impl RuntimeValue {
             RuntimeValue::Function(f) => Some(f.get_attribute_store()),
             RuntimeValue::BoundFunction(_) => None,
             RuntimeValue::List(l) => Some(&l.imp.as_ref().boxx),
+            RuntimeValue::Map(m) => Some(&m.imp.as_ref().boxx),
             RuntimeValue::Mixin(m) => Some(&m.imp.as_ref().entries),
             RuntimeValue::Type(t) => t.get_attribute_store(),
             RuntimeValue::Module(_) => None,
@@ -690,6 +799,11 @@ impl RuntimeValue {
             let bt = builtins.get_builtin_type_by_id(BuiltinTypeId::Int);
             attrs.extend(bt.list_attributes(builtins));
             push_resolved(attrs);
+        } else if let Some(i) = self.as_big_integer() {
+            let mut attrs = i.list_attributes(builtins);
+            let bt = builtins.get_builtin_type_by_id(BuiltinTypeId::Int);
+            attrs.extend(bt.list_attributes(builtins));
+            push_resolved(attrs);
         } else if let Some(i) = self.as_float() {
             let mut attrs = i.list_attributes(builtins);
             let bt = builtins.get_builtin_type_by_id(BuiltinTypeId::Float);
@@ -710,6 +824,11 @@ impl RuntimeValue {
             let bt = builtins.get_builtin_type_by_id(BuiltinTypeId::List);
             attrs.extend(bt.list_attributes(builtins));
             push_resolved(attrs);
+        } else if let Some(m) = self.as_map() {
+            let mut attrs = m.list_attributes(builtins);
+            let bt = builtins.get_builtin_type_by_id(BuiltinTypeId::Map);
+            attrs.extend(bt.list_attributes(builtins));
+            push_resolved(attrs);
         } else if let Some(f) = self.as_function() {
             push_resolved(f.list_attributes(builtins));
         } else if let Some(m) = self.as_module() {
@@ -750,6 +869,14 @@ impl RuntimeValue {
                         .and_then(|val| val_or_bound_func!(val, self).ok())
                 }
             },
+            RuntimeValue::BigInteger(bv) => match bv.imp.as_ref().boxx.read_slot(slot_id, sid) {
+                Some(val) => val_or_bound_func!(val, self).ok(),
+                None => {
+                    let bt = builtins.get_builtin_type_by_id(BuiltinTypeId::Int);
+                    Self::read_slot_from_type(&bt, slot_id, sid)
+                        .and_then(|val| val_or_bound_func!(val, self).ok())
+                }
+            },
             RuntimeValue::String(bv) => match bv.imp.as_ref().boxx.read_slot(slot_id, sid) {
                 Some(val) => val_or_bound_func!(val, self).ok(),
                 None => {
@@ -783,6 +910,14 @@ impl RuntimeValue {
                         .and_then(|val| val_or_bound_func!(val, self).ok())
                 }
             },
+            RuntimeValue::Map(m) => match m.imp.as_ref().boxx.read_slot(slot_id, sid) {
+                Some(val) => Some(val),
+                None => {
+                    let bt = builtins.get_builtin_type_by_id(BuiltinTypeId::Map);
+                    Self::read_slot_from_type(&bt, slot_id, sid)
+                        .and_then(|val| val_or_bound_func!(val, self).ok())
+                }
+            },
             RuntimeValue::Type(t) => {
                 let val = Self::read_slot_from_type(t, slot_id, sid)?;
                 if let Some(rf) = val.as_function() {
@@ -869,6 +1004,20 @@ impl RuntimeValue {
                         .map(|v| (v, val.1, val.2))
                 }
             },
+            RuntimeValue::BigInteger(bv) => {
+                match bv.imp.as_ref().boxx.resolve_to_slot(builtins, name) {
+                    Some(val) => val_or_bound_func!(val.0, self)
+                        .ok()
+                        .map(|v| (v, val.1, val.2)),
+                    None => {
+                        let bt = builtins.get_builtin_type_by_id(BuiltinTypeId::Int);
+                        let val = Self::resolve_to_slot_from_type(&bt, builtins, name)?;
+                        val_or_bound_func!(val.0, self)
+                            .ok()
+                            .map(|v| (v, val.1, val.2))
+                    }
+                }
+            }
             RuntimeValue::String(bv) => {
                 match bv.imp.as_ref().boxx.resolve_to_slot(builtins, name) {
                     Some(val) => val_or_bound_func!(val.0, self)
@@ -919,6 +1068,16 @@ impl RuntimeValue {
                         .map(|v| (v, val.1, val.2))
                 }
             },
+            RuntimeValue::Map(m) => match m.imp.as_ref().boxx.resolve_to_slot(builtins, name) {
+                Some(val) => Some(val),
+                None => {
+                    let bt = builtins.get_builtin_type_by_id(BuiltinTypeId::Map);
+                    let val = Self::resolve_to_slot_from_type(&bt, builtins, name)?;
+                    val_or_bound_func!(val.0, self)
+                        .ok()
+                        .map(|v| (v, val.1, val.2))
+                }
+            },
             RuntimeValue::Type(t) => {
                 let val = Self::resolve_to_slot_from_type(t, builtins, name)?;
                 if let Some(rf) = val.0.as_function() {
@@ -1003,6 +1162,19 @@ impl RuntimeValue {
                     }
                 }
             }
+        } else if let Some(m) = self.as_map() {
+            match m.read(builtins, attrib_sym) {
+                Some(val) => Ok(val),
+                _ => {
+                    let bt = builtins.get_builtin_type_by_id(BuiltinTypeId::Map);
+                    match bt.read_attribute(builtins, attrib_sym) {
+                        Ok(val) => {
+                            val_or_bound_func!(val, self)
+                        }
+                        _ => Err(AttributeError::NoSuchAttribute),
+                    }
+                }
+            }
         } else if let Some(t) = self.as_type() {
             let val = t.read_attribute(builtins, attrib_sym)?;
             if let Some(rf) = val.as_function() {
@@ -1036,6 +1208,28 @@ impl RuntimeValue {
         }
     }
 
+    // bounds that were left out of the slice are passed as Maybe::None
+    pub fn read_slice(
+        &self,
+        bounds: [Option<RuntimeValue>; 3],
+        cur_frame: &mut Frame,
+        vm: &mut VirtualMachine,
+    ) -> ExecutionResult<CallResult> {
+        match self.read_attribute(INTERNED_OP_IMPL_READ_SLICE, &vm.globals) {
+            Ok(read_slice) => {
+                for bound in bounds.into_iter().rev() {
+                    let bound = match bound {
+                        Some(val) => vm.globals.create_maybe_some(val)?,
+                        None => vm.globals.create_maybe_none()?,
+                    };
+                    cur_frame.stack.push(bound);
+                }
+                read_slice.eval(3, cur_frame, vm, false)
+            }
+            _ => Err(VmErrorReason::UnexpectedType.into()),
+        }
+    }
+
     pub fn write_index(
         &self,
         indices: &[RuntimeValue],
@@ -6,7 +6,11 @@ use std::{
 
 use rustc_data_structures::fx::FxHashSet;
 
-use crate::{error::vm_error::VmErrorReason, shape::ShapeId};
+use crate::{
+    error::vm_error::VmErrorReason,
+    gc::{self, Traced},
+    shape::ShapeId,
+};
 use crate::{shape::SlotId, symbol::Symbol};
 
 use super::{RuntimeValue, structure::Struct};
@@ -84,7 +88,7 @@ impl ObjectBox {
         Some((val, sid, slot_id))
     }
 
-    pub(super) fn list_attributes(
+    pub(crate) fn list_attributes(
         &self,
         builtins: &crate::builtins::VmGlobals,
     ) -> FxHashSet<Symbol> {
@@ -102,6 +106,18 @@ impl ObjectBox {
         ret
     }
 
+    pub(super) fn visit_values(&self, visit: &mut dyn FnMut(usize)) {
+        for val in self.get() {
+            crate::gc::visit_value(val, visit);
+        }
+    }
+
+    pub(super) fn clear(&self) {
+        self.shape.set(crate::shape::Shapes::EMPTY_SHAPE_INDEX);
+        let slots = std::mem::take(self.get_mut());
+        drop(slots);
+    }
+
     pub(crate) fn contains(&self, builtins: &crate::builtins::VmGlobals, name: Symbol) -> bool {
         let slot_count = self.get().len();
         if let Some(slot_id) = builtins.shapes.resolve_slot(self.shape.get(), name) {
@@ -155,11 +171,21 @@ impl ObjectImpl {
     }
 }
 
+impl Traced for ObjectImpl {
+    fn visit_children(&self, visit: &mut dyn FnMut(usize)) {
+        self.boxx.visit_values(visit);
+    }
+
+    fn clear(&self) {
+        self.boxx.clear();
+    }
+}
+
 impl Object {
     pub fn new(kind: &Struct) -> Self {
-        Self {
-            imp: Rc::new(ObjectImpl::new(kind)),
-        }
+        let imp = Rc::new(ObjectImpl::new(kind));
+        gc::track(&imp);
+        Self { imp }
     }
 
     pub(crate) fn read_slot(&self, slot_id: SlotId, sid: ShapeId) -> Option<RuntimeValue> {
@@ -16,6 +16,7 @@ pub struct CodeObject {
     pub body: Rc<[Opcode]>,
     pub required_argc: u8,
     pub default_argc: u8,
+    pub arg_names: Rc<[String]>,
     pub frame_size: u8,
     pub loc: SourcePointer,
     pub line_table: Rc<LineTable>,
@@ -58,6 +59,7 @@ impl TryFrom<&CompiledCodeObject> for CodeObject {
             body,
             required_argc: value.required_argc,
             default_argc: value.default_argc,
+            arg_names: value.arg_names.clone().into(),
             frame_size: value.frame_size,
             loc: value.loc.clone(),
             line_table: Rc::from(value.line_table.clone()),
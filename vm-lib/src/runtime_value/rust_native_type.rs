@@ -19,6 +19,7 @@ pub enum RustNativeValueKind {
     Integer,
     Float,
     List,
+    Map,
     String,
     Type,
 }
@@ -54,6 +55,12 @@ impl RustNativeTypeImpl {
         attrs.extend(self.mixins.borrow().list_attributes(builtins));
         attrs
     }
+
+    fn unmet_mixin_requirement(&self, builtins: &VmGlobals) -> Option<(String, Symbol)> {
+        self.mixins
+            .borrow()
+            .unmet_requirement(&self.list_attributes(builtins))
+    }
 }
 
 #[derive(Clone)]
@@ -114,6 +121,10 @@ impl RustNativeType {
     pub fn list_attributes(&self, builtins: &VmGlobals) -> FxHashSet<Symbol> {
         self.imp.list_attributes(builtins)
     }
+
+    pub fn unmet_mixin_requirement(&self, builtins: &VmGlobals) -> Option<(String, Symbol)> {
+        self.imp.unmet_mixin_requirement(builtins)
+    }
 }
 
 impl PartialEq for RustNativeType {
@@ -130,6 +141,7 @@ impl std::fmt::Debug for RustNativeType {
             RustNativeValueKind::Integer => write!(f, "Int"),
             RustNativeValueKind::Float => write!(f, "Float"),
             RustNativeValueKind::List => write!(f, "List"),
+            RustNativeValueKind::Map => write!(f, "Map"),
             RustNativeValueKind::String => write!(f, "String"),
             RustNativeValueKind::Type => write!(f, "Type"),
         }
@@ -1,7 +1,7 @@
 // SPDX-License-Identifier: Apache-2.0
 use std::{cell::RefCell, rc::Rc};
 
-use rustc_data_structures::fx::FxHashSet;
+use rustc_data_structures::fx::{FxHashMap, FxHashSet};
 
 use crate::{
     builtins::VmGlobals,
@@ -17,10 +17,18 @@ use super::{
     mixin::Mixin,
 };
 
+// the accessors of a computed property, called with the object bound as this
+#[derive(Clone, Default)]
+pub struct Property {
+    pub getter: Option<Function>,
+    pub setter: Option<Function>,
+}
+
 pub(super) struct StructImpl {
     name: String,
     pub(super) entries: ObjectBox,
     mixins: RefCell<crate::mixin_includer::MixinIncluder>,
+    properties: RefCell<FxHashMap<Symbol, Property>>,
 }
 
 impl StructImpl {
@@ -29,6 +37,16 @@ impl StructImpl {
             name: name.to_owned(),
             entries: ObjectBox::default(),
             mixins: RefCell::new(crate::mixin_includer::MixinIncluder::default()),
+            properties: Default::default(),
+        }
+    }
+
+    fn property(&self, name: Symbol) -> Option<Property> {
+        let properties = self.properties.borrow();
+        if properties.is_empty() {
+            None
+        } else {
+            properties.get(&name).cloned()
         }
     }
 
@@ -69,6 +87,12 @@ impl StructImpl {
         attrs.extend(self.mixins.borrow().list_attributes(builtins));
         attrs
     }
+
+    fn unmet_mixin_requirement(&self, builtins: &VmGlobals) -> Option<(String, Symbol)> {
+        self.mixins
+            .borrow()
+            .unmet_requirement(&self.list_attributes(builtins))
+    }
 }
 
 #[derive(Clone)]
@@ -102,6 +126,32 @@ impl Struct {
     pub fn list_attributes(&self, builtins: &VmGlobals) -> FxHashSet<Symbol> {
         self.imp.list_attributes(builtins)
     }
+
+    pub fn unmet_mixin_requirement(&self, builtins: &VmGlobals) -> Option<(String, Symbol)> {
+        self.imp.unmet_mixin_requirement(builtins)
+    }
+
+    pub fn property(&self, name: Symbol) -> Option<Property> {
+        self.imp.property(name)
+    }
+
+    pub fn bind_getter(&self, name: Symbol, getter: Function) {
+        self.imp
+            .properties
+            .borrow_mut()
+            .entry(name)
+            .or_default()
+            .getter = Some(getter);
+    }
+
+    pub fn bind_setter(&self, name: Symbol, setter: Function) {
+        self.imp
+            .properties
+            .borrow_mut()
+            .entry(name)
+            .or_default()
+            .setter = Some(setter);
+    }
 }
 
 impl PartialEq for Struct {
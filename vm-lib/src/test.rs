@@ -1,5 +1,12 @@
 // SPDX-License-Identifier: Apache-2.0
-use aria_compiler::compile_from_source;
+use aria_compiler::{
+    CompilationOptions, compile_from_source,
+    constant_value::ConstantValue,
+    do_compile::{CompilationErrorReason, CompilationWarningReason},
+    module::CompiledModule,
+    module_bundle::ModuleBundle,
+    module_file::source_hash,
+};
 use aria_parser::ast::SourceBuffer;
 
 use crate::{
@@ -7,7 +14,7 @@ use crate::{
     builtins::runtime_error::RUNTIME_ERR_CASE_UNEXPECTED_TYPE_IDX,
     error::vm_error::VmErrorReason,
     haxby_eval,
-    vm::{ExecutionResult, VmOptions},
+    vm::{ExecutionResult, RunloopExit, VirtualMachine, VmOptions},
 };
 
 fn exec_code(src: &'static str) -> ExecutionResult<HaxbyEvalResult> {
@@ -265,3 +272,530 @@ func main(x,y,z,...) {
             .is_err_and(|err| err.reason == VmErrorReason::InvalidMainSignature)
     );
 }
+
+#[test]
+fn test_module_survives_serialization() {
+    let input = r##"
+struct Pair {
+    type func new(a, b) {
+        return alloc(This) {.a = a, .b = b};
+    }
+
+    func sum() {
+        return this.a + this.b;
+    }
+}
+
+func main() {
+    val p = Pair.new(3, 4.5);
+    assert p.sum() == 7.5;
+    assert "hello" + " world" == "hello world";
+}
+"##;
+
+    let sb = SourceBuffer::stdin(input);
+    let module = compile_from_source(&sb, &Default::default()).expect("module did not compile");
+    let bytes = module.to_bytes(source_hash(input));
+    let module = CompiledModule::from_bytes(&bytes).expect("module did not deserialize");
+    assert_eq!(
+        module.load_entry_code_object().loc.buffer.content.as_str(),
+        input
+    );
+
+    let result = haxby_eval(module, Default::default()).expect("module did not run");
+    assert!(matches!(result.exit, RunloopExit::Ok(_)));
+}
+
+#[test]
+fn test_truncated_module_is_rejected() {
+    let sb = SourceBuffer::stdin("func main() {}");
+    let module = compile_from_source(&sb, &Default::default()).expect("module did not compile");
+    let bytes = module.to_bytes(0);
+
+    assert!(CompiledModule::from_bytes(&bytes[0..bytes.len() - 1]).is_err());
+    assert!(CompiledModule::from_bytes(b"not a module").is_err());
+}
+
+#[test]
+fn test_bundled_modules_are_imported() {
+    let helper = r##"
+func triple(x) {
+    return 3 * x;
+}
+"##;
+    let input = r##"
+import triple from bundled.helper;
+
+func main() {
+    assert triple(14) == 42;
+}
+"##;
+
+    let entry = compile_from_source(&SourceBuffer::stdin(input), &Default::default())
+        .expect("module did not compile");
+    assert!(
+        entry
+            .imported_paths()
+            .contains(&"bundled.helper".to_owned())
+    );
+    let helper = compile_from_source(&SourceBuffer::stdin(helper), &Default::default())
+        .expect("module did not compile");
+
+    let bundle = ModuleBundle {
+        entry,
+        imports: vec![("bundled.helper".to_owned(), helper)],
+    };
+    let bundle = ModuleBundle::from_bytes(&bundle.to_bytes()).expect("bundle did not deserialize");
+
+    let mut vm = VirtualMachine::default();
+    for (ipath, module) in bundle.imports {
+        vm.register_bundled_module("test.ariab", &ipath, module)
+            .expect("module was already registered");
+    }
+    let rm = match vm
+        .load_module("", bundle.entry)
+        .expect("module did not load")
+    {
+        RunloopExit::Ok(mli) => mli.module,
+        RunloopExit::Exception(_) => panic!("module threw an exception"),
+    };
+    let result = vm.execute_module(&rm).expect("module did not run");
+    assert!(matches!(result, RunloopExit::Ok(_)));
+}
+
+#[test]
+fn test_bundles_leave_out_sources() {
+    let input = r##"
+# only the source has this comment
+func main() {
+    throw 1;
+}
+"##;
+
+    let entry = compile_from_source(&SourceBuffer::stdin(input), &Default::default())
+        .expect("module did not compile");
+    let bytes = ModuleBundle {
+        entry,
+        imports: vec![],
+    }
+    .to_bytes();
+    let comment = b"only the source has this comment";
+    assert!(!bytes.windows(comment.len()).any(|w| w == comment));
+
+    let bundle = ModuleBundle::from_bytes(&bytes).expect("bundle did not deserialize");
+    match haxby_eval(bundle.entry, Default::default())
+        .expect("ok result expected")
+        .exit
+    {
+        RunloopExit::Ok(_) => panic!("expected exception to be thrown"),
+        RunloopExit::Exception(e) => {
+            // line numbers are still known without the source
+            let throw_site = e.backtrace.entries_iter().next().expect("empty backtrace");
+            assert!(!throw_site.buffer.has_content());
+            assert_eq!(
+                3,
+                throw_site
+                    .buffer
+                    .line_index_for_position(throw_site.location.start)
+            );
+        }
+    }
+}
+
+#[test]
+fn test_conflicting_bundled_modules_are_rejected() {
+    let helper = || {
+        compile_from_source(&SourceBuffer::stdin("func f() = 1;"), &Default::default())
+            .expect("module did not compile")
+    };
+
+    let mut vm = VirtualMachine::default();
+    vm.register_bundled_module("one.ariab", "bundled.helper", helper())
+        .expect("first registration should succeed");
+    assert_eq!(
+        Err(VmErrorReason::ConflictingBundledModule(
+            "bundled.helper".to_owned(),
+            "bundle one.ariab".to_owned()
+        )),
+        vm.register_bundled_module("two.ariab", "bundled.helper", helper())
+    );
+
+    // a module that was already imported from elsewhere cannot be replaced either
+    let loaded = match vm.load_module("", helper()).expect("module did not load") {
+        RunloopExit::Ok(mli) => mli.module,
+        RunloopExit::Exception(_) => panic!("module threw an exception"),
+    };
+    vm.inject_imported_module("other.helper", loaded);
+    assert!(matches!(
+        vm.register_bundled_module("one.ariab", "other.helper", helper()),
+        Err(VmErrorReason::ConflictingBundledModule(..))
+    ));
+}
+
+#[test]
+fn test_non_exhaustive_match_warns() {
+    let input = r##"
+enum Light {
+    case Red,
+    case Yellow,
+    case Green(Int),
+}
+
+func check(l) {
+    match l {
+        case Red => { return 1; },
+        case Green(n) => { return n; },
+    }
+    match l {
+        case Red => { return 1; },
+    } else {
+        return 0;
+    }
+    match l {
+        case Red => { return 1; },
+        x => { return 0; },
+    }
+    match l {
+        case Maybe => { return 1; },
+    }
+}
+
+val green = |l| => {
+    match l {
+        case Green(n) => { return n; },
+    }
+};
+"##;
+
+    let module = compile_from_source(&SourceBuffer::stdin(input), &Default::default())
+        .expect("module did not compile");
+    let warnings = module
+        .warnings
+        .iter()
+        .filter(|w| matches!(w.reason, CompilationWarningReason::NonExhaustiveMatch(..)))
+        .map(|w| w.reason.to_string())
+        .collect::<Vec<_>>();
+    assert_eq!(
+        vec![
+            "match over enum 'Light' does not handle Yellow",
+            "match over enum 'Light' does not handle Red, Yellow",
+        ],
+        warnings
+    );
+}
+
+#[test]
+fn test_yield_outside_function_does_not_compile() {
+    let input = r##"
+if true {
+    yield 1;
+}
+"##;
+
+    assert!(
+        compile_from_source(&SourceBuffer::stdin(input), &Default::default()).is_err_and(
+            |errors| matches!(
+                errors[0].reason,
+                CompilationErrorReason::FlowControlNotAllowed
+            )
+        )
+    );
+}
+
+#[test]
+fn test_await_outside_async_function_does_not_compile() {
+    let input = r##"
+func main() {
+    await 1;
+}
+"##;
+
+    assert!(
+        compile_from_source(&SourceBuffer::stdin(input), &Default::default()).is_err_and(
+            |errors| matches!(errors[0].reason, CompilationErrorReason::AwaitOutsideAsync)
+        )
+    );
+}
+
+#[test]
+fn test_yield_in_async_function_does_not_compile() {
+    let input = r##"
+async func main() {
+    yield 1;
+}
+"##;
+
+    assert!(
+        compile_from_source(&SourceBuffer::stdin(input), &Default::default()).is_err_and(
+            |errors| matches!(
+                errors[0].reason,
+                CompilationErrorReason::YieldInAsyncFunction
+            )
+        )
+    );
+}
+
+#[test]
+fn test_import_of_private_name_does_not_compile() {
+    let input = r##"
+import uses_helper, _helper from private_names.module;
+"##;
+
+    assert!(
+        compile_from_source(&SourceBuffer::stdin(input), &Default::default()).is_err_and(
+            |errors| matches!(
+                &errors[0].reason,
+                CompilationErrorReason::PrivateImport(name, module)
+                    if name == "_helper" && module == "private_names.module"
+            )
+        )
+    );
+}
+
+#[test]
+fn test_missing_mixin_requirement_fails() {
+    let input = r##"
+mixin Counter {
+    require func next();
+
+    func twice() = this.next() + this.next();
+}
+
+struct Bad {
+    include Counter
+}
+
+func main() {}
+"##;
+
+    assert!(exec_code(input).is_err_and(|err| err.reason
+        == VmErrorReason::MixinRequirementNotMet("Bad".to_owned(), "Counter.next".to_owned())));
+}
+
+#[test]
+fn test_property_in_mixin_does_not_compile() {
+    let input = r##"
+mixin Sized {
+    prop size {
+        get => 0;
+    }
+}
+"##;
+
+    assert!(
+        compile_from_source(&SourceBuffer::stdin(input), &Default::default()).is_err_and(
+            |errors| matches!(
+                &errors[0].reason,
+                CompilationErrorReason::PropertyOutsideStruct
+            )
+        )
+    );
+}
+
+#[test]
+fn test_unknown_loop_label_does_not_compile() {
+    let input = r##"
+func main() {
+    outer: while true {
+        val f = || => {
+            while true {
+                break outer;
+            }
+        };
+        break inner;
+    }
+}
+"##;
+
+    assert!(
+        compile_from_source(&SourceBuffer::stdin(input), &Default::default()).is_err_and(
+            |errors| matches!(
+                &errors[0].reason,
+                CompilationErrorReason::NoSuchLoopLabel(label) if label == "outer"
+            )
+        )
+    );
+}
+
+#[test]
+fn test_optimizer_folds_constants() {
+    let input = r##"
+func main() {
+    return 1000 + 2 * 3 * 7;
+}
+"##;
+
+    let sb = SourceBuffer::stdin(input);
+    let folded = ConstantValue::Integer(1042);
+    let module = compile_from_source(&sb, &Default::default()).expect("module did not compile");
+    assert!(module.constants.values().any(|cv| *cv == folded));
+
+    let options = CompilationOptions {
+        optimize: false,
+        ..Default::default()
+    };
+    let module = compile_from_source(&sb, &options).expect("module did not compile");
+    assert!(!module.constants.values().any(|cv| *cv == folded));
+}
+
+#[test]
+fn test_compiler_warnings() {
+    let input = r##"
+import Map from aria.structures.map;
+import Set from aria.structures.set;
+
+func f(items) {
+    val unused = 1;
+    val _ignored = 2;
+    val total = 0;
+    val last = 0;
+    for item in items {
+        val total = item;
+        last = item;
+        Set.new();
+    }
+    val captured = 3;
+    val g = || => captured;
+    return g;
+    total = 1;
+}
+"##;
+
+    let module = compile_from_source(&SourceBuffer::stdin(input), &Default::default())
+        .expect("module did not compile");
+    let warnings: Vec<_> = module
+        .warnings
+        .iter()
+        .map(|w| w.reason.to_string())
+        .collect();
+    assert_eq!(
+        vec![
+            "'Map' is imported but never used",
+            "variable 'unused' is never read",
+            "variable 'total' is assigned but never read",
+            "variable 'last' is assigned but never read",
+            "'total' shadows an earlier variable with the same name",
+            "variable 'total' is never read",
+            "this code will never run",
+        ],
+        warnings
+    );
+}
+
+#[test]
+fn test_unresolved_identifiers() {
+    let input = r##"
+import Map from aria.structures.map;
+
+val limit = 10;
+
+func count_up(items) {
+    val total = 0;
+    for item in items {
+        totl += item;
+    }
+    prinln(total);
+    mising_value = Map.new();
+    return later(total) + limt + Whatever;
+}
+
+func later(n) = n;
+"##;
+
+    let module = compile_from_source(&SourceBuffer::stdin(input), &Default::default())
+        .expect("module did not compile");
+    let mut vm = VirtualMachine::default();
+    let module = match vm.load_module("", module).expect("module did not load") {
+        RunloopExit::Ok(m) => m.module,
+        RunloopExit::Exception(_) => panic!("module threw while loading"),
+    };
+    let warnings: Vec<_> = module
+        .warnings()
+        .iter()
+        .filter(|w| matches!(w.reason, CompilationWarningReason::UnresolvedIdentifier(..)))
+        .map(|w| w.reason.to_string())
+        .collect();
+    assert_eq!(
+        vec![
+            "'totl' is not defined; did you mean 'total'?",
+            "'prinln' is not defined; did you mean 'println'?",
+            "'mising_value' is not defined",
+            "'limt' is not defined; did you mean 'limit'?",
+            "'Whatever' is not defined",
+        ],
+        warnings
+    );
+}
+
+#[test]
+fn test_parser_reports_every_syntax_error() {
+    let input = r##"
+func f(x) {
+    val y = x + ;
+    if y > 1 {
+        println("big")
+    } else {
+        println(|a| => { return a; }, 2 3);
+    }
+    return y;
+}
+
+struct S {
+    func m( {
+    }
+}
+
+func main() {
+    f(1);
+}
+"##;
+
+    let errors = match compile_from_source(&SourceBuffer::stdin(input), &Default::default()) {
+        Ok(_) => panic!("module should not compile"),
+        Err(errors) => errors,
+    };
+    let lines: Vec<_> = errors
+        .iter()
+        .map(|e| {
+            assert!(matches!(e.reason, CompilationErrorReason::ParserError(_)));
+            input[..e.loc.location.start].lines().count()
+        })
+        .collect();
+    assert_eq!(vec![3, 6, 7, 13], lines);
+}
+
+#[test]
+fn test_parser_stops_after_many_syntax_errors() {
+    let input = "func f() {\n    val x = ;\n}\n".repeat(500);
+
+    let errors = match compile_from_source(&SourceBuffer::stdin(&input), &Default::default()) {
+        Ok(_) => panic!("module should not compile"),
+        Err(errors) => errors,
+    };
+    assert_eq!(100, errors.len());
+}
+
+#[test]
+fn test_tail_calls_elide_frames_from_backtrace() {
+    let input = r##"
+func fail(n) {
+    if n == 0 {
+        throw 1;
+    }
+    return fail(n - 1);
+}
+
+func main() {
+    fail(100000);
+}
+"##;
+
+    match exec_code(input).expect("ok result expected").exit {
+        RunloopExit::Ok(_) => panic!("expected exception to be thrown"),
+        RunloopExit::Exception(e) => {
+            // the throw and the call in main, but none of the tail calls
+            assert_eq!(2, e.backtrace.len());
+            assert_eq!(100000, e.backtrace.elided_frames());
+        }
+    }
+}
@@ -6,11 +6,15 @@ use std::{
     rc::Rc,
 };
 
-use aria_compiler::{compile_from_source, module::CompiledModule};
+use aria_compiler::{
+    check_unresolved_identifiers, compile_from_source, compile_from_source_cached,
+    do_compile::CompilationWarning, module::CompiledModule,
+};
 use aria_parser::ast::SourceBuffer;
 use haxby_opcodes::{
     BuiltinTypeId, OPCODE_BIND_CASE, OPCODE_ENUM_CHECK_IS_CASE, OPCODE_NEW_ENUM_VAL,
-    OPCODE_READ_ATTRIBUTE, OPCODE_WRITE_ATTRIBUTE, Opcode, enum_case_attribs::CASE_HAS_PAYLOAD,
+    OPCODE_READ_ATTRIBUTE, OPCODE_TRY_READ_ATTRIBUTE, OPCODE_WRITE_ATTRIBUTE, Opcode,
+    enum_case_attribs::CASE_HAS_PAYLOAD, list_check_len_attribs::LEN_AT_LEAST,
 };
 use std::sync::OnceLock;
 
@@ -32,9 +36,11 @@ use crate::{
         RuntimeValue,
         enumeration::{Enum, EnumCase},
         function::Function,
+        integer::{self, IntegerOp},
         isa::IsaCheckable,
         kind::RuntimeValueType,
         list::List,
+        map::Map,
         mixin::Mixin,
         object::Object,
         structure::Struct,
@@ -52,6 +58,9 @@ pub struct VmOptions {
     pub dump_stack: bool,
     pub vm_args: Vec<String>,
     pub console: ConsoleHandle,
+    pub use_module_cache: bool,
+    // how many values may be created before the cycle collector runs (0 never runs it)
+    pub gc_threshold: usize,
 }
 
 impl Default for VmOptions {
@@ -63,6 +72,11 @@ impl Default for VmOptions {
             dump_stack: Default::default(),
             vm_args: Default::default(),
             console: Rc::new(RefCell::new(StdConsole {})),
+            use_module_cache: std::env::var_os("ARIA_NO_MODULE_CACHE").is_none(),
+            gc_threshold: std::env::var("ARIA_GC_THRESHOLD")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(crate::gc::DEFAULT_GC_THRESHOLD),
         }
     }
 }
@@ -73,6 +87,10 @@ pub struct VirtualMachine {
     pub globals: VmGlobals,
     pub import_stack: Stack<String>,
     pub imported_modules: HashMap<String, ModuleLoadInfo>,
+    // modules shipped in a bundle, which take precedence over the filesystem
+    // and are moved into imported_modules once they have been loaded; each
+    // remembers the bundle it came from, to report conflicting bundles
+    bundled_modules: HashMap<String, (String, CompiledModule)>,
     pub loaded_dylibs: HashMap<String, libloading::Library>,
     frame_pool: Vec<Frame>,
 }
@@ -82,7 +100,17 @@ impl VirtualMachine {
         &self.options.console
     }
 
+    // every call is a safe point for the cycle collector, since it only
+    // ever frees values that nothing outside of a cycle refers to
+    pub(crate) fn gc_safe_point(&mut self) {
+        if crate::gc::should_collect(self.options.gc_threshold) {
+            crate::gc::collect();
+        }
+    }
+
     pub(crate) fn acquire_frame(&mut self, f: &Function) -> Frame {
+        self.gc_safe_point();
+
         let mut frame = self.frame_pool.pop().unwrap_or_default();
         frame.reset_for_function(f);
         frame
@@ -116,6 +144,7 @@ impl VirtualMachine {
             globals: Default::default(),
             import_stack: Default::default(),
             imported_modules: Default::default(),
+            bundled_modules: Default::default(),
             loaded_dylibs: Default::default(),
             frame_pool: Default::default(),
         }
@@ -133,7 +162,7 @@ macro_rules! build_vm_error {
         Err($crate::error::vm_error::VmError {
             reason: $reason,
             opcode: Some($next),
-            loc: lt,
+            loc: lt.map(Box::new),
             backtrace: Default::default(),
         })
     }};
@@ -206,6 +235,24 @@ macro_rules! binop_eval {
     };
 }
 
+macro_rules! call_eval {
+    ( ($call_expr: expr), $next: expr, $frame: expr, $op_idx: expr) => {
+        match $call_expr {
+            Ok(crate::runtime_value::CallResult::Ok(_)) => {}
+            Ok(crate::runtime_value::CallResult::Exception(e)) => {
+                return Ok(OpcodeRunExit::Exception(e));
+            }
+            Err(err) => {
+                if err.loc.is_some() {
+                    return Err(err);
+                } else {
+                    return build_vm_error!(err.reason, $next, $frame, $op_idx);
+                }
+            }
+        }
+    };
+}
+
 macro_rules! unaryop_eval {
     ( ($op_expr: expr), $next: expr, $frame: expr, $op_idx: expr) => {
         match $op_expr {
@@ -362,7 +409,7 @@ impl VirtualMachine {
         }
     }
 
-    fn resolve_import_path_to_path(
+    pub fn resolve_import_path_to_path(
         ipath: &str,
         widget_root_path: Option<&PathBuf>,
     ) -> Result<PathBuf, VmErrorReason> {
@@ -398,6 +445,36 @@ impl VirtualMachine {
         ))
     }
 
+    // locates and compiles the module that ipath refers to, as the import statement would
+    pub fn compile_imported_module(
+        ipath: &str,
+        widget_root_path: Option<&PathBuf>,
+        use_module_cache: bool,
+    ) -> Result<CompiledModule, VmErrorReason> {
+        let import_path = Self::resolve_import_path_to_path(ipath, widget_root_path)?;
+
+        let sb = SourceBuffer::from_path(&import_path).map_err(|_| {
+            VmErrorReason::ImportNotAvailable(ipath.to_owned(), "no such file".to_owned())
+        })?;
+
+        let c_module = if use_module_cache {
+            compile_from_source_cached(&sb, &Default::default())
+        } else {
+            compile_from_source(&sb, &Default::default())
+        };
+        c_module.map_err(|ces| {
+            let err_msg = ces
+                .iter()
+                .map(|x| format!("error: {x}"))
+                .collect::<Vec<_>>()
+                .join("\n");
+            VmErrorReason::ImportNotAvailable(
+                ipath.to_owned(),
+                format!("module failed to compile: {err_msg}"),
+            )
+        })
+    }
+
     fn create_import_model_from_path(
         module: &RuntimeModule,
         builtins: &mut VmGlobals,
@@ -470,7 +547,10 @@ impl VirtualMachine {
         match entry_result {
             Ok(ok) => match ok {
                 crate::runtime_value::CallResult::Exception(e) => Ok(RunloopExit::Exception(e)),
-                _ => Ok(RunloopExit::Ok(ModuleLoadInfo { module: r_mod })),
+                _ => {
+                    r_mod.add_warnings(self.check_unresolved_identifiers(&r_mod));
+                    Ok(RunloopExit::Ok(ModuleLoadInfo { module: r_mod }))
+                }
             },
             Err(err) => Err(err),
         }
@@ -485,6 +565,14 @@ impl VirtualMachine {
         self.load_into_module(name, r_mod)
     }
 
+    // the names that a loaded module uses but that resolve neither to one of
+    // its own values (which include everything it imported) nor to a builtin
+    fn check_unresolved_identifiers(&self, module: &RuntimeModule) -> Vec<CompilationWarning> {
+        let mut known_names = module.list_named_values();
+        known_names.extend(self.globals.list_named_values());
+        check_unresolved_identifiers(module.get_compiled_module(), &known_names)
+    }
+
     pub fn get_module_by_name(&self, name: &str) -> Option<RuntimeModule> {
         self.modules.get(name).cloned()
     }
@@ -495,6 +583,33 @@ impl VirtualMachine {
             .map(|mli| mli.module.clone())
     }
 
+    // a module can only come from one place: registering it twice, or after
+    // it was already imported from the filesystem, is an error rather than
+    // letting whichever was first win
+    pub fn register_bundled_module(
+        &mut self,
+        bundle: &str,
+        ipath: &str,
+        module: CompiledModule,
+    ) -> Result<(), VmErrorReason> {
+        if let Some((other, _)) = self.bundled_modules.get(ipath) {
+            return Err(VmErrorReason::ConflictingBundledModule(
+                ipath.to_owned(),
+                format!("bundle {other}"),
+            ));
+        }
+        if let Some(mli) = self.imported_modules.get(ipath) {
+            return Err(VmErrorReason::ConflictingBundledModule(
+                ipath.to_owned(),
+                mli.module.load_entry_code_object().loc.buffer.name.clone(),
+            ));
+        }
+
+        self.bundled_modules
+            .insert(ipath.to_owned(), (bundle.to_owned(), module));
+        Ok(())
+    }
+
     pub fn inject_imported_module(&mut self, name: &str, module: RuntimeModule) {
         self.imported_modules
             .insert(name.to_owned(), ModuleLoadInfo { module });
@@ -564,7 +679,23 @@ impl VirtualMachine {
         sidecar: &SidecarSlice,
         target_frame: &mut Frame,
     ) -> ExecutionResult<RunloopExit> {
-        self.runloop(bc, sidecar, module, target_frame)
+        let mut result = self.runloop(bc, sidecar, module, target_frame);
+        // a tail call that reused the frame returns with the callee set up in
+        // it, to be run here instead of in a nested call
+        while matches!(result, Ok(RunloopExit::Ok(())))
+            && std::mem::take(&mut target_frame.pending_tail_call)
+        {
+            let callee = target_frame
+                .func
+                .clone()
+                .expect("tail call without a function");
+            let bcf = callee
+                .imp
+                .as_bytecode_function()
+                .expect("tail call into a builtin function");
+            result = self.runloop(&bcf.body, &bcf.sidecar, &bcf.module, target_frame);
+        }
+        result
     }
 
     fn run_opcode(
@@ -628,7 +759,7 @@ impl VirtualMachine {
                 let x = pop_or_err!(next, frame, op_idx);
                 let y = pop_or_err!(next, frame, op_idx);
                 if let (RuntimeValue::Integer(a), RuntimeValue::Integer(b)) = (&x, &y) {
-                    frame.stack.push(RuntimeValue::Integer(b + a));
+                    frame.stack.push(b + a);
                 } else if let (RuntimeValue::Integer(a), RuntimeValue::Float(b)) = (&x, &y) {
                     frame.stack.push(RuntimeValue::Float(b + &a.to_fp()));
                 } else if let (RuntimeValue::Float(a), RuntimeValue::Integer(b)) = (&x, &y) {
@@ -637,6 +768,8 @@ impl VirtualMachine {
                     frame.stack.push(RuntimeValue::String(b + a));
                 } else if let (RuntimeValue::Float(a), RuntimeValue::Float(b)) = (&x, &y) {
                     frame.stack.push(RuntimeValue::Float(b + a))
+                } else if let Some(res) = integer::big_integer_binop(IntegerOp::Add, &y, &x) {
+                    binop_eval!((res), next, frame, op_idx)
                 } else {
                     binop_eval!(
                         (RuntimeValue::add(&y, &x, frame, self)),
@@ -650,13 +783,15 @@ impl VirtualMachine {
                 let x = pop_or_err!(next, frame, op_idx);
                 let y = pop_or_err!(next, frame, op_idx);
                 if let (RuntimeValue::Integer(a), RuntimeValue::Integer(b)) = (&x, &y) {
-                    frame.stack.push(RuntimeValue::Integer(b - a));
+                    frame.stack.push(b - a);
                 } else if let (RuntimeValue::Float(a), RuntimeValue::Float(b)) = (&x, &y) {
                     frame.stack.push(RuntimeValue::Float(b - a))
                 } else if let (RuntimeValue::Integer(a), RuntimeValue::Float(b)) = (&x, &y) {
                     frame.stack.push(RuntimeValue::Float(b - &a.to_fp()));
                 } else if let (RuntimeValue::Float(a), RuntimeValue::Integer(b)) = (&x, &y) {
                     frame.stack.push(RuntimeValue::Float(&b.to_fp() - a));
+                } else if let Some(res) = integer::big_integer_binop(IntegerOp::Sub, &y, &x) {
+                    binop_eval!((res), next, frame, op_idx)
                 } else {
                     binop_eval!(
                         (RuntimeValue::sub(&y, &x, frame, self)),
@@ -670,13 +805,15 @@ impl VirtualMachine {
                 let x = pop_or_err!(next, frame, op_idx);
                 let y = pop_or_err!(next, frame, op_idx);
                 if let (RuntimeValue::Integer(a), RuntimeValue::Integer(b)) = (&x, &y) {
-                    frame.stack.push(RuntimeValue::Integer(b * a));
+                    frame.stack.push(b * a);
                 } else if let (RuntimeValue::Float(a), RuntimeValue::Float(b)) = (&x, &y) {
                     frame.stack.push(RuntimeValue::Float(b * a))
                 } else if let (RuntimeValue::Integer(a), RuntimeValue::Float(b)) = (&x, &y) {
                     frame.stack.push(RuntimeValue::Float(b * &a.to_fp()));
                 } else if let (RuntimeValue::Float(a), RuntimeValue::Integer(b)) = (&x, &y) {
                     frame.stack.push(RuntimeValue::Float(&b.to_fp() * a));
+                } else if let Some(res) = integer::big_integer_binop(IntegerOp::Mul, &y, &x) {
+                    binop_eval!((res), next, frame, op_idx)
                 } else {
                     binop_eval!(
                         (RuntimeValue::mul(&y, &x, frame, self)),
@@ -693,7 +830,7 @@ impl VirtualMachine {
                     if *a.raw_value() == 0 {
                         return build_vm_error!(VmErrorReason::DivisionByZero, next, frame, op_idx);
                     }
-                    frame.stack.push(RuntimeValue::Integer(b / a));
+                    frame.stack.push(b / a);
                 } else if let (RuntimeValue::Float(a), RuntimeValue::Float(b)) = (&x, &y) {
                     if *a.raw_value() == 0.0 {
                         return build_vm_error!(VmErrorReason::DivisionByZero, next, frame, op_idx);
@@ -709,6 +846,8 @@ impl VirtualMachine {
                         return build_vm_error!(VmErrorReason::DivisionByZero, next, frame, op_idx);
                     }
                     frame.stack.push(RuntimeValue::Float(&b.to_fp() / a));
+                } else if let Some(res) = integer::big_integer_binop(IntegerOp::Div, &y, &x) {
+                    binop_eval!((res), next, frame, op_idx)
                 } else {
                     binop_eval!(
                         (RuntimeValue::div(&y, &x, frame, self)),
@@ -725,7 +864,7 @@ impl VirtualMachine {
                     if *a.raw_value() == 0 {
                         return build_vm_error!(VmErrorReason::DivisionByZero, next, frame, op_idx);
                     }
-                    frame.stack.push(RuntimeValue::Integer(b % a));
+                    frame.stack.push(b % a);
                 } else if let (RuntimeValue::Float(a), RuntimeValue::Float(b)) = (&x, &y) {
                     if *a.raw_value() == 0.0 {
                         return build_vm_error!(VmErrorReason::DivisionByZero, next, frame, op_idx);
@@ -741,6 +880,8 @@ impl VirtualMachine {
                         return build_vm_error!(VmErrorReason::DivisionByZero, next, frame, op_idx);
                     }
                     frame.stack.push(RuntimeValue::Float(&b.to_fp() % a))
+                } else if let Some(res) = integer::big_integer_binop(IntegerOp::Rem, &y, &x) {
+                    binop_eval!((res), next, frame, op_idx)
                 } else {
                     binop_eval!(
                         (RuntimeValue::rem(&y, &x, frame, self)),
@@ -753,7 +894,9 @@ impl VirtualMachine {
             Opcode::Neg => {
                 let n = pop_or_err!(next, frame, op_idx);
                 if let RuntimeValue::Integer(i) = &n {
-                    frame.stack.push(RuntimeValue::Integer(-i));
+                    frame.stack.push(-i);
+                } else if let RuntimeValue::BigInteger(i) = &n {
+                    frame.stack.push(-i);
                 } else if let RuntimeValue::Float(i) = &n {
                     frame.stack.push(RuntimeValue::Float(-i));
                 } else {
@@ -764,7 +907,10 @@ impl VirtualMachine {
                 let by = pop_or_err!(next, frame, op_idx);
                 let n = pop_or_err!(next, frame, op_idx);
                 if let (RuntimeValue::Integer(n), RuntimeValue::Integer(by)) = (&n, &by) {
-                    frame.stack.push(RuntimeValue::Integer(n << by));
+                    frame.stack.push(n << by);
+                } else if let Some(res) = integer::big_integer_binop(IntegerOp::ShiftLeft, &n, &by)
+                {
+                    binop_eval!((res), next, frame, op_idx)
                 } else {
                     binop_eval!(
                         (RuntimeValue::leftshift(&n, &by, frame, self)),
@@ -778,7 +924,10 @@ impl VirtualMachine {
                 let by = pop_or_err!(next, frame, op_idx);
                 let n = pop_or_err!(next, frame, op_idx);
                 if let (RuntimeValue::Integer(n), RuntimeValue::Integer(by)) = (&n, &by) {
-                    frame.stack.push(RuntimeValue::Integer(n >> by));
+                    frame.stack.push(n >> by);
+                } else if let Some(res) = integer::big_integer_binop(IntegerOp::ShiftRight, &n, &by)
+                {
+                    binop_eval!((res), next, frame, op_idx)
                 } else {
                     binop_eval!(
                         (RuntimeValue::rightshift(&n, &by, frame, self)),
@@ -818,6 +967,10 @@ impl VirtualMachine {
                     frame
                         .stack
                         .push(RuntimeValue::Boolean(Into::into(b.to_fp() < *a)));
+                } else if let Some(ord) = integer::big_integer_compare(&y, &x) {
+                    frame.stack.push(RuntimeValue::Boolean(Into::into(
+                        ord.is_some_and(std::cmp::Ordering::is_lt),
+                    )));
                 } else {
                     binop_eval!(
                         (RuntimeValue::less_than(&y, &x, frame, self)),
@@ -842,6 +995,10 @@ impl VirtualMachine {
                     frame
                         .stack
                         .push(RuntimeValue::Boolean(Into::into(b.to_fp() <= *a)));
+                } else if let Some(ord) = integer::big_integer_compare(&y, &x) {
+                    frame.stack.push(RuntimeValue::Boolean(Into::into(
+                        ord.is_some_and(std::cmp::Ordering::is_le),
+                    )));
                 } else {
                     binop_eval!(
                         (RuntimeValue::less_than_equal(&y, &x, frame, self)),
@@ -866,6 +1023,10 @@ impl VirtualMachine {
                     frame
                         .stack
                         .push(RuntimeValue::Boolean(Into::into(b.to_fp() > *a)));
+                } else if let Some(ord) = integer::big_integer_compare(&y, &x) {
+                    frame.stack.push(RuntimeValue::Boolean(Into::into(
+                        ord.is_some_and(std::cmp::Ordering::is_gt),
+                    )));
                 } else {
                     binop_eval!(
                         (RuntimeValue::greater_than(&y, &x, frame, self)),
@@ -890,6 +1051,10 @@ impl VirtualMachine {
                     frame
                         .stack
                         .push(RuntimeValue::Boolean(Into::into(b.to_fp() >= *a)));
+                } else if let Some(ord) = integer::big_integer_compare(&y, &x) {
+                    frame.stack.push(RuntimeValue::Boolean(Into::into(
+                        ord.is_some_and(std::cmp::Ordering::is_ge),
+                    )));
                 } else {
                     binop_eval!(
                         (RuntimeValue::greater_than_equal(&y, &x, frame, self)),
@@ -1015,6 +1180,33 @@ impl VirtualMachine {
                     }
                 }
             }
+            Opcode::ReadSlice(flags) => {
+                use haxby_opcodes::read_slice_attribs::{HAS_START, HAS_STEP, HAS_STOP};
+                let mut bounds: [Option<RuntimeValue>; 3] = [None, None, None];
+                for (i, flag) in [HAS_START, HAS_STOP, HAS_STEP]
+                    .into_iter()
+                    .enumerate()
+                    .rev()
+                {
+                    if flags & flag == flag {
+                        bounds[i] = Some(pop_or_err!(next, frame, op_idx));
+                    }
+                }
+                let cnt = pop_or_err!(next, frame, op_idx);
+                match cnt.read_slice(bounds, frame, self) {
+                    Ok(crate::runtime_value::CallResult::Ok(_)) => {}
+                    Ok(crate::runtime_value::CallResult::Exception(e)) => {
+                        return Ok(OpcodeRunExit::Exception(e));
+                    }
+                    Err(e) => {
+                        return if e.loc.is_none() {
+                            build_vm_error!(e.reason, next, frame, op_idx)
+                        } else {
+                            Err(e)
+                        };
+                    }
+                }
+            }
             Opcode::WriteIndex(n) => {
                 let val = pop_or_err!(next, frame, op_idx);
                 let mut indices = Vec::<_>::with_capacity(n as usize);
@@ -1046,6 +1238,41 @@ impl VirtualMachine {
                     op_idx
                 );
             }
+            Opcode::TryReadAttribute(_) => {
+                return build_vm_error!(
+                    VmErrorReason::UnknownOpcode(OPCODE_TRY_READ_ATTRIBUTE),
+                    next,
+                    frame,
+                    op_idx
+                );
+            }
+            // pushes the attribute and true, or just false if the value has no
+            // attribute by that name; patterns use it to skip values that lack a field
+            Opcode::TryReadAttributeSymbol(n) => {
+                let n = crate::symbol::Symbol(n);
+                let val_obj = pop_or_err!(next, frame, op_idx);
+
+                match val_obj.read_attribute(n, &self.globals) {
+                    Ok(val) => frame.stack.push(val),
+                    Err(_) => {
+                        let getter = val_obj
+                            .as_object()
+                            .and_then(|obj| obj.get_struct().property(n))
+                            .and_then(|property| property.getter);
+                        let Some(getter) = getter else {
+                            frame.stack.push(RuntimeValue::Boolean(false.into()));
+                            return Ok(OpcodeRunExit::Continue);
+                        };
+                        call_eval!(
+                            (val_obj.bind(getter).eval(0, frame, self, false)),
+                            next,
+                            frame,
+                            op_idx
+                        );
+                    }
+                }
+                frame.stack.push(RuntimeValue::Boolean(true.into()));
+            }
             Opcode::WriteAttribute(_) => {
                 return build_vm_error!(
                     VmErrorReason::UnknownOpcode(OPCODE_WRITE_ATTRIBUTE),
@@ -1103,7 +1330,35 @@ impl VirtualMachine {
                     })));
                 }
 
-                match val_obj.read_attribute(n, &self.globals) {
+                let read = val_obj.read_attribute(n, &self.globals);
+                // computed properties are only looked for once there is no
+                // field or method by that name
+                if let Err(crate::runtime_value::AttributeError::NoSuchAttribute) = read
+                    && let Some(property) = val_obj
+                        .as_object()
+                        .and_then(|obj| obj.get_struct().property(n))
+                {
+                    let Some(getter) = property.getter else {
+                        return build_vm_error!(
+                            VmErrorReason::OperationFailed(format!(
+                                "property '{}' has no getter",
+                                self.globals.resolve_symbol(n).unwrap_or("<unknown>")
+                            )),
+                            next,
+                            frame,
+                            op_idx
+                        );
+                    };
+                    call_eval!(
+                        (val_obj.bind(getter).eval(0, frame, self, false)),
+                        next,
+                        frame,
+                        op_idx
+                    );
+                    return Ok(OpcodeRunExit::Continue);
+                }
+
+                match read {
                     Ok(val) => {
                         frame.stack.push(val);
                     }
@@ -1130,6 +1385,32 @@ impl VirtualMachine {
             Opcode::WriteAttributeSymbol(n) => {
                 let val = pop_or_err!(next, frame, op_idx);
                 let obj = pop_or_err!(next, frame, op_idx);
+                if let Some(property) = obj
+                    .as_object()
+                    .and_then(|o| o.get_struct().property(crate::symbol::Symbol(n)))
+                {
+                    let Some(setter) = property.setter else {
+                        return build_vm_error!(
+                            VmErrorReason::OperationFailed(format!(
+                                "property '{}' has no setter",
+                                self.globals
+                                    .resolve_symbol(crate::symbol::Symbol(n))
+                                    .unwrap_or("<unknown>")
+                            )),
+                            next,
+                            frame,
+                            op_idx
+                        );
+                    };
+                    frame.stack.push(val);
+                    call_eval!(
+                        (obj.bind(setter).eval(1, frame, self, true)),
+                        next,
+                        frame,
+                        op_idx
+                    );
+                    return Ok(OpcodeRunExit::Continue);
+                }
                 match obj.write_attribute(crate::symbol::Symbol(n), val, &mut self.globals) {
                     Ok(_) => {}
                     Err(err) => {
@@ -1177,6 +1458,8 @@ impl VirtualMachine {
                     frame.stack.push(RuntimeValue::Boolean(a ^ b));
                 } else if let (RuntimeValue::Integer(a), RuntimeValue::Integer(b)) = (&x, &y) {
                     frame.stack.push(RuntimeValue::Integer(a ^ b));
+                } else if let Some(res) = integer::big_integer_binop(IntegerOp::Xor, &y, &x) {
+                    binop_eval!((res), next, frame, op_idx)
                 } else {
                     binop_eval!(
                         (RuntimeValue::xor(&y, &x, frame, self)),
@@ -1195,6 +1478,9 @@ impl VirtualMachine {
                     && let Ok(y) = IsaCheckable::try_from(&y)
                 {
                     frame.stack.push(RuntimeValue::TypeCheck(x & &y));
+                } else if let Some(res) = integer::big_integer_binop(IntegerOp::BitwiseAnd, &y, &x)
+                {
+                    binop_eval!((res), next, frame, op_idx)
                 } else {
                     binop_eval!(
                         (RuntimeValue::bitwise_and(&y, &x, frame, self)),
@@ -1213,6 +1499,8 @@ impl VirtualMachine {
                     && let Ok(y) = IsaCheckable::try_from(&y)
                 {
                     frame.stack.push(RuntimeValue::TypeCheck(x | &y));
+                } else if let Some(res) = integer::big_integer_binop(IntegerOp::BitwiseOr, &y, &x) {
+                    binop_eval!((res), next, frame, op_idx)
                 } else {
                     binop_eval!(
                         (RuntimeValue::bitwise_or(&y, &x, frame, self)),
@@ -1254,12 +1542,19 @@ impl VirtualMachine {
                 *op_idx = n as usize;
             }
             Opcode::JumpIfArgSupplied(arg, dest) => {
-                if frame.argc > arg {
+                if frame.is_arg_supplied(arg) {
                     *op_idx = dest as usize;
                 }
             }
-            Opcode::Call(argc) => {
+            Opcode::Call(argc) | Opcode::TailCall(argc) => {
                 let x = pop_or_err!(next, frame, op_idx);
+                // a tail call either hands this frame over to the callee, which
+                // runs once this function has returned (see eval_bytecode_in_frame),
+                // or is a call like any other followed by a return
+                let is_tail_call = matches!(next, Opcode::TailCall(_));
+                if is_tail_call && x.tail_call_in_frame(argc, frame, self) {
+                    return Ok(OpcodeRunExit::Return);
+                }
                 match x.eval(argc, frame, self, false) {
                     Ok(crate::runtime_value::CallResult::Ok(_)) => {}
                     Ok(crate::runtime_value::CallResult::Exception(e)) => {
@@ -1273,10 +1568,48 @@ impl VirtualMachine {
                         }
                     }
                 }
+                if is_tail_call {
+                    return Ok(OpcodeRunExit::Return);
+                }
+            }
+            Opcode::CallNamed(argc, kwargc) => {
+                let x = pop_or_err!(next, frame, op_idx);
+                // positional arguments sit above the (value, name) pairs
+                let positional = frame.stack.pop_count(argc as usize);
+                let mut keywords = Vec::with_capacity(kwargc as usize);
+                for _ in 0..kwargc {
+                    let name = pop_or_err!(next, frame, op_idx);
+                    let val = pop_or_err!(next, frame, op_idx);
+                    let Some(name) = name.as_string() else {
+                        return build_vm_error!(VmErrorReason::UnexpectedType, next, frame, op_idx);
+                    };
+                    keywords.push((name.raw_value().clone(), val));
+                }
+                for arg in positional.into_iter().rev() {
+                    frame.stack.push(arg);
+                }
+                match x.eval_with_keywords(argc, keywords, frame, self, false) {
+                    Ok(crate::runtime_value::CallResult::Ok(_)) => {}
+                    Ok(crate::runtime_value::CallResult::Exception(e)) => {
+                        return Ok(OpcodeRunExit::Exception(e));
+                    }
+                    Err(err) => {
+                        if err.loc.is_some() {
+                            return Err(err);
+                        } else {
+                            return build_vm_error!(err.reason, next, frame, op_idx);
+                        }
+                    }
+                }
             }
             Opcode::Return => {
                 return Ok(OpcodeRunExit::Return);
             }
+            Opcode::Yield | Opcode::Await => {
+                // the yielded value stays on the stack for the caller to pick up
+                frame.resume_at = Some(*op_idx + 1);
+                return Ok(OpcodeRunExit::Return);
+            }
             Opcode::ReturnUnit => {
                 return match self.globals.create_unit_object() {
                     Ok(unit) => {
@@ -1328,6 +1661,75 @@ impl VirtualMachine {
                 let list = RuntimeValue::List(list);
                 frame.stack.push(list);
             }
+            Opcode::BuildMap(n) => {
+                let mut values = Vec::<_>::with_capacity(2 * n as usize);
+                for _ in 0..2 * n {
+                    let val = pop_or_err!(next, frame, op_idx);
+                    values.push(val);
+                }
+                let map = Map::new_with_capacity(n as usize);
+                // keys and values were pushed in source order, key first
+                while let Some(key) = values.pop() {
+                    let Some(value) = values.pop() else {
+                        return build_vm_error!(VmErrorReason::EmptyStack, next, frame, op_idx);
+                    };
+                    match map.insert(key, value, frame, self) {
+                        Ok(RunloopExit::Ok(_)) => {}
+                        Ok(RunloopExit::Exception(e)) => {
+                            return Ok(OpcodeRunExit::Exception(e));
+                        }
+                        Err(e) => {
+                            return if e.loc.is_none() {
+                                build_vm_error!(e.reason, next, frame, op_idx)
+                            } else {
+                                Err(e)
+                            };
+                        }
+                    }
+                }
+                frame.stack.push(RuntimeValue::Map(map));
+            }
+            Opcode::BuildString(n) => {
+                let mut values = Vec::<_>::with_capacity(n as usize);
+                for _ in 0..n {
+                    let val = pop_or_err!(next, frame, op_idx);
+                    values.push(val);
+                }
+                let mut result = String::new();
+                for val in values.iter().rev() {
+                    match val.as_string() {
+                        Some(s) => result.push_str(s.raw_value()),
+                        None => result.push_str(&val.prettyprint(frame, self)),
+                    }
+                }
+                frame
+                    .stack
+                    .push(RuntimeValue::String(result.as_str().into()));
+            }
+            Opcode::ListCheckLen(a, n) => {
+                let at_least = (a & LEN_AT_LEAST) == LEN_AT_LEAST;
+                let val = pop_or_err!(next, frame, op_idx);
+                let is_match = match val.as_list() {
+                    Some(list) if at_least => list.len() >= n as usize,
+                    Some(list) => list.len() == n as usize,
+                    None => false,
+                };
+                frame.stack.push(RuntimeValue::Boolean(is_match.into()));
+            }
+            Opcode::ListTail(n) => {
+                let val = pop_or_err!(next, frame, op_idx);
+                if let Some(list) = val.as_list() {
+                    let tail = List::new_with_capacity(list.len().saturating_sub(n as usize));
+                    for idx in (n as usize)..list.len() {
+                        if let Some(item) = list.get_at(idx) {
+                            tail.append(item);
+                        }
+                    }
+                    frame.stack.push(RuntimeValue::List(tail));
+                } else {
+                    return build_vm_error!(VmErrorReason::UnexpectedType, next, frame, op_idx);
+                }
+            }
             Opcode::BuildFunction => {
                 let val = pop_or_err!(next, frame, op_idx);
                 if let Some(co) = val.as_code_object() {
@@ -1390,6 +1792,73 @@ impl VirtualMachine {
                     return build_vm_error!(VmErrorReason::UnexpectedType, next, frame, op_idx);
                 }
             }
+            Opcode::RequireMember => {
+                let name = pop_or_err!(next, frame, op_idx);
+                let mixin = pop_or_err!(next, frame, op_idx);
+
+                if let (Some(name), Some(mixin)) = (name.as_string(), mixin.as_mixin()) {
+                    let name = match self.globals.intern_symbol(name.raw_value()) {
+                        Ok(name) => name,
+                        Err(e) => return build_vm_error!(e, next, frame, op_idx),
+                    };
+                    mixin.add_requirement(name);
+                } else {
+                    return build_vm_error!(VmErrorReason::UnexpectedType, next, frame, op_idx);
+                }
+            }
+            Opcode::BindGetter | Opcode::BindSetter => {
+                let name = pop_or_err!(next, frame, op_idx);
+                let accessor = pop_or_err!(next, frame, op_idx);
+                let ty = pop_or_err!(next, frame, op_idx);
+
+                if let (Some(name), Some(accessor), Some(strukt)) =
+                    (name.as_string(), accessor.as_function(), ty.as_struct())
+                {
+                    let name = match self.globals.intern_symbol(name.raw_value()) {
+                        Ok(name) => name,
+                        Err(e) => return build_vm_error!(e, next, frame, op_idx),
+                    };
+                    if matches!(next, Opcode::BindGetter) {
+                        strukt.bind_getter(name, accessor.clone());
+                    } else {
+                        strukt.bind_setter(name, accessor.clone());
+                    }
+                } else {
+                    return build_vm_error!(VmErrorReason::UnexpectedType, next, frame, op_idx);
+                }
+            }
+            Opcode::CheckMixinContracts => {
+                let ty = pop_or_err!(next, frame, op_idx);
+
+                let unmet = if let Some(strukt) = ty.as_struct() {
+                    strukt
+                        .unmet_mixin_requirement(&self.globals)
+                        .map(|unmet| (strukt.name().to_owned(), unmet))
+                } else if let Some(enumm) = ty.as_enum() {
+                    enumm
+                        .unmet_mixin_requirement(&self.globals)
+                        .map(|unmet| (enumm.name().to_owned(), unmet))
+                } else if let Some(btt) = ty.as_rust_native() {
+                    btt.unmet_mixin_requirement(&self.globals)
+                        .map(|unmet| (format!("{btt:?}"), unmet))
+                } else {
+                    // a mixin passes its requirements on to the types that include it
+                    None
+                };
+
+                if let Some((type_name, (mixin_name, member))) = unmet {
+                    let member = self.globals.resolve_symbol(member).unwrap_or("<unknown>");
+                    return build_vm_error!(
+                        VmErrorReason::MixinRequirementNotMet(
+                            type_name,
+                            format!("{mixin_name}.{member}")
+                        ),
+                        next,
+                        frame,
+                        op_idx
+                    );
+                }
+            }
             Opcode::BindCase(..) => {
                 return build_vm_error!(
                     VmErrorReason::UnknownOpcode(OPCODE_BIND_CASE),
@@ -1556,7 +2025,10 @@ impl VirtualMachine {
                 let ev = if let Some(ev) = ev.as_enum_value() {
                     ev
                 } else {
-                    return build_vm_error!(VmErrorReason::UnexpectedType, next, frame, op_idx);
+                    // a value that is not an enum is simply not this case, so that
+                    // enum patterns can be mixed with other patterns in a match
+                    frame.stack.push(RuntimeValue::Boolean(false.into()));
+                    return Ok(OpcodeRunExit::Continue);
                 };
 
                 let current_sidecar = next_sidecar
@@ -1857,7 +2329,8 @@ impl VirtualMachine {
                 } else {
                     return build_vm_error!(VmErrorReason::UnexpectedType, next, frame, op_idx);
                 };
-                match dest.lift_all_symbols_from_other(src, self) {
+                // import * brings in only the public names of the module
+                match dest.lift_all_symbols_from_other(src, false, self) {
                     Ok(_) => {}
                     Err(e) => {
                         return build_vm_error!(e, next, frame, op_idx);
@@ -1887,31 +2360,6 @@ impl VirtualMachine {
 
                     frame.stack.push(RuntimeValue::Module(mli.module.clone()));
                 } else {
-                    let import_path = match Self::resolve_import_path_to_path(
-                        ipath,
-                        this_module.get_compiled_module().widget_root_path.as_ref(),
-                    ) {
-                        Ok(ipath) => ipath,
-                        Err(err) => {
-                            return build_vm_error!(err, next, frame, op_idx);
-                        }
-                    };
-
-                    let sb = match SourceBuffer::from_path(&import_path) {
-                        Ok(sb) => sb,
-                        Err(_) => {
-                            return build_vm_error!(
-                                VmErrorReason::ImportNotAvailable(
-                                    ipath.to_owned(),
-                                    "no such file".to_owned()
-                                ),
-                                next,
-                                frame,
-                                op_idx
-                            );
-                        }
-                    };
-
                     if self.import_stack.contains(ipath) {
                         return build_vm_error!(
                             VmErrorReason::CircularImport(ipath.to_owned()),
@@ -1919,31 +2367,25 @@ impl VirtualMachine {
                             frame,
                             op_idx
                         );
-                    } else {
-                        self.import_stack.push(ipath.clone());
                     }
 
-                    let c_module = match compile_from_source(&sb, &Default::default()) {
-                        Ok(cm) => cm,
-                        Err(ces) => {
-                            let err_msg = ces
-                                .iter()
-                                .map(|x| format!("error: {x}"))
-                                .collect::<Vec<_>>()
-                                .join("\n");
-                            assert!(*ipath == self.import_stack.pop());
-                            return build_vm_error!(
-                                VmErrorReason::ImportNotAvailable(
-                                    ipath.to_owned(),
-                                    format!("module failed to compile: {err_msg}")
-                                ),
-                                next,
-                                frame,
-                                op_idx
-                            );
-                        }
+                    let c_module = match self.bundled_modules.remove(ipath) {
+                        Some((_, cm)) => cm,
+                        None => match Self::compile_imported_module(
+                            ipath,
+                            this_module.get_compiled_module().widget_root_path.as_ref(),
+                            self.options.use_module_cache,
+                        ) {
+                            Ok(cm) => cm,
+                            Err(err) => {
+                                return build_vm_error!(err, next, frame, op_idx);
+                            }
+                        },
                     };
-                    let mli = match self.load_module(&sb.name, c_module)? {
+                    let module_name = c_module.load_entry_code_object().loc.buffer.name;
+
+                    self.import_stack.push(ipath.clone());
+                    let mli = match self.load_module(&module_name, c_module)? {
                         RunloopExit::Ok(mli) => mli,
                         RunloopExit::Exception(e) => {
                             assert!(*ipath == self.import_stack.pop());
@@ -1979,7 +2421,7 @@ impl VirtualMachine {
         module: &RuntimeModule,
         frame: &mut Frame,
     ) -> ExecutionResult<RunloopExit, VmError> {
-        let mut op_counter = 0;
+        let mut op_counter = frame.resume_at.take().unwrap_or(0);
         loop {
             #[cfg(debug_assertions)]
             if self.options.tracing && self.options.dump_stack {
@@ -2056,6 +2498,7 @@ impl VirtualMachine {
                         {
                             let mut new_err = err.clone();
                             new_err.backtrace.push(lt);
+                            new_err.backtrace.note_elided_frames(frame.elided_frames);
                             new_err
                         } else {
                             err
@@ -2077,13 +2520,14 @@ impl VirtualMachine {
                         frame.stack.push(except.value);
                     }
                     None => {
-                        let new_except = if let Some(lt) =
+                        let mut new_except = if let Some(lt) =
                             frame.get_line_entry_at_pos(current_op_counter as u16)
                         {
                             except.thrown_at(lt)
                         } else {
                             except
                         };
+                        new_except.backtrace.note_elided_frames(frame.elided_frames);
                         return Ok(RunloopExit::Exception(new_except));
                     }
                 }